@@ -0,0 +1,716 @@
+//! Headless command-line front end for the Dikaios calculators.
+//!
+//! Every subcommand calls the same `dikaios_lib::calc` functions as the GUI,
+//! so both always agree. Input comes from flags or from a JSON/YAML file
+//! shaped like the matching Tauri command input.
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use dikaios_lib::calc::{
+    self, AddressFamily, CiscoSecretType, DhcpMode, DhcpOptions, DhcpServer, Diagnostic, DikaiosError, FhrpProtocol, Growth, LinuxFormat, HostRequirement, IpCalculatorInput, IpInput, Ipv6Requirement, Ipv6Size, MaskInput,
+    Platform, RedundancyOptions, TrunkPort, UnusedSpace, VlanAllocation, VlanConfig, VlanInput, VlsmInput, VlsmOptions, WirelessNetworkInput,
+    WirelessSecurity,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+#[derive(Parser)]
+#[command(name = "dikaios-cli", version, about = "IP address management calculators without the GUI")]
+struct Cli {
+    /// How to print the result
+    #[arg(short, long, value_enum, global = true, default_value_t = Format::Table)]
+    format: Format,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Table,
+    Json,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Describe the network an IPv4 or IPv6 address belongs to
+    Calc(CalcArgs),
+    /// List the usable hosts of an IPv4 subnet
+    Range(RangeArgs),
+    /// Plan variable length subnets inside a parent block
+    Vlsm(VlsmArgs),
+    /// Allocate VLAN subnets and generate the switch commands
+    Vlan(VlanArgs),
+    /// Plan a wireless network and its router and access point commands
+    Wireless(WirelessArgs),
+    /// Print the subnet mask reference table
+    Ref,
+}
+
+#[derive(Args)]
+struct CalcArgs {
+    /// Address to describe, e.g. 192.168.4.56 or 2001:db8::1
+    #[arg(long, required_unless_present = "input")]
+    address: Option<String>,
+    /// Prefix length, or for IPv4 a netmask or wildcard mask
+    #[arg(long, required_unless_present = "input")]
+    bits: Option<String>,
+    /// Read the input from a JSON or YAML file instead of flags
+    #[arg(short, long, conflicts_with_all = ["address", "bits"])]
+    input: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct RangeArgs {
+    /// Any address inside the subnet
+    #[arg(long, required_unless_present = "input")]
+    address: Option<String>,
+    /// Subnet mask as a prefix length, netmask or wildcard mask
+    #[arg(long, required_unless_present = "input")]
+    mask: Option<String>,
+    /// Number of matching hosts to skip
+    #[arg(long)]
+    offset: Option<u64>,
+    /// Number of hosts to print; without --offset or --limit every host is streamed
+    #[arg(long)]
+    limit: Option<u64>,
+    /// Only list hosts whose address contains this text
+    #[arg(long)]
+    filter: Option<String>,
+    /// Stream every matching host to this file instead of printing them
+    #[arg(short, long, conflicts_with_all = ["offset", "limit"])]
+    output: Option<PathBuf>,
+    /// Read the input from a JSON or YAML file instead of flags
+    #[arg(short, long, conflicts_with_all = ["address", "mask"])]
+    input: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct VlsmArgs {
+    /// Parent network address, e.g. 192.168.1.0 or 2001:db8::
+    #[arg(long, required_unless_present = "input")]
+    network: Option<String>,
+    /// Parent mask as a prefix length, netmask or wildcard mask
+    #[arg(long, required_unless_present = "input")]
+    cidr: Option<String>,
+    /// IPv4 host counts, comma separated, each optionally named as
+    /// "NAME:HOSTS" and given its own growth as "HOSTS+GROWTH", e.g.
+    /// "Sales LAN:50+30%,WAN link:2"
+    #[arg(long, value_delimiter = ',')]
+    hosts: Vec<HostRequirement>,
+    /// Room to grow for every IPv4 subnet, e.g. "+30%" or "double"
+    #[arg(long)]
+    growth: Option<Growth>,
+    /// Addresses to reserve at the start of every IPv4 subnet
+    #[arg(long)]
+    reserved: Option<u32>,
+    /// Plan 2-host links as /31 and single hosts such as loopbacks as /32
+    #[arg(long)]
+    point_to_point: bool,
+    /// IPv6 requirements, comma separated: "/56" for a prefix length or a
+    /// plain number of /64 subnets, each optionally prefixed with "NAME:"
+    #[arg(long, value_delimiter = ',', value_parser = parse_ipv6_requirement)]
+    ipv6: Vec<Ipv6Requirement>,
+    /// List the subnets in input order instead of allocation order
+    #[arg(long)]
+    input_order: bool,
+    /// Read the input from a JSON or YAML file instead of flags
+    #[arg(short, long, conflicts_with_all = ["network", "cidr", "hosts", "ipv6", "growth", "reserved", "point_to_point"])]
+    input: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct VlanArgs {
+    /// Parent network address
+    #[arg(long, required_unless_present = "input")]
+    network: Option<String>,
+    /// Parent mask as a prefix length, netmask or wildcard mask
+    #[arg(long, required_unless_present = "input")]
+    cidr: Option<String>,
+    /// A VLAN as ID:NAME:HOSTS, e.g. 10:Sales:50; repeat for more VLANs
+    #[arg(long = "vlan", value_parser = parse_vlan)]
+    vlans: Vec<VlanConfig>,
+    /// Access ports of a VLAN as VLAN=PORTS, e.g. 10=Gi1/0/1-12; repeat for
+    /// more ports
+    #[arg(long = "access", value_parser = parse_access_ports)]
+    access_ports: Vec<(u32, String)>,
+    /// A trunk as INTERFACE[:ALLOWED[:NATIVE]], e.g. Gi1/0/48:10,20:99;
+    /// carries every VLAN when ALLOWED is empty; repeat for more trunks
+    #[arg(long = "trunk", value_parser = parse_trunk)]
+    trunks: Vec<TrunkPort>,
+    /// Configuration syntax: cisco_ios, juniper_junos, arista_eos,
+    /// mikrotik_routeros or vyos; defaults to the input file's or Cisco IOS
+    #[arg(long)]
+    platform: Option<Platform>,
+    /// Print Linux subinterface configuration instead: netplan,
+    /// systemd_networkd, network_manager or ifupdown
+    #[arg(long)]
+    linux: Option<LinuxFormat>,
+    /// Parent interface of the VLAN subinterfaces for --linux
+    #[arg(long, default_value = "eth0", requires = "linux")]
+    parent_interface: String,
+    /// VLAN whose gateway becomes the default route for --linux; no default
+    /// route is written without it
+    #[arg(long, value_name = "VLAN", requires = "linux")]
+    uplink_vlan: Option<u32>,
+    /// Serve DHCP on every VLAN from pools on the switch
+    #[arg(long, group = "dhcp_mode")]
+    dhcp: bool,
+    /// Relay DHCP on every VLAN to this central server instead
+    #[arg(long, value_name = "SERVER", group = "dhcp_mode")]
+    dhcp_relay: Option<String>,
+    /// DNS servers handed out by DHCP, comma-separated
+    #[arg(long, value_delimiter = ',', requires = "dhcp_mode")]
+    dns: Vec<String>,
+    /// Domain name handed out by DHCP
+    #[arg(long, requires = "dhcp_mode")]
+    domain: Option<String>,
+    /// DHCP lease time in seconds
+    #[arg(long, default_value_t = calc::DEFAULT_LEASE_TIME, requires = "dhcp_mode")]
+    lease: u32,
+    /// Addresses after the gateway to keep out of each DHCP pool
+    #[arg(long, default_value_t = 0, requires = "dhcp_mode")]
+    dhcp_exclude: u32,
+    /// Print the DHCP server configuration instead: kea or isc_dhcpd
+    #[arg(long)]
+    dhcp_server: Option<DhcpServer>,
+    /// Share every gateway between two routers with hsrp, vrrp or glbp
+    #[arg(long)]
+    redundancy: Option<FhrpProtocol>,
+    /// Hostnames of the two redundant routers as FIRST,SECOND
+    #[arg(long, value_parser = parse_routers, requires = "redundancy")]
+    routers: Option<[String; 2]>,
+    /// Read the input from a JSON or YAML file instead of flags
+    #[arg(short, long, conflicts_with_all = ["network", "cidr", "vlans", "access_ports", "trunks", "dhcp_mode", "redundancy"])]
+    input: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct WirelessArgs {
+    /// Network address; 192.168.1.0/24 is used when omitted
+    #[arg(long, requires = "cidr")]
+    network: Option<String>,
+    /// Network mask as a prefix length, netmask or wildcard mask
+    #[arg(long, requires = "network")]
+    cidr: Option<String>,
+    /// Wireless network name
+    #[arg(long, required_unless_present = "input")]
+    ssid: Option<String>,
+    /// WPA passphrase, or a pre-shared key of 64 hex digits
+    #[arg(long, required_unless_present = "input")]
+    password: Option<String>,
+    /// Replace the passphrase with ******** and print the derived WPA PSK
+    #[arg(long)]
+    mask: bool,
+    /// Enable secret of the router and the access point, written hashed
+    #[arg(long)]
+    enable_secret: Option<String>,
+    /// Hash of the enable secret: type8 (PBKDF2-SHA256) or type9 (scrypt)
+    #[arg(long, default_value = "type9", requires = "enable_secret")]
+    secret_type: CiscoSecretType,
+    #[arg(long, default_value = "WirelessRouter")]
+    router_hostname: String,
+    #[arg(long, default_value = "AccessPoint")]
+    ap_hostname: String,
+    /// Wireless security: wpa2, wpa3 or wpa2_wpa3
+    #[arg(long, default_value = "wpa2")]
+    security: WirelessSecurity,
+    /// Two-letter country code of the Linux access point
+    #[arg(long, default_value = "US")]
+    country: String,
+    /// Wi-Fi channel of the Linux access point
+    #[arg(long, default_value_t = 6)]
+    channel: u32,
+    /// Wireless interface of the Linux access point
+    #[arg(long, default_value = "wlan0")]
+    interface: String,
+    /// Print hostapd.conf and dnsmasq.conf for a Linux access point instead
+    #[arg(long)]
+    linux: bool,
+    /// With --linux, print a Kea DHCPv4 configuration instead of dnsmasq.conf
+    #[arg(long, requires = "linux")]
+    kea: bool,
+    /// Write the join QR code to this .svg or .png file instead
+    #[arg(long)]
+    qr: Option<PathBuf>,
+    /// Write a printable onboarding card to this HTML file instead
+    #[arg(long)]
+    card: Option<PathBuf>,
+    /// Read the input from a JSON or YAML file instead of flags
+    #[arg(short, long, conflicts_with_all = ["network", "cidr", "ssid", "password"])]
+    input: Option<PathBuf>,
+}
+
+#[derive(Serialize)]
+struct VlanOutput {
+    allocations: Vec<VlanAllocation>,
+    trunks: Vec<TrunkPort>,
+    platform: Platform,
+    commands: String,
+    unused: UnusedSpace,
+    warnings: Vec<Diagnostic>,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match run(cli.command, cli.format) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            match e.downcast_ref::<DikaiosError>() {
+                Some(DikaiosError::ValidationFailed { diagnostics, .. }) => diagnostics.iter().for_each(print_diagnostic),
+                _ => eprintln!("error: {}", e),
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(command: Command, format: Format) -> Result<(), Box<dyn Error>> {
+    match command {
+        Command::Calc(args) => {
+            let input = match args.input {
+                Some(path) => read_input(&path)?,
+                None => IpCalculatorInput {
+                    ip_address: args.address.unwrap_or_default(),
+                    network_bits: args.bits.unwrap_or_default(),
+                },
+            };
+            let info = calc::calculate_ip_info(&input.ip_address, &input.network_bits)?;
+
+            if format == Format::Json {
+                return print_json(&info);
+            }
+
+            let mut fields = vec![
+                ("Family", match info.family {
+                    AddressFamily::Ipv4 => "IPv4".to_string(),
+                    AddressFamily::Ipv6 => "IPv6".to_string(),
+                }),
+                ("Address", info.address),
+            ];
+            fields.extend(info.expanded.map(|expanded| ("Expanded", expanded)));
+            fields.extend([
+                ("Netmask", info.netmask),
+                ("Wildcard", info.wildcard),
+                ("Network", format!("{}/{}", info.network, info.prefix_length)),
+            ]);
+            fields.extend(info.broadcast.map(|broadcast| ("Broadcast", broadcast)));
+            fields.extend([
+                ("HostMin", info.hostmin),
+                ("HostMax", info.hostmax),
+            ]);
+            fields.extend(info.hosts.map(|hosts| ("Hosts/Net", hosts.to_string())));
+            fields.push(("Addresses", info.address_count));
+            fields.extend(info.ip_class.map(|class| ("IP Class", class)));
+            fields.extend(info.scope.map(|scope| ("Scope", scope)));
+            fields.push(("Type", if info.is_private { "Private" } else { "Public" }.to_string()));
+            print_fields(&fields);
+        }
+        Command::Range(args) => {
+            let mut input = match args.input {
+                Some(path) => read_input(&path)?,
+                None => IpInput::new(args.address.unwrap_or_default(), args.mask.unwrap_or_default()),
+            };
+            input.offset = args.offset.unwrap_or(input.offset);
+            input.limit = args.limit.or(input.limit);
+            input.filter = args.filter.or(input.filter);
+
+            if let Some(path) = args.output {
+                let range = calc::host_range(&input.ip_address, &input.subnet_mask)?;
+                let file = File::create(&path)
+                    .map_err(|e| format!("Cannot create {}: {}", path.display(), e))?;
+                let written = range.write_to(input.filter.as_deref(), file)?;
+                eprintln!("Wrote {} hosts to {}", written, path.display());
+                return Ok(());
+            }
+
+            let paginate = format == Format::Json || input.offset > 0 || input.limit.is_some();
+            if !paginate {
+                let range = calc::host_range(&input.ip_address, &input.subnet_mask)?;
+                range.write_to(input.filter.as_deref(), io::stdout().lock())?;
+                return Ok(());
+            }
+
+            let page = calc::calculate_host_page(&input)?;
+            if format == Format::Json {
+                return print_json(&page);
+            }
+
+            for ip in &page.hosts {
+                println!("{}", ip);
+            }
+            let shown = format!("Hosts {}-{}", page.offset + 1, page.offset + page.hosts.len() as u64);
+            match page.total {
+                Some(total) => eprintln!("{} of {}", shown, total),
+                None if page.truncated => {
+                    eprintln!("{}, searched the first {} hosts only", shown, calc::MAX_FILTER_SCAN)
+                }
+                None => eprintln!("{}, more follow", shown),
+            }
+        }
+        Command::Vlsm(args) => {
+            let input = match args.input {
+                Some(path) => read_input(&path)?,
+                None => VlsmInput {
+                    base_network: args.network.unwrap_or_default(),
+                    base_cidr: MaskInput::from(args.cidr.unwrap_or_default()),
+                    host_requirements: args.hosts,
+                    ipv6_requirements: args.ipv6,
+                    options: VlsmOptions {
+                        growth: args.growth.unwrap_or_default(),
+                        reserved: args.reserved.unwrap_or_default(),
+                        point_to_point: args.point_to_point,
+                    },
+                },
+            };
+            let mut plan = calc::calculate_vlsm_plan(&input)?;
+            if args.input_order {
+                plan.subnets.sort_by_key(|subnet| subnet.index);
+            }
+
+            if format == Format::Json {
+                return print_json(&plan);
+            }
+
+            let rows = plan.subnets.into_iter()
+                .map(|subnet| vec![
+                    (subnet.index + 1).to_string(),
+                    subnet.name,
+                    format!("{}/{}", subnet.network, subnet.cidr),
+                    subnet.subnet_mask,
+                    subnet.first_host,
+                    subnet.last_host,
+                    subnet.broadcast.unwrap_or_default(),
+                    subnet.required_hosts.map(|hosts| hosts.to_string()).unwrap_or_default(),
+                    subnet.planned_hosts.map(|hosts| hosts.to_string()).unwrap_or_default(),
+                    subnet.reserved_hosts.map(|hosts| hosts.to_string()).unwrap_or_default(),
+                    subnet.usable_hosts.map(|hosts| hosts.to_string()).unwrap_or(subnet.address_count),
+                ])
+                .collect::<Vec<_>>();
+            print_table(
+                &["#", "Name", "Subnet", "Mask", "First", "Last", "Broadcast", "Required", "Planned", "Reserved", "Size"],
+                &rows,
+            );
+            print_unused(&plan.unused);
+        }
+        Command::Vlan(args) => {
+            let mut input = match args.input {
+                Some(path) => read_input(&path)?,
+                None => {
+                    let mut vlan_configs = args.vlans;
+                    for (vlan_id, ports) in args.access_ports {
+                        let vlan = vlan_configs.iter_mut()
+                            .find(|vlan| vlan.vlan_id == vlan_id)
+                            .ok_or_else(|| format!("--access refers to VLAN {}, which has no --vlan", vlan_id))?;
+                        vlan.access_ports.push(ports);
+                    }
+                    let dhcp = (args.dhcp || args.dhcp_relay.is_some()).then(|| DhcpOptions {
+                        mode: if args.dhcp_relay.is_some() { DhcpMode::Relay } else { DhcpMode::Pool },
+                        server: args.dhcp_relay,
+                        dns_servers: args.dns,
+                        domain_name: args.domain,
+                        lease_time: args.lease,
+                        excluded_addresses: args.dhcp_exclude,
+                        ..DhcpOptions::default()
+                    });
+                    VlanInput {
+                        base_network: args.network.unwrap_or_default(),
+                        base_cidr: MaskInput::from(args.cidr.unwrap_or_default()),
+                        vlan_configs,
+                        trunks: args.trunks,
+                        platform: Platform::default(),
+                        dhcp,
+                        redundancy: args.redundancy.map(|protocol| RedundancyOptions {
+                            routers: args.routers.unwrap_or_else(|| RedundancyOptions::new(protocol).routers),
+                            protocol,
+                        }),
+                    }
+                }
+            };
+            input.platform = args.platform.unwrap_or(input.platform);
+            let plan = calc::calculate_vlan_plan(&input)?;
+            plan.warnings.iter().for_each(print_diagnostic);
+
+            if let Some(linux) = args.linux {
+                let files =
+                    calc::export_linux_config(&plan.allocations, &args.parent_interface, args.uplink_vlan, linux)?;
+                if format == Format::Json {
+                    return print_json(&files);
+                }
+
+                for file in files {
+                    println!("# {}", file.path);
+                    println!("{}", file.contents);
+                }
+                return Ok(());
+            }
+
+            if let Some(server) = args.dhcp_server {
+                if plan.dhcp.is_none() {
+                    return Err("--dhcp-server needs DHCP to be enabled with --dhcp or --dhcp-relay".into());
+                }
+                print!("{}", calc::dhcp_server_config(&plan.allocations, server));
+                return Ok(());
+            }
+
+            let commands = plan.render(input.platform);
+
+            if format == Format::Json {
+                return print_json(&VlanOutput {
+                    allocations: plan.allocations,
+                    trunks: plan.trunks,
+                    platform: input.platform,
+                    commands,
+                    unused: plan.unused,
+                    warnings: plan.warnings,
+                });
+            }
+
+            let rows = plan.allocations.into_iter()
+                .map(|allocation| vec![
+                    allocation.vlan_id.to_string(),
+                    allocation.vlan_name,
+                    format!("{}/{}", allocation.network, allocation.cidr),
+                    allocation.gateway,
+                    format!("{} - {}", allocation.first_host, allocation.last_host),
+                    allocation.broadcast,
+                    allocation.usable_hosts.to_string(),
+                ])
+                .collect::<Vec<_>>();
+            print_table(&["VLAN", "Name", "Subnet", "Gateway", "Hosts", "Broadcast", "Usable"], &rows);
+            print_unused(&plan.unused);
+            println!();
+            print!("{}", commands);
+        }
+        Command::Wireless(args) => {
+            let input = match args.input {
+                Some(path) => read_input(&path)?,
+                None => WirelessNetworkInput {
+                    auto_generate: args.network.is_none(),
+                    base_network: args.network,
+                    base_cidr: args.cidr.map(MaskInput::from),
+                    ssid: args.ssid.unwrap_or_default(),
+                    wpa_password: args.password.unwrap_or_default(),
+                    router_hostname: args.router_hostname,
+                    ap_hostname: args.ap_hostname,
+                    security: args.security,
+                    country_code: args.country,
+                    channel: args.channel,
+                    interface: args.interface,
+                    mask_passphrase: args.mask,
+                    enable_secret: args.enable_secret,
+                    secret_type: args.secret_type,
+                },
+            };
+            let interface = input.interface.clone();
+            let config = calc::generate_wireless_config(input.clone())?;
+            config.warnings.iter().for_each(print_diagnostic);
+
+            if args.qr.is_some() || args.card.is_some() {
+                let onboarding = calc::generate_wifi_onboarding(&input)?;
+                if let Some(path) = args.qr {
+                    let is_png = path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("png"));
+                    let contents = if is_png {
+                        calc::qr_png(&onboarding.payload, calc::QR_PNG_MODULE_SIZE)?
+                    } else {
+                        onboarding.qr_svg.into_bytes()
+                    };
+                    fs::write(&path, contents).map_err(|e| format!("Cannot write {}: {}", path.display(), e))?;
+                    eprintln!("Wrote the QR code to {}", path.display());
+                }
+                if let Some(path) = args.card {
+                    fs::write(&path, onboarding.card_html)
+                        .map_err(|e| format!("Cannot write {}: {}", path.display(), e))?;
+                    eprintln!("Wrote the onboarding card to {}", path.display());
+                }
+                return Ok(());
+            }
+
+            if args.linux {
+                println!("# /etc/hostapd/hostapd.conf");
+                println!("{}", config.hostapd_conf);
+                if args.kea {
+                    println!("# /etc/kea/kea-dhcp4.conf");
+                    print!("{}", config.kea_config);
+                } else {
+                    println!("# /etc/dnsmasq.d/{}.conf", interface);
+                    print!("{}", config.dnsmasq_conf);
+                }
+                return Ok(());
+            }
+
+            if format == Format::Json {
+                return print_json(&config);
+            }
+
+            print_fields(&[
+                ("SSID", config.ssid),
+                ("Security", config.security.to_string()),
+                ("WPA PSK", config.psk),
+                ("Network", format!("{}/{}", config.network, config.cidr)),
+                ("Subnet Mask", config.subnet_mask),
+                ("Router", config.router_ip),
+                ("Access Point", config.ap_ip),
+                ("DHCP Pool", format!("{} - {}", config.dhcp_start, config.dhcp_end)),
+                ("Broadcast", config.broadcast),
+                ("Usable Hosts", config.usable_hosts.to_string()),
+            ]);
+            println!("\n{}\n\n{}\n", config.router_commands, config.ap_commands);
+            for step in config.setup_steps {
+                println!("{}", step);
+            }
+        }
+        Command::Ref => {
+            let references = calc::generate_subnet_references();
+
+            if format == Format::Json {
+                return print_json(&references);
+            }
+
+            let rows = references.into_iter()
+                .map(|reference| vec![
+                    reference.cidr,
+                    reference.subnet_mask,
+                    reference.total_hosts.to_string(),
+                    reference.usable_hosts.to_string(),
+                ])
+                .collect::<Vec<_>>();
+            print_table(&["CIDR", "Subnet Mask", "Total", "Usable"], &rows);
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads a command input from a `.yaml`/`.yml` file, or JSON otherwise.
+fn read_input<T: DeserializeOwned>(path: &Path) -> Result<T, String> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("yaml" | "yml") => serde_yaml::from_str(&contents)
+            .map_err(|e| format!("Invalid YAML in {}: {}", path.display(), e)),
+        _ => serde_json::from_str(&contents)
+            .map_err(|e| format!("Invalid JSON in {}: {}", path.display(), e)),
+    }
+}
+
+fn parse_ipv6_requirement(value: &str) -> Result<Ipv6Requirement, String> {
+    let (name, size) = value.rsplit_once(':').unwrap_or(("", value));
+    let size = match size.trim().strip_prefix('/') {
+        Some(prefix) => prefix.parse()
+            .map(Ipv6Size::PrefixLength)
+            .map_err(|_| format!("Invalid prefix length: {}", value))?,
+        None => size.trim().parse()
+            .map(Ipv6Size::Subnets64)
+            .map_err(|_| format!("Invalid number of /64 subnets: {}", value))?,
+    };
+    Ok(Ipv6Requirement::new(name.trim(), size))
+}
+
+fn parse_vlan(value: &str) -> Result<VlanConfig, String> {
+    let mut parts = value.splitn(3, ':');
+    let (Some(id), Some(name), Some(hosts)) = (parts.next(), parts.next(), parts.next()) else {
+        return Err("VLAN must be written as ID:NAME:HOSTS".to_string());
+    };
+
+    Ok(VlanConfig {
+        vlan_id: id.parse().map_err(|_| format!("Invalid VLAN ID: {}", id))?,
+        vlan_name: name.to_string(),
+        required_hosts: hosts.parse().map_err(|_| format!("Invalid host count: {}", hosts))?,
+        access_ports: Vec::new(),
+    })
+}
+
+fn parse_routers(value: &str) -> Result<[String; 2], String> {
+    match value.split_once(',') {
+        Some((first, second)) => Ok([first.trim().to_string(), second.trim().to_string()]),
+        None => Err("Routers must be written as FIRST,SECOND".to_string()),
+    }
+}
+
+fn parse_access_ports(value: &str) -> Result<(u32, String), String> {
+    let Some((id, ports)) = value.split_once('=') else {
+        return Err("Access ports must be written as VLAN=PORTS".to_string());
+    };
+    let vlan_id = id.trim().parse().map_err(|_| format!("Invalid VLAN ID: {}", id))?;
+    Ok((vlan_id, ports.trim().to_string()))
+}
+
+fn parse_trunk(value: &str) -> Result<TrunkPort, String> {
+    // Interface names contain slashes but no colons, so split on those
+    let mut parts = value.splitn(3, ':');
+    let interface = parts.next().unwrap_or_default().trim();
+    if interface.is_empty() {
+        return Err("Trunk must be written as INTERFACE[:ALLOWED[:NATIVE]]".to_string());
+    }
+    let allowed_vlans = parts.next()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(|id| id.parse().map_err(|_| format!("Invalid VLAN ID: {}", id)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let native_vlan = parts.next()
+        .map(|id| id.trim().parse().map_err(|_| format!("Invalid native VLAN ID: {}", id)))
+        .transpose()?;
+
+    Ok(TrunkPort { interface: interface.to_string(), allowed_vlans, native_vlan })
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<(), Box<dyn Error>> {
+    let json = serde_json::to_string_pretty(value)?;
+    println!("{}", json);
+    Ok(())
+}
+
+fn print_fields(fields: &[(&str, String)]) {
+    let width = fields.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+    for (label, value) in fields {
+        println!("{:<width$}  {}", format!("{}:", label), value, width = width + 1);
+    }
+}
+
+fn print_diagnostic(diagnostic: &Diagnostic) {
+    eprintln!("{}: {}", diagnostic.severity, diagnostic);
+}
+
+fn print_unused(unused: &UnusedSpace) {
+    println!();
+    if unused.blocks.is_empty() {
+        println!("Unused: none");
+    } else {
+        println!("Unused: {} addresses in {}", unused.address_count, unused.blocks.join(", "));
+    }
+}
+
+fn print_table(headers: &[&str], rows: &[Vec<String>]) {
+    let mut widths: Vec<usize> = headers.iter().map(|header| header.len()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let line = |cells: Vec<&str>| {
+        cells.iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    println!("{}", line(headers.to_vec()));
+    let separators: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+    println!("{}", line(separators.iter().map(String::as_str).collect()));
+    for row in rows {
+        println!("{}", line(row.iter().map(String::as_str).collect()));
+    }
+}
@@ -0,0 +1,283 @@
+//! DHCP for the VLANs of a plan.
+//!
+//! Every VLAN that DHCP is enabled for gets a [`DhcpScope`]: the pool of
+//! addresses handed out, which starts at the first host after any addresses
+//! excluded for static hosts and runs to the last host, together with the
+//! matching Kea DHCPv4 `subnet4` entry and ISC dhcpd `subnet` declaration.
+//!
+//! In [`DhcpMode::Pool`] the Cisco IOS renderer turns the scopes into
+//! `ip dhcp pool` blocks so the switch serves the VLANs itself. In
+//! [`DhcpMode::Relay`] the Cisco IOS and Arista EOS renderers add an
+//! `ip helper-address` to every VLAN interface instead, and the scopes are
+//! meant for the central server. The other platforms only get the scopes.
+
+use super::diagnostic::Diagnostic;
+use super::error::DikaiosError;
+use super::prefix::parse_ipv4;
+use super::vlan::{VlanAllocation, VlanPlan};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fmt;
+use std::net::Ipv4Addr;
+use std::str::FromStr;
+
+/// Lease time in seconds when the input gives none.
+pub const DEFAULT_LEASE_TIME: u32 = 86_400;
+
+/// The shortest lease time in seconds; Cisco IOS counts leases in minutes.
+pub const MIN_LEASE_TIME: u32 = 60;
+
+/// Who hands out the addresses of the VLANs.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DhcpMode {
+    /// The switch serves every VLAN from its own pool.
+    #[default]
+    Pool,
+    /// The switch relays DHCP requests to a central `server`.
+    Relay,
+}
+
+/// DHCP settings shared by every VLAN of a plan.
+///
+/// An empty `vlans` enables DHCP on every VLAN of the plan.
+/// `excluded_addresses` counts the addresses after the gateway, or after the
+/// router addresses of a redundant pair, that stay out of the pool for
+/// statically addressed hosts.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct DhcpOptions {
+    #[serde(default)]
+    pub mode: DhcpMode,
+    #[serde(default)]
+    pub server: Option<String>,
+    #[serde(default)]
+    pub vlans: Vec<u32>,
+    #[serde(default)]
+    pub dns_servers: Vec<String>,
+    #[serde(default)]
+    pub domain_name: Option<String>,
+    #[serde(default = "default_lease_time")]
+    pub lease_time: u32,
+    #[serde(default)]
+    pub excluded_addresses: u32,
+}
+
+fn default_lease_time() -> u32 {
+    DEFAULT_LEASE_TIME
+}
+
+impl Default for DhcpOptions {
+    fn default() -> Self {
+        DhcpOptions {
+            mode: DhcpMode::default(),
+            server: None,
+            vlans: Vec::new(),
+            dns_servers: Vec::new(),
+            domain_name: None,
+            lease_time: DEFAULT_LEASE_TIME,
+            excluded_addresses: 0,
+        }
+    }
+}
+
+/// The DHCP service of one VLAN.
+///
+/// Addresses from the gateway through `excluded_end` are kept out of the
+/// pool `pool_start..=pool_end`. `kea` is the Kea DHCPv4 `subnet4` entry and
+/// `dhcpd` the ISC dhcpd `subnet` declaration for the VLAN.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct DhcpScope {
+    pub pool_start: String,
+    pub pool_end: String,
+    pub excluded_end: String,
+    pub dns_servers: Vec<String>,
+    pub domain_name: Option<String>,
+    pub lease_time: u32,
+    pub kea: serde_json::Value,
+    pub dhcpd: String,
+}
+
+/// The DHCP server software to write a configuration for.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum DhcpServer {
+    Kea,
+    IscDhcpd,
+}
+
+impl DhcpServer {
+    pub const ALL: [DhcpServer; 2] = [DhcpServer::Kea, DhcpServer::IscDhcpd];
+
+    /// The identifier used in inputs and on the command line.
+    pub fn id(&self) -> &'static str {
+        match self {
+            DhcpServer::Kea => "kea",
+            DhcpServer::IscDhcpd => "isc_dhcpd",
+        }
+    }
+}
+
+impl fmt::Display for DhcpServer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DhcpServer::Kea => "Kea DHCPv4",
+            DhcpServer::IscDhcpd => "ISC dhcpd",
+        })
+    }
+}
+
+impl FromStr for DhcpServer {
+    type Err = DikaiosError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let id = s.trim().to_ascii_lowercase().replace('-', "_");
+        Self::ALL.into_iter()
+            .find(|server| server.id() == id)
+            .ok_or_else(|| DikaiosError::InvalidInput {
+                field: "format",
+                message: format!(
+                    "Unknown DHCP server \"{}\", expected one of {}",
+                    s.trim(),
+                    Self::ALL.map(|server| server.id()).join(", ")
+                ),
+            })
+    }
+}
+
+/// The VLANs of a computed plan and the server to configure for them.
+#[derive(Debug, Deserialize, Clone)]
+pub struct DhcpExportInput {
+    pub allocations: Vec<VlanAllocation>,
+    pub server: DhcpServer,
+}
+
+/// Enables DHCP on the VLANs of `plan` that `options` selects and keeps the
+/// options in the plan for the renderers.
+///
+/// VLANs that `options` names but the plan lacks are reported in the plan's
+/// warnings.
+pub fn configure_dhcp(plan: &mut VlanPlan, options: &DhcpOptions) -> Result<(), DikaiosError> {
+    if options.mode == DhcpMode::Relay {
+        match options.server.as_deref().map(str::trim) {
+            Some(server) if !server.is_empty() => {
+                parse_ipv4(server, "dhcp_server")?;
+            }
+            _ => return Err(DikaiosError::Missing { field: "dhcp_server" }),
+        }
+    }
+    for server in &options.dns_servers {
+        parse_ipv4(server, "dns_servers")?;
+    }
+    if let Some(domain_name) = &options.domain_name {
+        if domain_name.trim().is_empty() || domain_name.contains(|c: char| c.is_whitespace() || c == '"') {
+            return Err(DikaiosError::InvalidInput {
+                field: "domain_name",
+                message: format!("\"{}\" is not a valid domain name", domain_name),
+            });
+        }
+    }
+    if options.lease_time < MIN_LEASE_TIME {
+        return Err(DikaiosError::OutOfRange { field: "lease_time", min: MIN_LEASE_TIME as u64, max: u32::MAX as u64 });
+    }
+
+    for &vlan_id in &options.vlans {
+        if plan.allocation(vlan_id).is_none() {
+            plan.warnings.push(
+                Diagnostic::warning("dhcp", format!(
+                    "DHCP is enabled for VLAN {}, which is not part of the plan", vlan_id
+                ))
+                .for_vlan(vlan_id)
+                .suggest(format!("Remove VLAN {} from the DHCP VLANs", vlan_id)),
+            );
+        }
+    }
+
+    for allocation in &mut plan.allocations {
+        if options.vlans.is_empty() || options.vlans.contains(&allocation.vlan_id) {
+            allocation.dhcp = Some(dhcp_scope(allocation, options)?);
+        }
+    }
+    plan.dhcp = Some(options.clone());
+
+    Ok(())
+}
+
+fn dhcp_scope(allocation: &VlanAllocation, options: &DhcpOptions) -> Result<DhcpScope, DikaiosError> {
+    let first_host = u32::from(parse_ipv4(&allocation.first_host, "first_host")?);
+    let last_host = u32::from(parse_ipv4(&allocation.last_host, "last_host")?);
+
+    let excluded_end = first_host as u64 - 1 + options.excluded_addresses as u64;
+    if excluded_end >= last_host as u64 {
+        return Err(DikaiosError::InvalidInput {
+            field: "excluded_addresses",
+            message: format!(
+                "VLAN {} ({}) has no addresses left for a DHCP pool after excluding {}",
+                allocation.vlan_id, allocation.vlan_name, options.excluded_addresses
+            ),
+        });
+    }
+    let excluded_end = Ipv4Addr::from(excluded_end as u32);
+    let pool_start = Ipv4Addr::from(u32::from(excluded_end) + 1);
+    let pool_end = Ipv4Addr::from(last_host);
+
+    let dns_servers: Vec<String> = options.dns_servers.iter().map(|server| server.trim().to_string()).collect();
+    let domain_name = options.domain_name.as_ref().map(|name| name.trim().to_string());
+
+    let mut option_data = vec![json!({ "name": "routers", "data": allocation.gateway })];
+    if !dns_servers.is_empty() {
+        option_data.push(json!({ "name": "domain-name-servers", "data": dns_servers.join(", ") }));
+    }
+    if let Some(domain_name) = &domain_name {
+        option_data.push(json!({ "name": "domain-name", "data": domain_name }));
+    }
+    let kea = json!({
+        "id": allocation.vlan_id,
+        "subnet": format!("{}/{}", allocation.network, allocation.cidr),
+        "pools": [{ "pool": format!("{} - {}", pool_start, pool_end) }],
+        "option-data": option_data,
+        "valid-lifetime": options.lease_time,
+        "user-context": { "vlan": allocation.vlan_id, "name": allocation.vlan_name },
+    });
+
+    let mut dhcpd = format!("# VLAN {} - {}\n", allocation.vlan_id, allocation.vlan_name);
+    dhcpd.push_str(&format!("subnet {} netmask {} {{\n", allocation.network, allocation.subnet_mask));
+    dhcpd.push_str(&format!("  range {} {};\n", pool_start, pool_end));
+    dhcpd.push_str(&format!("  option routers {};\n", allocation.gateway));
+    dhcpd.push_str(&format!("  option broadcast-address {};\n", allocation.broadcast));
+    if !dns_servers.is_empty() {
+        dhcpd.push_str(&format!("  option domain-name-servers {};\n", dns_servers.join(", ")));
+    }
+    if let Some(domain_name) = &domain_name {
+        dhcpd.push_str(&format!("  option domain-name \"{}\";\n", domain_name));
+    }
+    dhcpd.push_str(&format!("  default-lease-time {};\n", options.lease_time));
+    dhcpd.push_str(&format!("  max-lease-time {};\n", options.lease_time));
+    dhcpd.push_str("}\n");
+
+    Ok(DhcpScope {
+        pool_start: pool_start.to_string(),
+        pool_end: pool_end.to_string(),
+        excluded_end: excluded_end.to_string(),
+        dns_servers,
+        domain_name,
+        lease_time: options.lease_time,
+        kea,
+        dhcpd,
+    })
+}
+
+/// The configuration that serves every VLAN of `allocations` with a DHCP
+/// scope from `server`: a Kea `Dhcp4` object holding the `subnet4` entries,
+/// or the dhcpd `subnet` declarations one after another.
+pub fn dhcp_server_config(allocations: &[VlanAllocation], server: DhcpServer) -> String {
+    let scopes = allocations.iter().filter_map(|allocation| allocation.dhcp.as_ref());
+
+    match server {
+        DhcpServer::Kea => {
+            let subnets: Vec<&serde_json::Value> = scopes.map(|scope| &scope.kea).collect();
+            let config = json!({ "Dhcp4": { "subnet4": subnets } });
+            serde_json::to_string_pretty(&config).unwrap_or_default() + "\n"
+        }
+        DhcpServer::IscDhcpd => scopes.map(|scope| scope.dhcpd.as_str()).collect::<Vec<_>>().join("\n"),
+    }
+}
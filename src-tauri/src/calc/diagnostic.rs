@@ -0,0 +1,79 @@
+//! Findings of the validation passes that check a whole input at once.
+//!
+//! A validation pass collects every problem it finds instead of stopping at
+//! the first one, so the page can show them all together. Errors make the
+//! calculation fail with [`DikaiosError::ValidationFailed`]; warnings are
+//! returned alongside the result.
+//!
+//! [`DikaiosError::ValidationFailed`]: super::DikaiosError::ValidationFailed
+
+use serde::Serialize;
+use std::fmt;
+
+/// How serious a [`Diagnostic`] is.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// The input cannot be used as given.
+    Error,
+    /// The input works but is probably not what was meant.
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        })
+    }
+}
+
+/// One problem with an input.
+///
+/// `field` is the offending input as in [`DikaiosError`](super::DikaiosError),
+/// `vlan_id` the VLAN it concerns, if any, and `suggestion` a way to fix it.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub field: &'static str,
+    pub vlan_id: Option<u32>,
+    pub message: String,
+    pub suggestion: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn error(field: &'static str, message: impl Into<String>) -> Self {
+        Diagnostic { severity: Severity::Error, field, vlan_id: None, message: message.into(), suggestion: None }
+    }
+
+    pub fn warning(field: &'static str, message: impl Into<String>) -> Self {
+        Diagnostic { severity: Severity::Warning, field, vlan_id: None, message: message.into(), suggestion: None }
+    }
+
+    /// The same diagnostic, concerning the VLAN `vlan_id`.
+    pub fn for_vlan(mut self, vlan_id: u32) -> Self {
+        self.vlan_id = Some(vlan_id);
+        self
+    }
+
+    /// The same diagnostic, suggesting `suggestion` as the fix.
+    pub fn suggest(mut self, suggestion: impl Into<String>) -> Self {
+        self.suggestion = Some(suggestion.into());
+        self
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, " ({})", suggestion)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,249 @@
+//! The error type shared by every calculator and Tauri command.
+
+use super::diagnostic::Diagnostic;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use std::fmt;
+
+/// Why a calculation was rejected.
+///
+/// `field` is the name of the offending input as it appears in the command
+/// input, e.g. `"base_cidr"`, so the UI can highlight it. On the wire the
+/// error is an object with a `code`, a human `message`, the `field`, and
+/// where it applies the `octet` index, the allowed `range`, the number of
+/// `missing` addresses or the `diagnostics` of a failed validation pass.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DikaiosError {
+    /// The value is not a valid IPv4 or IPv6 address.
+    InvalidAddress { field: &'static str },
+    /// The octet at the zero-based `octet` index is not a number.
+    InvalidOctet { field: &'static str, octet: usize },
+    /// The octet at the zero-based `octet` index is larger than 255.
+    OctetOutOfRange { field: &'static str, octet: usize },
+    /// The value is not a number.
+    InvalidNumber { field: &'static str },
+    /// The value is outside `min..=max`.
+    OutOfRange { field: &'static str, min: u64, max: u64 },
+    /// A required value was not given.
+    Missing { field: &'static str },
+    /// The requested subnets cannot be placed in the parent block.
+    AllocationFailed { field: &'static str, message: String },
+    /// Any other problem with a value.
+    InvalidInput { field: &'static str, message: String },
+    /// The requested subnets need `required` addresses but the parent block
+    /// only holds `available`.
+    InsufficientSpace { field: &'static str, required: u128, available: u128 },
+    /// A dotted mask whose one bits are not contiguous, e.g. `255.0.255.0`.
+    NonContiguousMask { field: &'static str },
+    /// Writing the result to a file or channel failed.
+    Io { field: &'static str, message: String },
+    /// A validation pass found at least one error. `diagnostics` holds every
+    /// error and warning it found.
+    ValidationFailed { field: &'static str, diagnostics: Vec<Diagnostic> },
+}
+
+impl DikaiosError {
+    /// A stable, machine-readable identifier for the kind of error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            DikaiosError::InvalidAddress { .. } => "invalid_address",
+            DikaiosError::InvalidOctet { .. } => "invalid_octet",
+            DikaiosError::OctetOutOfRange { .. } => "octet_out_of_range",
+            DikaiosError::InvalidNumber { .. } => "invalid_number",
+            DikaiosError::OutOfRange { .. } => "out_of_range",
+            DikaiosError::Missing { .. } => "missing",
+            DikaiosError::AllocationFailed { .. } => "allocation_failed",
+            DikaiosError::InvalidInput { .. } => "invalid_input",
+            DikaiosError::InsufficientSpace { .. } => "insufficient_space",
+            DikaiosError::NonContiguousMask { .. } => "non_contiguous_mask",
+            DikaiosError::Io { .. } => "io_error",
+            DikaiosError::ValidationFailed { .. } => "validation_failed",
+        }
+    }
+
+    /// The input field that caused the error.
+    pub fn field(&self) -> &'static str {
+        match self {
+            DikaiosError::InvalidAddress { field }
+            | DikaiosError::InvalidOctet { field, .. }
+            | DikaiosError::OctetOutOfRange { field, .. }
+            | DikaiosError::InvalidNumber { field }
+            | DikaiosError::OutOfRange { field, .. }
+            | DikaiosError::Missing { field }
+            | DikaiosError::AllocationFailed { field, .. }
+            | DikaiosError::InvalidInput { field, .. }
+            | DikaiosError::InsufficientSpace { field, .. }
+            | DikaiosError::NonContiguousMask { field }
+            | DikaiosError::Io { field, .. }
+            | DikaiosError::ValidationFailed { field, .. } => field,
+        }
+    }
+
+    /// The same error reported against a different input field.
+    pub fn with_field(mut self, new_field: &'static str) -> Self {
+        match &mut self {
+            DikaiosError::InvalidAddress { field }
+            | DikaiosError::InvalidOctet { field, .. }
+            | DikaiosError::OctetOutOfRange { field, .. }
+            | DikaiosError::InvalidNumber { field }
+            | DikaiosError::OutOfRange { field, .. }
+            | DikaiosError::Missing { field }
+            | DikaiosError::AllocationFailed { field, .. }
+            | DikaiosError::InvalidInput { field, .. }
+            | DikaiosError::InsufficientSpace { field, .. }
+            | DikaiosError::NonContiguousMask { field }
+            | DikaiosError::Io { field, .. }
+            | DikaiosError::ValidationFailed { field, .. } => *field = new_field,
+        }
+        self
+    }
+}
+
+impl fmt::Display for DikaiosError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = field_label(self.field());
+
+        match self {
+            DikaiosError::InvalidAddress { .. } => write!(f, "{} is not a valid IP address", label),
+            DikaiosError::InvalidOctet { octet, .. } => write!(f, "{} octet {} is not a number", label, octet + 1),
+            DikaiosError::OctetOutOfRange { octet, .. } => {
+                write!(f, "{} octet {} must be between 0 and 255", label, octet + 1)
+            }
+            DikaiosError::InvalidNumber { .. } => write!(f, "{} must be a number", label),
+            DikaiosError::OutOfRange { min, max, .. } => write!(f, "{} must be between {} and {}", label, min, max),
+            DikaiosError::Missing { .. } => write!(f, "{} is required", label),
+            DikaiosError::AllocationFailed { message, .. } | DikaiosError::InvalidInput { message, .. } => {
+                write!(f, "{}", message)
+            }
+            DikaiosError::InsufficientSpace { required, available, .. } => write!(
+                f,
+                "The requested subnets need {} addresses but the parent block only holds {}; {} more are needed",
+                required, available, required.saturating_sub(*available)
+            ),
+            DikaiosError::NonContiguousMask { .. } => {
+                write!(f, "{} is not a contiguous netmask or wildcard mask", label)
+            }
+            DikaiosError::Io { message, .. } => write!(f, "Cannot write {}: {}", label, message),
+            DikaiosError::ValidationFailed { diagnostics, .. } => {
+                let errors: Vec<String> = diagnostics.iter()
+                    .filter(|diagnostic| diagnostic.is_error())
+                    .map(ToString::to_string)
+                    .collect();
+                write!(f, "{}: {}", label, errors.join("; "))
+            }
+        }
+    }
+}
+
+/// The name of an input field as shown on the pages.
+fn field_label(field: &str) -> &str {
+    match field {
+        "ip_address" => "IP address",
+        "network_bits" => "Network bits",
+        "subnet_mask" => "Subnet mask",
+        "base_network" => "Base network",
+        "base_cidr" => "Base CIDR",
+        "host_requirements" => "Host requirements",
+        "ipv6_requirements" => "IPv6 requirements",
+        "vlan_configs" => "VLAN configurations",
+        "trunks" => "Trunk ports",
+        "growth" => "Growth",
+        "reserved" => "Reserved addresses",
+        "platform" => "Platform",
+        "parent_interface" => "Parent interface",
+        "uplink_vlan" => "Uplink VLAN",
+        "format" => "Format",
+        "dhcp" => "DHCP",
+        "redundancy" => "Redundancy",
+        "protocol" => "Protocol",
+        "dhcp_server" => "DHCP server",
+        "dns_servers" => "DNS servers",
+        "domain_name" => "Domain name",
+        "lease_time" => "Lease time",
+        "excluded_addresses" => "Excluded addresses",
+        "wireless" => "Wireless settings",
+        "ssid" => "SSID",
+        "wpa_password" => "WPA passphrase",
+        "router_hostname" => "Router hostname",
+        "ap_hostname" => "Access point hostname",
+        "security" => "Security",
+        "country_code" => "Country code",
+        "channel" => "Channel",
+        "interface" => "Wireless interface",
+        "enable_secret" => "Enable secret",
+        "secret_type" => "Secret type",
+        "qr" => "QR code",
+        "project" => "Project file",
+        "ipam" => "IPAM database",
+        "id" => "Record ID",
+        "name" => "Name",
+        "vlan_id" => "VLAN ID",
+        "gateway" => "Gateway",
+        "address" => "Address",
+        "hostname" => "Hostname",
+        "mac" => "MAC address",
+        "status" => "Status",
+        "parent" => "Parent prefix",
+        "used" => "Used subnets",
+        "prefixes" => "Prefixes",
+        "cell_prefix_length" => "Heatmap cell prefix length",
+        "subnet" => "Subnet",
+        "first" => "First address",
+        "last" => "Last address",
+        "prefix" => "Prefix",
+        "prefix_length" => "Prefix length",
+        "limit" => "Page size",
+        "path" => "Output file",
+        "on_chunk" => "Host stream",
+        other => other,
+    }
+}
+
+impl std::error::Error for DikaiosError {}
+
+impl Serialize for DikaiosError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(serde::Serialize)]
+        struct Range {
+            min: u64,
+            max: u64,
+        }
+
+        let octet = match self {
+            DikaiosError::InvalidOctet { octet, .. } | DikaiosError::OctetOutOfRange { octet, .. } => Some(*octet),
+            _ => None,
+        };
+        let range = match self {
+            DikaiosError::OutOfRange { min, max, .. } => Some(Range { min: *min, max: *max }),
+            DikaiosError::OctetOutOfRange { .. } => Some(Range { min: 0, max: 255 }),
+            _ => None,
+        };
+
+        let missing = match self {
+            DikaiosError::InsufficientSpace { required, available, .. } => {
+                Some(required.saturating_sub(*available).to_string())
+            }
+            _ => None,
+        };
+
+        let diagnostics = match self {
+            DikaiosError::ValidationFailed { diagnostics, .. } => Some(diagnostics),
+            _ => None,
+        };
+
+        let mut state = serializer.serialize_struct("DikaiosError", 7)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("field", self.field())?;
+        state.serialize_field("octet", &octet)?;
+        state.serialize_field("range", &range)?;
+        state.serialize_field("missing", &missing)?;
+        state.serialize_field("diagnostics", &diagnostics)?;
+        state.end()
+    }
+}
+
+/// Parses a number, reporting failures against `field`.
+pub(crate) fn parse_number<T: std::str::FromStr>(value: &str, field: &'static str) -> Result<T, DikaiosError> {
+    value.trim().parse()
+        .map_err(|_| DikaiosError::InvalidNumber { field })
+}
@@ -0,0 +1,242 @@
+//! What is left of a parent block once some of its subnets are in use.
+//!
+//! [`free_space_report`] lists the free space as the fewest CIDR blocks,
+//! the largest prefix that can still be allocated and how fragmented the
+//! free space is, along with runs and cells a page can draw as a bar or a
+//! heatmap of the parent block.
+
+use super::error::DikaiosError;
+use super::prefix::{Ipv4Prefix, Ipv6Prefix, Prefix, IPV6_ALL_ADDRESSES};
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// How many bits below the parent the heatmap cells are by default, so the
+/// heatmap has 64 cells unless the parent is smaller than that.
+pub const DEFAULT_CELL_BITS: u32 = 6;
+
+/// The most bits below the parent a heatmap cell may be, 4096 cells.
+pub const MAX_CELL_BITS: u32 = 12;
+
+/// A parent block and the subnets of it already in use.
+#[derive(Debug, Deserialize, Clone)]
+pub struct FreeSpaceInput {
+    /// The parent block, e.g. `10.0.0.0/16` or `2001:db8::/48`.
+    pub parent: String,
+    /// Subnets in use. They must lie inside the parent and may overlap each
+    /// other.
+    #[serde(default)]
+    pub used: Vec<String>,
+    /// Prefix length of one heatmap cell, [`DEFAULT_CELL_BITS`] longer than
+    /// the parent if unset.
+    #[serde(default)]
+    pub cell_prefix_length: Option<u32>,
+}
+
+/// A stretch of the parent block that is entirely used or entirely free.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct SpaceRun {
+    pub first: String,
+    pub last: String,
+    pub used: bool,
+    /// Where the run starts, as a fraction of the parent block.
+    pub offset: f64,
+    /// How long the run is, as a fraction of the parent block.
+    pub width: f64,
+}
+
+/// One equal slice of the parent block and how much of it is used.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct HeatmapCell {
+    pub prefix: String,
+    /// Fraction of the cell's addresses in use, from 0 to 1.
+    pub used: f64,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct FreeSpaceReport {
+    pub parent: String,
+    /// The free addresses as the fewest CIDR blocks, lowest first.
+    pub free_blocks: Vec<String>,
+    /// Number of free addresses as a decimal string.
+    pub free_count: String,
+    /// Number of used addresses as a decimal string.
+    pub used_count: String,
+    /// The shortest free prefix, the largest subnet that can still be
+    /// allocated, or `None` when the parent is full.
+    pub largest_free: Option<String>,
+    /// One minus the share of the free space held by the largest free
+    /// block: 0 when the free space is a single block or there is none,
+    /// close to 1 when it is scattered over many small blocks.
+    pub fragmentation: f64,
+    /// The used and free runs covering the parent block, in address order.
+    pub runs: Vec<SpaceRun>,
+    /// The parent block cut into equal cells, in address order.
+    pub heatmap: Vec<HeatmapCell>,
+}
+
+/// Reports the free space of `input.parent` around the used subnets.
+pub fn free_space_report(input: &FreeSpaceInput) -> Result<FreeSpaceReport, DikaiosError> {
+    let parent: Prefix = input.parent.trim().parse().map_err(|e: DikaiosError| e.with_field("parent"))?;
+    let parent = parent.trunc();
+    let (first, last) = parent.bounds();
+
+    let mut used = Vec::with_capacity(input.used.len());
+    for prefix in &input.used {
+        let prefix: Prefix = prefix.trim().parse().map_err(|e: DikaiosError| e.with_field("used"))?;
+        if !parent.contains_prefix(&prefix) {
+            return Err(DikaiosError::InvalidInput {
+                field: "used",
+                message: format!("{} is not inside {}", prefix.trunc(), parent),
+            });
+        }
+        used.push(prefix.bounds());
+    }
+    let used = merge(used);
+
+    // The free gaps between the used ranges
+    let mut free = Vec::new();
+    let mut next = Some(first);
+    for &(start, end) in &used {
+        if let Some(gap_start) = next.filter(|&gap_start| gap_start < start) {
+            free.push((gap_start, start - 1));
+        }
+        next = end.checked_add(1);
+    }
+    if let Some(gap_start) = next.filter(|&gap_start| gap_start <= last) {
+        free.push((gap_start, last));
+    }
+
+    let free_blocks: Vec<Prefix> = free.iter().flat_map(|&(start, end)| summarize(&parent, start, end)).collect();
+    let largest_free = free_blocks.iter().min_by_key(|block| block.prefix_len()).copied();
+    let free_total: f64 = free.iter().map(|&(start, end)| span(start, end)).sum();
+    let fragmentation = match largest_free {
+        Some(block) => {
+            let (start, end) = block.bounds();
+            1.0 - span(start, end) / free_total
+        }
+        None => 0.0,
+    };
+
+    let size = span(first, last);
+    let mut runs: Vec<SpaceRun> = used
+        .iter()
+        .map(|&range| (range, true))
+        .chain(free.iter().map(|&range| (range, false)))
+        .map(|((start, end), used)| SpaceRun {
+            first: address(&parent, start),
+            last: address(&parent, end),
+            used,
+            offset: (start - first) as f64 / size,
+            width: span(start, end) / size,
+        })
+        .collect();
+    runs.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+
+    Ok(FreeSpaceReport {
+        parent: parent.to_string(),
+        free_blocks: free_blocks.iter().map(Prefix::to_string).collect(),
+        free_count: count(&free),
+        used_count: count(&used),
+        largest_free: largest_free.map(|block| block.to_string()),
+        fragmentation,
+        runs,
+        heatmap: heatmap(&parent, &used, input.cell_prefix_length)?,
+    })
+}
+
+/// Sorts the ranges and joins those that overlap or touch.
+fn merge(mut ranges: Vec<(u128, u128)>) -> Vec<(u128, u128)> {
+    ranges.sort_unstable();
+    let mut merged: Vec<(u128, u128)> = Vec::with_capacity(ranges.len());
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(previous) if previous.1.checked_add(1).is_none_or(|next| start <= next) => {
+                previous.1 = previous.1.max(end);
+            }
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// Number of addresses in `start..=end`. A float, since the whole IPv6
+/// space holds one address more than `u128` can count.
+fn span(start: u128, end: u128) -> f64 {
+    (end - start) as f64 + 1.0
+}
+
+/// Total number of addresses in the ranges as a decimal string.
+fn count(ranges: &[(u128, u128)]) -> String {
+    ranges
+        .iter()
+        .try_fold(0u128, |total, &(start, end)| (end - start).checked_add(1).and_then(|size| total.checked_add(size)))
+        .map_or_else(|| IPV6_ALL_ADDRESSES.to_string(), |total| total.to_string())
+}
+
+fn address(family: &Prefix, value: u128) -> String {
+    match family {
+        Prefix::V4(_) => IpAddr::V4(Ipv4Addr::from(value as u32)),
+        Prefix::V6(_) => IpAddr::V6(Ipv6Addr::from(value)),
+    }
+    .to_string()
+}
+
+/// The fewest prefixes of the parent's family covering `start..=end`.
+fn summarize(family: &Prefix, start: u128, end: u128) -> Vec<Prefix> {
+    match family {
+        Prefix::V4(_) => Ipv4Prefix::summarize(Ipv4Addr::from(start as u32), Ipv4Addr::from(end as u32))
+            .into_iter()
+            .map(Prefix::V4)
+            .collect(),
+        Prefix::V6(_) => Ipv6Prefix::summarize(Ipv6Addr::from(start), Ipv6Addr::from(end))
+            .into_iter()
+            .map(Prefix::V6)
+            .collect(),
+    }
+}
+
+/// Cuts the parent into cells of `cell_prefix_length` and measures how much
+/// of each one the merged `used` ranges cover.
+fn heatmap(parent: &Prefix, used: &[(u128, u128)], cell_prefix_length: Option<u32>) -> Result<Vec<HeatmapCell>, DikaiosError> {
+    let max_len = match parent {
+        Prefix::V4(_) => Ipv4Prefix::MAX_LEN,
+        Prefix::V6(_) => Ipv6Prefix::MAX_LEN,
+    };
+    let parent_len = parent.prefix_len();
+    let cell_len = cell_prefix_length.unwrap_or((parent_len + DEFAULT_CELL_BITS).min(max_len));
+    let longest = (parent_len + MAX_CELL_BITS).min(max_len);
+    if !(parent_len..=longest).contains(&cell_len) {
+        return Err(DikaiosError::OutOfRange {
+            field: "cell_prefix_length",
+            min: parent_len as u64,
+            max: longest as u64,
+        });
+    }
+
+    let (first, _) = parent.bounds();
+    let cell_mask = u128::MAX.checked_shr(128 - (max_len - cell_len)).unwrap_or(0);
+    let cell_size = span(0, cell_mask);
+    let mut remaining = used;
+    let mut cells = Vec::with_capacity(1 << (cell_len - parent_len));
+    for index in 0..1u128 << (cell_len - parent_len) {
+        let start = first + index.checked_shl(max_len - cell_len).unwrap_or(0);
+        let end = start + cell_mask;
+
+        let mut covered = 0.0;
+        while let Some(&(used_start, used_end)) = remaining.first() {
+            if used_start > end {
+                break;
+            }
+            covered += span(used_start.max(start), used_end.min(end));
+            if used_end > end {
+                break;
+            }
+            remaining = &remaining[1..];
+        }
+        cells.push(HeatmapCell {
+            prefix: summarize(parent, start, end)[0].to_string(),
+            used: covered / cell_size,
+        });
+    }
+    Ok(cells)
+}
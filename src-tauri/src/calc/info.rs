@@ -0,0 +1,144 @@
+//! Details about the network a single address belongs to.
+
+use super::error::DikaiosError;
+use super::mask::{parse_ipv4_mask, parse_ipv6_mask};
+use super::prefix::{parse_ipv4, parse_ipv6, AddressFamily, Ipv4Prefix, Ipv6Prefix, Prefix};
+use serde::{Deserialize, Serialize};
+use std::net::Ipv6Addr;
+
+/// An address and prefix length to describe.
+#[derive(Debug, Deserialize, Clone)]
+pub struct IpCalculatorInput {
+    pub ip_address: String,
+    pub network_bits: String,
+}
+
+/// Everything the IP calculator reports about an address and its prefix.
+///
+/// Fields that only make sense for one family are `None` for the other.
+#[derive(Debug, Serialize, Clone)]
+pub struct NetworkInfo {
+    pub family: AddressFamily,
+    pub prefix_length: u32,
+    pub address: String,
+    pub netmask: String,
+    pub wildcard: String,
+    pub network: String,
+    /// IPv6, /31 and /32 networks have no broadcast address.
+    pub broadcast: Option<String>,
+    pub hostmin: String,
+    pub hostmax: String,
+    /// Usable hosts only fit a `u32` for IPv4; see `address_count` for IPv6.
+    pub hosts: Option<u32>,
+    /// Total addresses in the prefix as a decimal string.
+    pub address_count: String,
+    pub ip_class: Option<String>,
+    pub is_private: bool,
+    pub scope: Option<String>,
+    pub compressed: Option<String>,
+    pub expanded: Option<String>,
+}
+
+/// Calculates the network details for `ip` with the mask `bits`, given as a
+/// prefix length or, for IPv4, a dotted netmask or wildcard mask.
+///
+/// Addresses containing a `:` are treated as IPv6.
+pub fn calculate_ip_info(ip: &str, bits: &str) -> Result<NetworkInfo, DikaiosError> {
+    if ip.contains(':') {
+        return calculate_ipv6_info(ip, bits);
+    }
+
+    let mask_bits = parse_ipv4_mask(bits, "network_bits")?;
+    let prefix = Ipv4Prefix::new(parse_ipv4(ip, "ip_address")?, mask_bits)
+        .map_err(|e| e.with_field("network_bits"))?;
+
+    let octets = prefix.addr().octets();
+    let ip_class = match octets[0] {
+        0..=127 => "A",
+        128..=191 => "B",
+        192..=223 => "C",
+        224..=239 => "D",
+        _ => "E",
+    }.to_string();
+
+    let is_private = (octets[0] == 10) ||
+                    (octets[0] == 172 && octets[1] >= 16 && octets[1] <= 31) ||
+                    (octets[0] == 192 && octets[1] == 168);
+
+    Ok(NetworkInfo {
+        family: AddressFamily::Ipv4,
+        prefix_length: mask_bits,
+        address: prefix.addr().to_string(),
+        netmask: prefix.netmask().to_string(),
+        wildcard: prefix.hostmask().to_string(),
+        network: prefix.network().to_string(),
+        broadcast: prefix.broadcast_addr().map(|broadcast| broadcast.to_string()),
+        hostmin: prefix.first_host().to_string(),
+        hostmax: prefix.last_host().to_string(),
+        hosts: Some(prefix.host_count()),
+        address_count: Prefix::V4(prefix).address_count(),
+        ip_class: Some(ip_class),
+        is_private,
+        scope: None,
+        compressed: None,
+        expanded: None,
+    })
+}
+
+fn calculate_ipv6_info(ip: &str, bits: &str) -> Result<NetworkInfo, DikaiosError> {
+    let prefix_len = parse_ipv6_mask(bits, "network_bits")?;
+    let prefix = Ipv6Prefix::new(parse_ipv6(ip, "ip_address")?, prefix_len)
+        .map_err(|e| e.with_field("network_bits"))?;
+    let addr = prefix.addr();
+
+    Ok(NetworkInfo {
+        family: AddressFamily::Ipv6,
+        prefix_length: prefix_len,
+        address: addr.to_string(),
+        netmask: prefix.netmask().to_string(),
+        wildcard: prefix.hostmask().to_string(),
+        network: prefix.network().to_string(),
+        broadcast: None,
+        hostmin: prefix.network().to_string(),
+        hostmax: prefix.last().to_string(),
+        hosts: None,
+        address_count: Prefix::V6(prefix).address_count(),
+        ip_class: None,
+        is_private: ipv6_scope(&addr) == "unique-local",
+        scope: Some(ipv6_scope(&addr).to_string()),
+        compressed: Some(addr.to_string()),
+        expanded: Some(expand_ipv6(&addr)),
+    })
+}
+
+/// Classifies an IPv6 address, e.g. `"link-local"` or `"global-unicast"`.
+pub fn ipv6_scope(addr: &Ipv6Addr) -> &'static str {
+    let segments = addr.segments();
+
+    if addr.is_unspecified() {
+        "unspecified"
+    } else if addr.is_loopback() {
+        "loopback"
+    } else if segments[0] & 0xff00 == 0xff00 {
+        "multicast"
+    } else if segments[0] & 0xffc0 == 0xfe80 {
+        "link-local"
+    } else if segments[0] & 0xfe00 == 0xfc00 {
+        "unique-local"
+    } else if segments[..5] == [0, 0, 0, 0, 0] && segments[5] == 0xffff {
+        "ipv4-mapped"
+    } else if segments[0] & 0xe000 == 0x2000 {
+        "global-unicast"
+    } else {
+        "reserved"
+    }
+}
+
+/// Writes all eight groups of an IPv6 address with leading zeros.
+pub fn expand_ipv6(addr: &Ipv6Addr) -> String {
+    addr.segments()
+        .iter()
+        .map(|segment| format!("{:04x}", segment))
+        .collect::<Vec<_>>()
+        .join(":")
+}
@@ -0,0 +1,247 @@
+//! Linux network configuration for the VLANs of a plan.
+//!
+//! Every allocation becomes an 802.1Q subinterface `<parent>.<vlan id>` of
+//! the given parent interface and addressed with the first host address of
+//! the VLAN. Only the uplink VLAN, if one is chosen, gets a default route
+//! through its gateway; the others reach just their own subnet, so the host
+//! never ends up with several default routes competing for its traffic.
+
+use super::error::DikaiosError;
+use super::vlan::VlanAllocation;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Longest interface name the Linux kernel accepts.
+const MAX_INTERFACE_NAME_LEN: usize = 15;
+
+/// The network configuration system to write files for.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum LinuxFormat {
+    Netplan,
+    SystemdNetworkd,
+    NetworkManager,
+    Ifupdown,
+}
+
+impl LinuxFormat {
+    pub const ALL: [LinuxFormat; 4] = [
+        LinuxFormat::Netplan,
+        LinuxFormat::SystemdNetworkd,
+        LinuxFormat::NetworkManager,
+        LinuxFormat::Ifupdown,
+    ];
+
+    /// The identifier used in inputs and on the command line.
+    pub fn id(&self) -> &'static str {
+        match self {
+            LinuxFormat::Netplan => "netplan",
+            LinuxFormat::SystemdNetworkd => "systemd_networkd",
+            LinuxFormat::NetworkManager => "network_manager",
+            LinuxFormat::Ifupdown => "ifupdown",
+        }
+    }
+}
+
+impl fmt::Display for LinuxFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            LinuxFormat::Netplan => "netplan",
+            LinuxFormat::SystemdNetworkd => "systemd-networkd",
+            LinuxFormat::NetworkManager => "NetworkManager",
+            LinuxFormat::Ifupdown => "ifupdown",
+        })
+    }
+}
+
+impl FromStr for LinuxFormat {
+    type Err = DikaiosError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let id = s.trim().to_ascii_lowercase().replace('-', "_");
+        Self::ALL.into_iter()
+            .find(|format| format.id() == id)
+            .ok_or_else(|| DikaiosError::InvalidInput {
+                field: "format",
+                message: format!(
+                    "Unknown Linux format \"{}\", expected one of {}",
+                    s.trim(),
+                    Self::ALL.map(|format| format.id()).join(", ")
+                ),
+            })
+    }
+}
+
+/// The VLANs of a computed plan and where to attach them.
+#[derive(Debug, Deserialize, Clone)]
+pub struct LinuxExportInput {
+    pub allocations: Vec<VlanAllocation>,
+    pub parent_interface: String,
+    /// The VLAN whose gateway becomes the host's default route; no default
+    /// route is written when unset.
+    #[serde(default)]
+    pub uplink_vlan: Option<u32>,
+    pub format: LinuxFormat,
+}
+
+/// A configuration file and where it belongs on the host.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct ConfigFile {
+    pub path: String,
+    pub contents: String,
+}
+
+/// Writes the configuration files that create a subinterface of
+/// `parent_interface` for every allocation, with a default route through
+/// the gateway of `uplink_vlan` only.
+pub fn export_linux_config(
+    allocations: &[VlanAllocation],
+    parent_interface: &str,
+    uplink_vlan: Option<u32>,
+    format: LinuxFormat,
+) -> Result<Vec<ConfigFile>, DikaiosError> {
+    let parent = parent_interface.trim();
+    if parent.is_empty() {
+        return Err(DikaiosError::Missing { field: "parent_interface" });
+    }
+    if parent.contains(|c: char| c.is_whitespace() || c == '/' || c == ':') {
+        return Err(DikaiosError::InvalidInput {
+            field: "parent_interface",
+            message: format!("\"{}\" is not a valid interface name", parent),
+        });
+    }
+    if let Some(uplink) = uplink_vlan.filter(|&id| !allocations.iter().any(|a| a.vlan_id == id)) {
+        return Err(DikaiosError::InvalidInput {
+            field: "uplink_vlan",
+            message: format!("VLAN {} is not part of the plan", uplink),
+        });
+    }
+
+    let vlans = allocations.iter()
+        .map(|allocation| {
+            let interface = format!("{}.{}", parent, allocation.vlan_id);
+            if interface.len() > MAX_INTERFACE_NAME_LEN {
+                return Err(DikaiosError::InvalidInput {
+                    field: "parent_interface",
+                    message: format!(
+                        "Interface name {} is longer than the {} characters Linux allows",
+                        interface, MAX_INTERFACE_NAME_LEN
+                    ),
+                });
+            }
+            Ok(Subinterface { allocation, interface, uplink: uplink_vlan == Some(allocation.vlan_id) })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(match format {
+        LinuxFormat::Netplan => netplan(parent, &vlans),
+        LinuxFormat::SystemdNetworkd => systemd_networkd(parent, &vlans),
+        LinuxFormat::NetworkManager => network_manager(parent, &vlans),
+        LinuxFormat::Ifupdown => ifupdown(parent, &vlans),
+    })
+}
+
+struct Subinterface<'a> {
+    allocation: &'a VlanAllocation,
+    interface: String,
+    /// Whether the default route goes through this VLAN's gateway.
+    uplink: bool,
+}
+
+impl Subinterface<'_> {
+    /// The host's own address in CIDR notation.
+    fn address(&self) -> String {
+        format!("{}/{}", self.allocation.first_host, self.allocation.cidr)
+    }
+}
+
+fn netplan(parent: &str, vlans: &[Subinterface]) -> Vec<ConfigFile> {
+    let mut contents = String::from("network:\n  version: 2\n  ethernets:\n");
+    contents.push_str(&format!("    {}: {{}}\n", parent));
+    contents.push_str("  vlans:\n");
+
+    for vlan in vlans {
+        contents.push_str(&format!("    # VLAN {} - {}\n", vlan.allocation.vlan_id, vlan.allocation.vlan_name));
+        contents.push_str(&format!("    {}:\n", vlan.interface));
+        contents.push_str(&format!("      id: {}\n", vlan.allocation.vlan_id));
+        contents.push_str(&format!("      link: {}\n", parent));
+        contents.push_str(&format!("      addresses:\n        - {}\n", vlan.address()));
+        if vlan.uplink {
+            contents.push_str("      routes:\n        - to: default\n");
+            contents.push_str(&format!("          via: {}\n", vlan.allocation.gateway));
+        }
+    }
+
+    vec![ConfigFile { path: "/etc/netplan/60-dikaios-vlans.yaml".to_string(), contents }]
+}
+
+fn systemd_networkd(parent: &str, vlans: &[Subinterface]) -> Vec<ConfigFile> {
+    let mut files = Vec::new();
+
+    // The parent has to list its VLANs; merge these lines into its existing
+    // .network file if it already has one
+    let mut contents = format!("[Match]\nName={}\n\n[Network]\n", parent);
+    for vlan in vlans {
+        contents.push_str(&format!("VLAN={}\n", vlan.interface));
+    }
+    files.push(ConfigFile { path: format!("/etc/systemd/network/10-{}.network", parent), contents });
+
+    for vlan in vlans {
+        files.push(ConfigFile {
+            path: format!("/etc/systemd/network/20-{}.netdev", vlan.interface),
+            contents: format!(
+                "[NetDev]\nName={}\nKind=vlan\nDescription={}\n\n[VLAN]\nId={}\n",
+                vlan.interface, vlan.allocation.vlan_name, vlan.allocation.vlan_id
+            ),
+        });
+        let mut contents = format!("[Match]\nName={}\n\n[Network]\nAddress={}\n", vlan.interface, vlan.address());
+        if vlan.uplink {
+            contents.push_str(&format!("\n[Route]\nGateway={}\n", vlan.allocation.gateway));
+        }
+        files.push(ConfigFile { path: format!("/etc/systemd/network/20-{}.network", vlan.interface), contents });
+    }
+
+    files
+}
+
+fn network_manager(parent: &str, vlans: &[Subinterface]) -> Vec<ConfigFile> {
+    vlans.iter()
+        .map(|vlan| ConfigFile {
+            path: format!("/etc/NetworkManager/system-connections/{}.nmconnection", vlan.interface),
+            contents: format!(
+                "[connection]\nid=VLAN {} {}\ntype=vlan\ninterface-name={}\n\n\
+                 [vlan]\nid={}\nparent={}\n\n\
+                 [ipv4]\nmethod=manual\n{}\n\n\
+                 [ipv6]\nmethod=disabled\n",
+                vlan.allocation.vlan_id, vlan.allocation.vlan_name, vlan.interface,
+                vlan.allocation.vlan_id, parent,
+                if vlan.uplink {
+                    format!("address1={},{}", vlan.address(), vlan.allocation.gateway)
+                } else {
+                    // Spelled out so a gateway added to the connection by
+                    // hand later still does not take over the default route
+                    format!("address1={}\nnever-default=true", vlan.address())
+                }
+            ),
+        })
+        .collect()
+}
+
+fn ifupdown(parent: &str, vlans: &[Subinterface]) -> Vec<ConfigFile> {
+    let mut contents = String::new();
+
+    for vlan in vlans {
+        contents.push_str(&format!("# VLAN {} - {}\n", vlan.allocation.vlan_id, vlan.allocation.vlan_name));
+        contents.push_str(&format!("auto {}\n", vlan.interface));
+        contents.push_str(&format!("iface {} inet static\n", vlan.interface));
+        contents.push_str(&format!("    vlan-raw-device {}\n", parent));
+        contents.push_str(&format!("    address {}\n", vlan.address()));
+        if vlan.uplink {
+            contents.push_str(&format!("    gateway {}\n", vlan.allocation.gateway));
+        }
+        contents.push('\n');
+    }
+
+    vec![ConfigFile { path: "/etc/network/interfaces.d/dikaios-vlans".to_string(), contents }]
+}
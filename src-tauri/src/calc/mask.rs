@@ -0,0 +1,98 @@
+//! The mask parser shared by every input that takes a prefix length.
+//!
+//! An IPv4 mask may be written as `/26`, `26`, a dotted netmask such as
+//! `255.255.255.192` or a Cisco wildcard such as `0.0.0.63`. IPv6 masks are
+//! always prefix lengths.
+
+use super::error::{parse_number, DikaiosError};
+use super::prefix::{parse_ipv4, Ipv4Prefix, Ipv6Prefix};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A mask as sent by the frontend or read from a file: either a bare
+/// prefix length or any of the textual forms accepted by [`parse_ipv4_mask`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum MaskInput {
+    Length(u32),
+    Text(String),
+}
+
+impl MaskInput {
+    /// The IPv4 prefix length this mask stands for.
+    pub fn ipv4_prefix_len(&self, field: &'static str) -> Result<u32, DikaiosError> {
+        match self {
+            MaskInput::Length(len) => check_len(*len, Ipv4Prefix::MAX_LEN, field),
+            MaskInput::Text(text) => parse_ipv4_mask(text, field),
+        }
+    }
+
+    /// The IPv6 prefix length this mask stands for.
+    pub fn ipv6_prefix_len(&self, field: &'static str) -> Result<u32, DikaiosError> {
+        match self {
+            MaskInput::Length(len) => check_len(*len, Ipv6Prefix::MAX_LEN, field),
+            MaskInput::Text(text) => parse_ipv6_mask(text, field),
+        }
+    }
+}
+
+impl From<u32> for MaskInput {
+    fn from(len: u32) -> Self {
+        MaskInput::Length(len)
+    }
+}
+
+impl From<String> for MaskInput {
+    fn from(text: String) -> Self {
+        MaskInput::Text(text)
+    }
+}
+
+impl fmt::Display for MaskInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MaskInput::Length(len) => write!(f, "{}", len),
+            MaskInput::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+/// Parses an IPv4 mask into a prefix length.
+///
+/// Dotted masks are read as a netmask first and as a wildcard otherwise, so
+/// `0.0.0.0` is a /0 and `255.255.255.255` a /32. Masks whose one bits are
+/// not contiguous are rejected.
+pub fn parse_ipv4_mask(value: &str, field: &'static str) -> Result<u32, DikaiosError> {
+    let value = value.trim();
+    if !value.contains('.') {
+        let len = parse_number(value.strip_prefix('/').unwrap_or(value), field)?;
+        return check_len(len, Ipv4Prefix::MAX_LEN, field);
+    }
+
+    let bits = u32::from(parse_ipv4(value, field)?);
+    if is_contiguous_netmask(bits) {
+        Ok(bits.leading_ones())
+    } else if is_contiguous_netmask(!bits) {
+        Ok(bits.leading_zeros())
+    } else {
+        Err(DikaiosError::NonContiguousMask { field })
+    }
+}
+
+/// Parses an IPv6 prefix length written as `/64` or `64`.
+pub fn parse_ipv6_mask(value: &str, field: &'static str) -> Result<u32, DikaiosError> {
+    let value = value.trim();
+    let len = parse_number(value.strip_prefix('/').unwrap_or(value), field)?;
+    check_len(len, Ipv6Prefix::MAX_LEN, field)
+}
+
+fn is_contiguous_netmask(bits: u32) -> bool {
+    bits.leading_ones() + bits.trailing_zeros() == 32
+}
+
+fn check_len(len: u32, max: u32, field: &'static str) -> Result<u32, DikaiosError> {
+    if len > max {
+        return Err(DikaiosError::OutOfRange { field, min: 0, max: max as u64 });
+    }
+    Ok(len)
+}
@@ -0,0 +1,68 @@
+//! The address calculators behind every Dikaios page, usable without Tauri.
+//!
+//! Each calculator takes plain strings and numbers, exactly as the GUI sends
+//! them, and returns serializable results. The [`prefix`] module provides
+//! the typed IPv4/IPv6 prefix model they are built on.
+//!
+//! ```
+//! use dikaios_lib::calc::{calculate_vlsm_subnets, HostRequirement, VlsmOptions};
+//!
+//! let requirements = [HostRequirement::new("WAN link", 2), HostRequirement::new("Sales LAN", 50)];
+//! let plan = calculate_vlsm_subnets("192.168.1.0", 24, &requirements, &VlsmOptions::default()).unwrap();
+//! assert_eq!(plan.subnets[0].network, "192.168.1.0");
+//! assert_eq!(plan.subnets[0].cidr, 26);
+//! assert_eq!((plan.subnets[0].name.as_str(), plan.subnets[0].index), ("Sales LAN", 1));
+//! assert_eq!(plan.unused.blocks, ["192.168.1.68/30", "192.168.1.72/29", "192.168.1.80/28", "192.168.1.96/27", "192.168.1.128/25"]);
+//! ```
+
+pub mod conflict;
+pub mod dhcp;
+pub mod diagnostic;
+pub mod error;
+pub mod free_space;
+pub mod info;
+pub mod linux;
+pub mod mask;
+pub mod onboarding;
+pub mod platform;
+pub mod prefix;
+pub mod project;
+pub mod range;
+pub mod redundancy;
+pub mod reference;
+pub mod secret;
+pub mod vlan;
+pub mod vlsm;
+pub mod wireless;
+
+pub use conflict::{check_conflicts, Conflict, ConflictEntry, ConflictInput, ConflictKind, ConflictReport, LabelledPrefix};
+pub use dhcp::{
+    configure_dhcp, dhcp_server_config, DhcpExportInput, DhcpMode, DhcpOptions, DhcpScope, DhcpServer, DEFAULT_LEASE_TIME,
+};
+pub use diagnostic::{Diagnostic, Severity};
+pub use error::DikaiosError;
+pub use free_space::{free_space_report, FreeSpaceInput, FreeSpaceReport, HeatmapCell, SpaceRun};
+pub use info::{calculate_ip_info, IpCalculatorInput, NetworkInfo};
+pub use linux::{export_linux_config, ConfigFile, LinuxExportInput, LinuxFormat};
+pub use mask::{parse_ipv4_mask, parse_ipv6_mask, MaskInput};
+pub use onboarding::{generate_wifi_onboarding, qr_png, qr_svg, wifi_qr_payload, WifiOnboarding, QR_PNG_MODULE_SIZE};
+pub use platform::{Platform, VlanRenderer};
+pub use prefix::{AddressFamily, Ipv4Prefix, Ipv6Prefix, Prefix};
+pub use project::{
+    load_project, project_from_str, project_to_string, save_project, Project, ProjectFormat, SavedVlanPlan, SavedVlsm,
+    SavedWirelessConfig, PROJECT_VERSION,
+};
+pub use range::{calculate_host_page, host_range, HostPage, HostRange, IpInput, MAX_FILTER_SCAN};
+pub use redundancy::{configure_redundancy, FhrpProtocol, RedundancyOptions, RedundantGateway};
+pub use reference::{generate_subnet_references, SubnetMaskReference};
+pub use secret::{cisco_salt, cisco_secret_hash, is_valid_wpa_key, wpa_psk, CiscoSecretType};
+pub use vlan::{
+    calculate_vlan_plan, calculate_vlans, validate_vlans, TrunkPort, VlanAllocation, VlanConfig, VlanInput, VlanPlan,
+};
+pub use vlsm::{
+    calculate_ipv6_vlsm_subnets, calculate_vlsm_plan, calculate_vlsm_subnets, Growth, HostRequirement, Ipv6Requirement,
+    Ipv6Size, UnusedSpace, VlsmInput, VlsmOptions, VlsmPlan, VlsmSubnet,
+};
+pub use wireless::{
+    generate_wireless_config, validate_wireless, WirelessNetworkConfig, WirelessNetworkInput, WirelessSecurity,
+};
@@ -0,0 +1,173 @@
+//! Handing a wireless network out to its users: the join QR code and a
+//! printable onboarding card.
+//!
+//! The QR code holds the `WIFI:` payload that phone cameras understand, so
+//! scanning it joins the network. Joining needs the passphrase, so the
+//! payload always carries it, even when `mask_passphrase` keeps it out of
+//! the rest of the output; the card then leaves the passphrase line out.
+
+use super::diagnostic::Diagnostic;
+use super::error::DikaiosError;
+use super::wireless::{validate_wireless, wireless_addressing, WirelessNetworkInput, WirelessSecurity, DNS_SERVERS};
+use qrcode::render::svg;
+use qrcode::{Color, EcLevel, QrCode};
+use serde::Serialize;
+use std::net::Ipv4Addr;
+
+/// Pixels per QR module in the PNG rendering.
+pub const QR_PNG_MODULE_SIZE: u32 = 8;
+
+/// Modules of white border the QR specification asks for around the code.
+const QR_QUIET_ZONE: usize = 4;
+
+/// The smallest width and height in pixels of the SVG rendering.
+const QR_SVG_SIZE: u32 = 256;
+
+/// The join QR code and onboarding card of a wireless network.
+///
+/// `qr_svg` is a standalone SVG document and `card_html` a standalone HTML
+/// page sized for printing on A6.
+#[derive(Debug, Serialize, Clone)]
+pub struct WifiOnboarding {
+    pub payload: String,
+    pub qr_svg: String,
+    pub card_html: String,
+}
+
+/// The `WIFI:T:WPA;S:<ssid>;P:<passphrase>;;` payload that joins `ssid`,
+/// with `\`, `;`, `,`, `:` and `"` escaped. WPA3-only networks are
+/// announced as `T:SAE`.
+pub fn wifi_qr_payload(ssid: &str, security: WirelessSecurity, passphrase: &str) -> String {
+    let auth = match security {
+        WirelessSecurity::Wpa2 | WirelessSecurity::Wpa2Wpa3 => "WPA",
+        WirelessSecurity::Wpa3 => "SAE",
+    };
+    format!("WIFI:T:{};S:{};P:{};;", auth, escape_wifi_field(ssid), escape_wifi_field(passphrase))
+}
+
+fn escape_wifi_field(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '\\' | ';' | ',' | ':' | '"') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn qr_code(payload: &str) -> Result<QrCode, DikaiosError> {
+    QrCode::with_error_correction_level(payload, EcLevel::M).map_err(|e| DikaiosError::InvalidInput {
+        field: "wireless",
+        message: format!("The network details do not fit in a QR code: {}", e),
+    })
+}
+
+/// `payload` as a QR code in an SVG document.
+pub fn qr_svg(payload: &str) -> Result<String, DikaiosError> {
+    Ok(qr_code(payload)?.render::<svg::Color>().min_dimensions(QR_SVG_SIZE, QR_SVG_SIZE).build())
+}
+
+/// `payload` as a QR code in a black and white PNG image with
+/// `module_size` pixels per module.
+pub fn qr_png(payload: &str, module_size: u32) -> Result<Vec<u8>, DikaiosError> {
+    let code = qr_code(payload)?;
+    let modules = code.width();
+    let colors = code.to_colors();
+    let scale = module_size.max(1) as usize;
+    let side = (modules + 2 * QR_QUIET_ZONE) * scale;
+
+    let mut pixels = vec![u8::MAX; side * side];
+    for (index, color) in colors.iter().enumerate() {
+        if *color != Color::Dark {
+            continue;
+        }
+        let x = (index % modules + QR_QUIET_ZONE) * scale;
+        let y = (index / modules + QR_QUIET_ZONE) * scale;
+        for row in y..y + scale {
+            pixels[row * side + x..row * side + x + scale].fill(0);
+        }
+    }
+
+    let mut png = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png, side as u32, side as u32);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.write_header()
+        .and_then(|mut writer| writer.write_image_data(&pixels))
+        .map_err(|e| DikaiosError::Io { field: "qr", message: e.to_string() })?;
+
+    Ok(png)
+}
+
+/// The QR code and printable card for joining the network described by
+/// `input`.
+///
+/// Only the addressing plan is worked out, so none of the secrets of the
+/// device configuration are derived or hashed. Fails like
+/// [`generate_wireless_config`](super::wireless::generate_wireless_config)
+/// when the input is invalid.
+pub fn generate_wifi_onboarding(input: &WirelessNetworkInput) -> Result<WifiOnboarding, DikaiosError> {
+    let diagnostics = validate_wireless(input);
+    if diagnostics.iter().any(Diagnostic::is_error) {
+        return Err(DikaiosError::ValidationFailed { field: "wireless", diagnostics });
+    }
+    let addressing = wireless_addressing(input)?;
+
+    let payload = wifi_qr_payload(&input.ssid, input.security, &input.wpa_password);
+    let qr_svg = qr_svg(&payload)?;
+    let inline_svg = qr_svg.split_once("?>").map_or(qr_svg.as_str(), |(_, document)| document);
+
+    let mut details = vec![("Network name", input.ssid.clone())];
+    if !input.mask_passphrase {
+        details.push(("Password", input.wpa_password.clone()));
+    }
+    details.push(("Security", input.security.to_string()));
+    details.push(("Network", addressing.prefix.to_string()));
+    details.push(("Gateway", Ipv4Addr::from(addressing.router_ip).to_string()));
+    details.push((
+        "Addresses",
+        format!("{} - {} (DHCP)", Ipv4Addr::from(addressing.dhcp_start), Ipv4Addr::from(addressing.dhcp_end)),
+    ));
+    details.push(("DNS", DNS_SERVERS.join(", ")));
+
+    let mut card_html = String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    card_html.push_str(&format!("<title>Wi-Fi {}</title>\n", escape_html(&input.ssid)));
+    card_html.push_str(concat!(
+        "<style>\n",
+        "@page { size: A6; margin: 8mm; }\n",
+        "body { font-family: sans-serif; margin: 0; }\n",
+        ".card { max-width: 90mm; margin: 0 auto; padding: 6mm; text-align: center; ",
+        "border: 1px solid #000; border-radius: 4mm; }\n",
+        ".card svg { width: 50mm; height: 50mm; }\n",
+        "dl { display: grid; grid-template-columns: auto 1fr; gap: 1mm 4mm; text-align: left; font-size: 10pt; }\n",
+        "dt { font-weight: bold; }\n",
+        "dd { margin: 0; font-family: monospace; word-break: break-all; }\n",
+        "</style>\n</head>\n<body>\n<div class=\"card\">\n",
+    ));
+    card_html.push_str(&format!("<h1>{}</h1>\n", escape_html(&input.ssid)));
+    card_html.push_str("<p>Scan with your phone camera to join</p>\n");
+    card_html.push_str(inline_svg);
+    card_html.push_str("\n<dl>\n");
+    for (label, value) in details {
+        card_html.push_str(&format!("<dt>{}</dt><dd>{}</dd>\n", label, escape_html(&value)));
+    }
+    card_html.push_str("</dl>\n</div>\n</body>\n</html>\n");
+
+    Ok(WifiOnboarding { payload, qr_svg, card_html })
+}
+
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,517 @@
+//! Device configuration for VLAN plans on each supported network platform.
+//!
+//! Every platform implements [`VlanRenderer`], which turns a plan into the
+//! VLAN, layer-3 interface, address and switch port configuration in that
+//! platform's syntax. [`Platform`] picks the renderer by name. Plans routed
+//! by a redundant pair get a configuration for each router, with the
+//! first-hop redundancy protocol on every VLAN interface.
+
+use super::dhcp::DhcpMode;
+use super::error::DikaiosError;
+use super::redundancy::{FhrpProtocol, RedundantGateway};
+use super::vlan::{VlanAllocation, VlanPlan};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// A network operating system VLAN configuration can be generated for.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Platform {
+    #[default]
+    CiscoIos,
+    JuniperJunos,
+    AristaEos,
+    MikrotikRouteros,
+    Vyos,
+}
+
+impl Platform {
+    pub const ALL: [Platform; 5] = [
+        Platform::CiscoIos,
+        Platform::JuniperJunos,
+        Platform::AristaEos,
+        Platform::MikrotikRouteros,
+        Platform::Vyos,
+    ];
+
+    /// The identifier used in inputs and on the command line.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Platform::CiscoIos => "cisco_ios",
+            Platform::JuniperJunos => "juniper_junos",
+            Platform::AristaEos => "arista_eos",
+            Platform::MikrotikRouteros => "mikrotik_routeros",
+            Platform::Vyos => "vyos",
+        }
+    }
+
+    pub fn renderer(&self) -> &'static dyn VlanRenderer {
+        match self {
+            Platform::CiscoIos => &CiscoIos,
+            Platform::JuniperJunos => &JuniperJunos,
+            Platform::AristaEos => &AristaEos,
+            Platform::MikrotikRouteros => &MikrotikRouteros,
+            Platform::Vyos => &Vyos,
+        }
+    }
+}
+
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.renderer().name())
+    }
+}
+
+impl FromStr for Platform {
+    type Err = DikaiosError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let id = s.trim().to_ascii_lowercase().replace('-', "_");
+        Self::ALL.into_iter()
+            .find(|platform| platform.id() == id)
+            .ok_or_else(|| DikaiosError::InvalidInput {
+                field: "platform",
+                message: format!(
+                    "Unknown platform \"{}\", expected one of {}",
+                    s.trim(),
+                    Self::ALL.map(|platform| platform.id()).join(", ")
+                ),
+            })
+    }
+}
+
+/// Renders the configuration for the VLANs of a plan.
+pub trait VlanRenderer {
+    /// The platform's display name, e.g. `"Cisco IOS"`.
+    fn name(&self) -> &'static str;
+
+    /// The prefix of a comment line, e.g. `"!"`.
+    fn comment(&self) -> &'static str;
+
+    /// The configuration for every allocation, in order, followed by the
+    /// access and trunk ports, ready to paste into one device. `router`
+    /// indexes the redundant pair of the plan and is `None` without one.
+    fn render_router(&self, plan: &VlanPlan, router: Option<usize>) -> String;
+
+    /// The configuration for the plan: that of its single layer-3 switch, or
+    /// that of each router of its redundant pair under a heading.
+    fn render(&self, plan: &VlanPlan) -> String {
+        let Some(redundancy) = &plan.redundancy else {
+            return self.render_router(plan, None);
+        };
+        redundancy.routers.iter()
+            .enumerate()
+            .map(|(index, name)| {
+                format!("{} Router {}\n{}", self.comment(), name.trim(), self.render_router(plan, Some(index)))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn has_ports(plan: &VlanPlan) -> bool {
+    !plan.trunks.is_empty() || plan.allocations.iter().any(|vlan| !vlan.access_ports.is_empty())
+}
+
+/// The address `router` puts on the interface of `vlan`: its own address in
+/// a redundant pair, otherwise the gateway.
+fn interface_address(vlan: &VlanAllocation, router: Option<usize>) -> &str {
+    match (&vlan.redundancy, router) {
+        (Some(gateway), Some(index)) => &gateway.router_addresses[index],
+        _ => &vlan.gateway,
+    }
+}
+
+/// The redundancy protocol, virtual gateway and priority `router` runs on
+/// the interface of `vlan`, if the plan has a redundant pair.
+fn first_hop<'a>(
+    plan: &VlanPlan,
+    vlan: &'a VlanAllocation,
+    router: Option<usize>,
+) -> Option<(FhrpProtocol, &'a RedundantGateway, u8)> {
+    let protocol = plan.redundancy.as_ref()?.protocol;
+    let gateway = vlan.redundancy.as_ref()?;
+    let index = router?;
+    Some((protocol, gateway, gateway.priorities[index]))
+}
+
+/// The central DHCP server that `vlan` relays requests to, if any.
+fn helper_address<'a>(plan: &'a VlanPlan, vlan: &VlanAllocation) -> Option<&'a str> {
+    let dhcp = plan.dhcp.as_ref()?;
+    if dhcp.mode != DhcpMode::Relay || vlan.dhcp.is_none() {
+        return None;
+    }
+    dhcp.server.as_deref().map(str::trim)
+}
+
+/// VLAN IDs joined with commas, e.g. `10,20,99`.
+fn vlan_list(vlan_ids: &[u32]) -> String {
+    vlan_ids.iter().map(u32::to_string).collect::<Vec<_>>().join(",")
+}
+
+/// Cisco IOS, as accepted by Cisco Packet Tracer.
+pub struct CiscoIos;
+
+impl VlanRenderer for CiscoIos {
+    fn name(&self) -> &'static str {
+        "Cisco IOS"
+    }
+
+    fn comment(&self) -> &'static str {
+        "!"
+    }
+
+    fn render_router(&self, plan: &VlanPlan, router: Option<usize>) -> String {
+        let mut out = String::new();
+        for vlan in &plan.allocations {
+            out.push_str(&format!("! Configuration for VLAN {} - {}\n", vlan.vlan_id, vlan.vlan_name));
+            out.push_str("enable\n");
+            out.push_str("configure terminal\n");
+            out.push_str(&format!("vlan {}\n", vlan.vlan_id));
+            out.push_str(&format!("name {}\n", vlan.vlan_name));
+            out.push_str("exit\n");
+            out.push_str(&format!("interface vlan {}\n", vlan.vlan_id));
+            out.push_str(&format!("ip address {} {}\n", interface_address(vlan, router), vlan.subnet_mask));
+            if let Some((protocol, gateway, priority)) = first_hop(plan, vlan, router) {
+                let command = match protocol {
+                    FhrpProtocol::Hsrp => {
+                        out.push_str("standby version 2\n");
+                        "standby"
+                    }
+                    FhrpProtocol::Vrrp => "vrrp",
+                    FhrpProtocol::Glbp => "glbp",
+                };
+                out.push_str(&format!("{} {} ip {}\n", command, gateway.group, vlan.gateway));
+                out.push_str(&format!("{} {} priority {}\n", command, gateway.group, priority));
+                out.push_str(&format!("{} {} preempt\n", command, gateway.group));
+            }
+            if let Some(server) = helper_address(plan, vlan) {
+                out.push_str(&format!("ip helper-address {}\n", server));
+            }
+            out.push_str("no shutdown\n");
+            out.push_str("exit\n");
+            out.push_str("exit\n");
+            out.push('\n');
+        }
+
+        // Only the first router of a redundant pair serves the pools, so the
+        // two never hand out the same address
+        let pools: Vec<_> = match &plan.dhcp {
+            Some(dhcp) if dhcp.mode == DhcpMode::Pool && router.unwrap_or(0) == 0 => plan.allocations.iter()
+                .filter_map(|vlan| vlan.dhcp.as_ref().map(|scope| (vlan, scope)))
+                .collect(),
+            _ => Vec::new(),
+        };
+        if !pools.is_empty() {
+            out.push_str("! DHCP pools\n");
+            out.push_str("enable\n");
+            out.push_str("configure terminal\n");
+            for (vlan, scope) in &pools {
+                if scope.excluded_end == vlan.gateway {
+                    out.push_str(&format!("ip dhcp excluded-address {}\n", vlan.gateway));
+                } else {
+                    out.push_str(&format!("ip dhcp excluded-address {} {}\n", vlan.gateway, scope.excluded_end));
+                }
+            }
+            for (vlan, scope) in &pools {
+                out.push_str(&format!("ip dhcp pool VLAN{}\n", vlan.vlan_id));
+                out.push_str(&format!("network {} {}\n", vlan.network, vlan.subnet_mask));
+                out.push_str(&format!("default-router {}\n", vlan.gateway));
+                if !scope.dns_servers.is_empty() {
+                    out.push_str(&format!("dns-server {}\n", scope.dns_servers.join(" ")));
+                }
+                if let Some(domain_name) = &scope.domain_name {
+                    out.push_str(&format!("domain-name {}\n", domain_name));
+                }
+                out.push_str(&format!(
+                    "lease {} {} {}\n",
+                    scope.lease_time / 86_400,
+                    scope.lease_time % 86_400 / 3600,
+                    scope.lease_time % 3600 / 60
+                ));
+                out.push_str("exit\n");
+            }
+            out.push_str("exit\n");
+            out.push('\n');
+        }
+
+        if has_ports(plan) {
+            out.push_str("! Switch port configuration\n");
+            out.push_str("enable\n");
+            out.push_str("configure terminal\n");
+            for vlan in &plan.allocations {
+                for ports in &vlan.access_ports {
+                    out.push_str(&format!("interface range {}\n", ports));
+                    out.push_str("switchport mode access\n");
+                    out.push_str(&format!("switchport access vlan {}\n", vlan.vlan_id));
+                    out.push_str("no shutdown\n");
+                    out.push_str("exit\n");
+                }
+            }
+            for trunk in &plan.trunks {
+                out.push_str(&format!("interface {}\n", trunk.interface));
+                out.push_str("switchport mode trunk\n");
+                if let Some(native_vlan) = trunk.native_vlan {
+                    out.push_str(&format!("switchport trunk native vlan {}\n", native_vlan));
+                }
+                if trunk.allowed_vlans.is_empty() {
+                    out.push_str("switchport trunk allowed vlan none\n");
+                } else {
+                    out.push_str(&format!("switchport trunk allowed vlan {}\n", vlan_list(&trunk.allowed_vlans)));
+                }
+                out.push_str("no shutdown\n");
+                out.push_str("exit\n");
+            }
+            out.push_str("exit\n");
+            out.push('\n');
+        }
+
+        out
+    }
+}
+
+/// Juniper Junos with ELS `set` commands and IRB interfaces.
+pub struct JuniperJunos;
+
+impl VlanRenderer for JuniperJunos {
+    fn name(&self) -> &'static str {
+        "Juniper Junos"
+    }
+
+    fn comment(&self) -> &'static str {
+        "#"
+    }
+
+    fn render_router(&self, plan: &VlanPlan, router: Option<usize>) -> String {
+        let mut out = String::new();
+        for vlan in &plan.allocations {
+            let name = junos_name(&vlan.vlan_name);
+            out.push_str(&format!("set vlans {} vlan-id {}\n", name, vlan.vlan_id));
+            out.push_str(&format!("set vlans {} l3-interface irb.{}\n", name, vlan.vlan_id));
+            let address = format!(
+                "set interfaces irb unit {} family inet address {}/{}",
+                vlan.vlan_id, interface_address(vlan, router), vlan.cidr
+            );
+            out.push_str(&address);
+            out.push('\n');
+            if let Some((_, gateway, priority)) = first_hop(plan, vlan, router) {
+                let group = format!("{} vrrp-group {}", address, gateway.group);
+                out.push_str(&format!("{} virtual-address {}\n", group, vlan.gateway));
+                out.push_str(&format!("{} priority {}\n", group, priority));
+                out.push_str(&format!("{} preempt\n", group));
+                out.push_str(&format!("{} accept-data\n", group));
+            }
+            out.push('\n');
+        }
+
+        for vlan in &plan.allocations {
+            if vlan.access_ports.is_empty() {
+                continue;
+            }
+            let name = junos_name(&vlan.vlan_name);
+            for ports in &vlan.access_ports {
+                out.push_str(&format!("set interfaces interface-range {}-access member {}\n", name, ports));
+            }
+            out.push_str(&format!(
+                "set interfaces interface-range {}-access unit 0 family ethernet-switching interface-mode access vlan members {}\n",
+                name, name
+            ));
+            out.push('\n');
+        }
+
+        for trunk in &plan.trunks {
+            // Members are named after the plan's VLANs; others by their ID
+            let members = trunk.allowed_vlans.iter()
+                .map(|&vlan_id| match plan.allocation(vlan_id) {
+                    Some(vlan) => junos_name(&vlan.vlan_name),
+                    None => vlan_id.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            if let Some(native_vlan) = trunk.native_vlan {
+                out.push_str(&format!("set interfaces {} native-vlan-id {}\n", trunk.interface, native_vlan));
+            }
+            out.push_str(&format!(
+                "set interfaces {} unit 0 family ethernet-switching interface-mode trunk vlan members [ {} ]\n",
+                trunk.interface, members
+            ));
+            out.push('\n');
+        }
+
+        out
+    }
+}
+
+/// Junos names may not contain spaces.
+fn junos_name(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join("-")
+}
+
+/// Arista EOS.
+pub struct AristaEos;
+
+impl VlanRenderer for AristaEos {
+    fn name(&self) -> &'static str {
+        "Arista EOS"
+    }
+
+    fn comment(&self) -> &'static str {
+        "!"
+    }
+
+    fn render_router(&self, plan: &VlanPlan, router: Option<usize>) -> String {
+        let mut out = String::from("configure\n");
+        for vlan in &plan.allocations {
+            out.push_str(&format!("! VLAN {} - {}\n", vlan.vlan_id, vlan.vlan_name));
+            out.push_str(&format!("vlan {}\n", vlan.vlan_id));
+            out.push_str(&format!("   name {}\n", vlan.vlan_name));
+            out.push_str("!\n");
+            out.push_str(&format!("interface Vlan{}\n", vlan.vlan_id));
+            out.push_str(&format!("   ip address {}/{}\n", interface_address(vlan, router), vlan.cidr));
+            if let Some((_, gateway, priority)) = first_hop(plan, vlan, router) {
+                out.push_str(&format!("   vrrp {} priority-level {}\n", gateway.group, priority));
+                out.push_str(&format!("   vrrp {} ipv4 {}\n", gateway.group, vlan.gateway));
+                out.push_str(&format!("   vrrp {} preempt\n", gateway.group));
+            }
+            if let Some(server) = helper_address(plan, vlan) {
+                out.push_str(&format!("   ip helper-address {}\n", server));
+            }
+            out.push_str("   no shutdown\n");
+            out.push_str("!\n");
+        }
+        for vlan in &plan.allocations {
+            for ports in &vlan.access_ports {
+                out.push_str(&format!("interface {}\n", ports));
+                out.push_str("   switchport mode access\n");
+                out.push_str(&format!("   switchport access vlan {}\n", vlan.vlan_id));
+                out.push_str("!\n");
+            }
+        }
+        for trunk in &plan.trunks {
+            out.push_str(&format!("interface {}\n", trunk.interface));
+            out.push_str("   switchport mode trunk\n");
+            if let Some(native_vlan) = trunk.native_vlan {
+                out.push_str(&format!("   switchport trunk native vlan {}\n", native_vlan));
+            }
+            if trunk.allowed_vlans.is_empty() {
+                out.push_str("   switchport trunk allowed vlan none\n");
+            } else {
+                out.push_str(&format!("   switchport trunk allowed vlan {}\n", vlan_list(&trunk.allowed_vlans)));
+            }
+            out.push_str("!\n");
+        }
+        out.push_str("end\n");
+        out
+    }
+}
+
+/// MikroTik RouterOS 7, with the VLANs on a VLAN-filtering bridge named
+/// `bridge`. Trunks are tagged members of the VLANs they carry, except for
+/// their native VLAN, which they get as their PVID.
+pub struct MikrotikRouteros;
+
+impl VlanRenderer for MikrotikRouteros {
+    fn name(&self) -> &'static str {
+        "MikroTik RouterOS"
+    }
+
+    fn comment(&self) -> &'static str {
+        "#"
+    }
+
+    fn render_router(&self, plan: &VlanPlan, router: Option<usize>) -> String {
+        let mut out = String::new();
+        for vlan in &plan.allocations {
+            let interface = format!("vlan{}", vlan.vlan_id);
+            let tagged = std::iter::once("bridge")
+                .chain(plan.trunks_carrying(vlan.vlan_id)
+                    .filter(|trunk| trunk.native_vlan != Some(vlan.vlan_id))
+                    .map(|trunk| trunk.interface.as_str()))
+                .collect::<Vec<_>>()
+                .join(",");
+            out.push_str(&format!("# VLAN {} - {}\n", vlan.vlan_id, vlan.vlan_name));
+            out.push_str(&format!("/interface bridge vlan add bridge=bridge tagged={} vlan-ids={}\n", tagged, vlan.vlan_id));
+            out.push_str(&format!(
+                "/interface vlan add interface=bridge name={} vlan-id={} comment=\"{}\"\n",
+                interface, vlan.vlan_id, vlan.vlan_name
+            ));
+            out.push_str(&format!(
+                "/ip address add address={}/{} interface={}\n",
+                interface_address(vlan, router), vlan.cidr, interface
+            ));
+            if let Some((_, gateway, priority)) = first_hop(plan, vlan, router) {
+                out.push_str(&format!(
+                    "/interface vrrp add interface={} name=vrrp{} vrid={} priority={} preemption-mode=yes\n",
+                    interface, vlan.vlan_id, gateway.group, priority
+                ));
+                out.push_str(&format!("/ip address add address={}/32 interface=vrrp{}\n", vlan.gateway, vlan.vlan_id));
+            }
+            out.push('\n');
+        }
+
+        if has_ports(plan) {
+            out.push_str("# Switch ports\n");
+            for vlan in &plan.allocations {
+                for ports in &vlan.access_ports {
+                    out.push_str(&format!(
+                        "/interface bridge port add bridge=bridge interface={} pvid={} frame-types=admit-only-untagged-and-priority-tagged\n",
+                        ports, vlan.vlan_id
+                    ));
+                }
+            }
+            for trunk in &plan.trunks {
+                match trunk.native_vlan {
+                    Some(native_vlan) => out.push_str(&format!(
+                        "/interface bridge port add bridge=bridge interface={} pvid={}\n",
+                        trunk.interface, native_vlan
+                    )),
+                    None => out.push_str(&format!(
+                        "/interface bridge port add bridge=bridge interface={} frame-types=admit-only-vlan-tagged\n",
+                        trunk.interface
+                    )),
+                }
+            }
+            out.push('\n');
+        }
+
+        out
+    }
+}
+
+/// VyOS, with the VLANs as `vif` subinterfaces of `eth0`.
+pub struct Vyos;
+
+impl VlanRenderer for Vyos {
+    fn name(&self) -> &'static str {
+        "VyOS"
+    }
+
+    fn comment(&self) -> &'static str {
+        "#"
+    }
+
+    fn render_router(&self, plan: &VlanPlan, router: Option<usize>) -> String {
+        let mut out = String::from("configure\n");
+        for vlan in &plan.allocations {
+            out.push_str(&format!("set interfaces ethernet eth0 vif {} description '{}'\n", vlan.vlan_id, vlan.vlan_name));
+            out.push_str(&format!(
+                "set interfaces ethernet eth0 vif {} address '{}/{}'\n",
+                vlan.vlan_id, interface_address(vlan, router), vlan.cidr
+            ));
+            if let Some((_, gateway, priority)) = first_hop(plan, vlan, router) {
+                let group = format!("set high-availability vrrp group VLAN{}", vlan.vlan_id);
+                out.push_str(&format!("{} interface 'eth0.{}'\n", group, vlan.vlan_id));
+                out.push_str(&format!("{} vrid '{}'\n", group, gateway.group));
+                out.push_str(&format!("{} address '{}/{}'\n", group, vlan.gateway, vlan.cidr));
+                out.push_str(&format!("{} priority '{}'\n", group, priority));
+            }
+        }
+        if has_ports(plan) {
+            out.push_str("# VyOS is a router: configure the access and trunk ports on the switch\n");
+        }
+        out.push_str("commit\n");
+        out.push_str("save\n");
+        out
+    }
+}
@@ -0,0 +1,398 @@
+//! Typed IPv4 and IPv6 prefixes shared by every calculator.
+
+use super::error::{parse_number, DikaiosError};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+/// Number of addresses in an IPv6 /0, which is one more than `u128::MAX`.
+pub(crate) const IPV6_ALL_ADDRESSES: &str = "340282366920938463463374607431768211456";
+
+/// The address family a prefix or result belongs to.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum AddressFamily {
+    Ipv4,
+    Ipv6,
+}
+
+/// Parses a dotted-quad IPv4 address such as `192.168.1.10`, reporting
+/// problems against the input `field`.
+pub fn parse_ipv4(ip: &str, field: &'static str) -> Result<Ipv4Addr, DikaiosError> {
+    let ip_parts: Vec<&str> = ip.trim().split('.').collect();
+    if ip_parts.len() != 4 {
+        return Err(DikaiosError::InvalidAddress { field });
+    }
+
+    let mut octets = [0u8; 4];
+    for (i, part) in ip_parts.iter().enumerate() {
+        let value: u32 = part.parse()
+            .map_err(|_| DikaiosError::InvalidOctet { field, octet: i })?;
+        octets[i] = u8::try_from(value)
+            .map_err(|_| DikaiosError::OctetOutOfRange { field, octet: i })?;
+    }
+
+    Ok(Ipv4Addr::from(octets))
+}
+
+/// Parses an IPv6 address in any notation accepted by [`Ipv6Addr`].
+pub fn parse_ipv6(ip: &str, field: &'static str) -> Result<Ipv6Addr, DikaiosError> {
+    ip.trim().parse()
+        .map_err(|_| DikaiosError::InvalidAddress { field })
+}
+
+/// An IPv4 address together with a prefix length, e.g. `192.168.1.10/24`.
+///
+/// The address keeps its host bits; use [`Ipv4Prefix::network`] or
+/// [`Ipv4Prefix::trunc`] to get the network itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ipv4Prefix {
+    addr: Ipv4Addr,
+    len: u8,
+}
+
+impl Ipv4Prefix {
+    pub const MAX_LEN: u32 = 32;
+
+    /// Creates a prefix, failing if `len` is greater than 32.
+    pub fn new(addr: Ipv4Addr, len: u32) -> Result<Self, DikaiosError> {
+        if len > Self::MAX_LEN {
+            return Err(DikaiosError::OutOfRange { field: "prefix_length", min: 0, max: Self::MAX_LEN as u64 });
+        }
+        Ok(Self { addr, len: len as u8 })
+    }
+
+    pub fn addr(&self) -> Ipv4Addr {
+        self.addr
+    }
+
+    pub fn prefix_len(&self) -> u32 {
+        self.len as u32
+    }
+
+    /// The host-bit mask as an integer, e.g. `0xff` for a /24.
+    pub fn hostmask_bits(&self) -> u32 {
+        if self.len == 0 { u32::MAX } else { (1u32 << (32 - self.len)) - 1 }
+    }
+
+    pub fn netmask(&self) -> Ipv4Addr {
+        Ipv4Addr::from(!self.hostmask_bits())
+    }
+
+    /// The Cisco-style wildcard mask, i.e. the inverted netmask.
+    pub fn hostmask(&self) -> Ipv4Addr {
+        Ipv4Addr::from(self.hostmask_bits())
+    }
+
+    pub fn network(&self) -> Ipv4Addr {
+        Ipv4Addr::from(u32::from(self.addr) & !self.hostmask_bits())
+    }
+
+    /// The last address of the prefix.
+    pub fn broadcast(&self) -> Ipv4Addr {
+        Ipv4Addr::from(u32::from(self.addr) | self.hostmask_bits())
+    }
+
+    /// Total number of addresses, including network and broadcast.
+    pub fn size(&self) -> u64 {
+        1u64 << (32 - self.len)
+    }
+
+    /// Whether the prefix has network and broadcast addresses. A /31 is a
+    /// point-to-point link where both addresses are hosts (RFC 3021), and a
+    /// /32 is a single host.
+    pub fn has_broadcast(&self) -> bool {
+        self.len < 31
+    }
+
+    /// The broadcast address, or `None` for a /31 or /32.
+    pub fn broadcast_addr(&self) -> Option<Ipv4Addr> {
+        self.has_broadcast().then(|| self.broadcast())
+    }
+
+    /// The first address that can be given to a host.
+    pub fn first_host(&self) -> Ipv4Addr {
+        if self.has_broadcast() {
+            Ipv4Addr::from(u32::from(self.network()) + 1)
+        } else {
+            self.network()
+        }
+    }
+
+    /// The last address that can be given to a host.
+    pub fn last_host(&self) -> Ipv4Addr {
+        if self.has_broadcast() {
+            Ipv4Addr::from(u32::from(self.broadcast()) - 1)
+        } else {
+            self.broadcast()
+        }
+    }
+
+    /// Number of addresses that can be given to hosts.
+    pub fn host_count(&self) -> u32 {
+        if self.has_broadcast() { (self.size() - 2) as u32 } else { self.size() as u32 }
+    }
+
+    /// The same prefix with its host bits cleared.
+    pub fn trunc(&self) -> Self {
+        Self { addr: self.network(), len: self.len }
+    }
+
+    pub fn contains(&self, addr: Ipv4Addr) -> bool {
+        u32::from(addr) & !self.hostmask_bits() == u32::from(self.network())
+    }
+
+    /// The fewest prefixes that exactly cover `first..=last`.
+    pub fn summarize(first: Ipv4Addr, last: Ipv4Addr) -> Vec<Self> {
+        let mut start = u32::from(first) as u64;
+        let end = u32::from(last) as u64;
+        let mut blocks = Vec::new();
+
+        while start <= end {
+            // The largest block aligned at `start` that does not pass `end`
+            let mut bits = start.trailing_zeros().min(32);
+            while (1u64 << bits) > end - start + 1 {
+                bits -= 1;
+            }
+            blocks.push(Self { addr: Ipv4Addr::from(start as u32), len: (32 - bits) as u8 });
+            start += 1u64 << bits;
+        }
+
+        blocks
+    }
+}
+
+impl fmt::Display for Ipv4Prefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.len)
+    }
+}
+
+impl FromStr for Ipv4Prefix {
+    type Err = DikaiosError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (addr, len) = split_prefix(s)?;
+        Self::new(parse_ipv4(addr, "prefix")?, len)
+            .map_err(|e| e.with_field("prefix"))
+    }
+}
+
+/// An IPv6 address together with a prefix length, e.g. `2001:db8::1/64`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ipv6Prefix {
+    addr: Ipv6Addr,
+    len: u8,
+}
+
+impl Ipv6Prefix {
+    pub const MAX_LEN: u32 = 128;
+
+    /// Creates a prefix, failing if `len` is greater than 128.
+    pub fn new(addr: Ipv6Addr, len: u32) -> Result<Self, DikaiosError> {
+        if len > Self::MAX_LEN {
+            return Err(DikaiosError::OutOfRange { field: "prefix_length", min: 0, max: Self::MAX_LEN as u64 });
+        }
+        Ok(Self { addr, len: len as u8 })
+    }
+
+    pub fn addr(&self) -> Ipv6Addr {
+        self.addr
+    }
+
+    pub fn prefix_len(&self) -> u32 {
+        self.len as u32
+    }
+
+    /// The host-bit mask as an integer.
+    pub fn hostmask_bits(&self) -> u128 {
+        if self.len == 0 { u128::MAX } else { (1u128 << (128 - self.len)) - 1 }
+    }
+
+    pub fn netmask(&self) -> Ipv6Addr {
+        Ipv6Addr::from(!self.hostmask_bits())
+    }
+
+    pub fn hostmask(&self) -> Ipv6Addr {
+        Ipv6Addr::from(self.hostmask_bits())
+    }
+
+    pub fn network(&self) -> Ipv6Addr {
+        Ipv6Addr::from(u128::from(self.addr) & !self.hostmask_bits())
+    }
+
+    /// The last address of the prefix. IPv6 has no broadcast address.
+    pub fn last(&self) -> Ipv6Addr {
+        Ipv6Addr::from(u128::from(self.addr) | self.hostmask_bits())
+    }
+
+    /// Total number of addresses, or `None` for a /0, which holds 2^128.
+    pub fn size(&self) -> Option<u128> {
+        self.hostmask_bits().checked_add(1)
+    }
+
+    pub fn trunc(&self) -> Self {
+        Self { addr: self.network(), len: self.len }
+    }
+
+    pub fn contains(&self, addr: Ipv6Addr) -> bool {
+        u128::from(addr) & !self.hostmask_bits() == u128::from(self.network())
+    }
+
+    /// The fewest prefixes that exactly cover `first..=last`.
+    pub fn summarize(first: Ipv6Addr, last: Ipv6Addr) -> Vec<Self> {
+        let mut start = u128::from(first);
+        let end = u128::from(last);
+        let mut blocks = Vec::new();
+
+        while start <= end {
+            // floor(log2(end - start + 1)), which is 128 for the whole space
+            let span = end - start;
+            let max_bits = match span.checked_add(1) {
+                Some(count) => 127 - count.leading_zeros(),
+                None => 128,
+            };
+            let bits = start.trailing_zeros().min(max_bits);
+            blocks.push(Self { addr: Ipv6Addr::from(start), len: (128 - bits) as u8 });
+
+            match 1u128.checked_shl(bits).and_then(|size| start.checked_add(size)) {
+                Some(next) => start = next,
+                None => break,
+            }
+        }
+
+        blocks
+    }
+}
+
+impl fmt::Display for Ipv6Prefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.len)
+    }
+}
+
+impl FromStr for Ipv6Prefix {
+    type Err = DikaiosError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (addr, len) = split_prefix(s)?;
+        Self::new(parse_ipv6(addr, "prefix")?, len)
+            .map_err(|e| e.with_field("prefix"))
+    }
+}
+
+/// A prefix of either address family.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Prefix {
+    V4(Ipv4Prefix),
+    V6(Ipv6Prefix),
+}
+
+impl Prefix {
+    pub fn family(&self) -> AddressFamily {
+        match self {
+            Prefix::V4(_) => AddressFamily::Ipv4,
+            Prefix::V6(_) => AddressFamily::Ipv6,
+        }
+    }
+
+    pub fn prefix_len(&self) -> u32 {
+        match self {
+            Prefix::V4(prefix) => prefix.prefix_len(),
+            Prefix::V6(prefix) => prefix.prefix_len(),
+        }
+    }
+
+    pub fn trunc(&self) -> Self {
+        match self {
+            Prefix::V4(prefix) => Prefix::V4(prefix.trunc()),
+            Prefix::V6(prefix) => Prefix::V6(prefix.trunc()),
+        }
+    }
+
+    /// The first and last address of the prefix as integers, so prefixes of
+    /// either family can be compared and ordered the same way.
+    pub fn bounds(&self) -> (u128, u128) {
+        match self {
+            Prefix::V4(prefix) => (u32::from(prefix.network()) as u128, u32::from(prefix.broadcast()) as u128),
+            Prefix::V6(prefix) => (u128::from(prefix.network()), u128::from(prefix.last())),
+        }
+    }
+
+    /// Whether every address of `other` is also in this prefix. Prefixes of
+    /// different families never contain each other.
+    pub fn contains_prefix(&self, other: &Prefix) -> bool {
+        let ((first, last), (other_first, other_last)) = (self.bounds(), other.bounds());
+        self.family() == other.family() && first <= other_first && other_last <= last
+    }
+
+    /// Whether the two prefixes share at least one address.
+    pub fn overlaps(&self, other: &Prefix) -> bool {
+        let ((first, last), (other_first, other_last)) = (self.bounds(), other.bounds());
+        self.family() == other.family() && first <= other_last && other_first <= last
+    }
+
+    /// Whether `addr` is in this prefix.
+    pub fn contains_addr(&self, addr: IpAddr) -> bool {
+        match (self, addr) {
+            (Prefix::V4(prefix), IpAddr::V4(addr)) => prefix.contains(addr),
+            (Prefix::V6(prefix), IpAddr::V6(addr)) => prefix.contains(addr),
+            _ => false,
+        }
+    }
+
+    /// Total number of addresses as a decimal string, since an IPv6 count
+    /// can be larger than any integer type JSON clients can hold.
+    pub fn address_count(&self) -> String {
+        match self {
+            Prefix::V4(prefix) => prefix.size().to_string(),
+            Prefix::V6(prefix) => match prefix.size() {
+                Some(size) => size.to_string(),
+                None => IPV6_ALL_ADDRESSES.to_string(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Prefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Prefix::V4(prefix) => prefix.fmt(f),
+            Prefix::V6(prefix) => prefix.fmt(f),
+        }
+    }
+}
+
+impl FromStr for Prefix {
+    type Err = DikaiosError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.contains(':') {
+            s.parse().map(Prefix::V6)
+        } else {
+            s.parse().map(Prefix::V4)
+        }
+    }
+}
+
+impl From<Ipv4Prefix> for Prefix {
+    fn from(prefix: Ipv4Prefix) -> Self {
+        Prefix::V4(prefix)
+    }
+}
+
+impl From<Ipv6Prefix> for Prefix {
+    fn from(prefix: Ipv6Prefix) -> Self {
+        Prefix::V6(prefix)
+    }
+}
+
+fn split_prefix(s: &str) -> Result<(&str, u32), DikaiosError> {
+    let (addr, len) = s.trim().split_once('/')
+        .ok_or(DikaiosError::InvalidInput {
+            field: "prefix",
+            message: "Prefix must be written as address/length".to_string(),
+        })?;
+    Ok((addr, parse_number(len, "prefix")?))
+}
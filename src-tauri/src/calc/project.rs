@@ -0,0 +1,227 @@
+//! Project files: the VLSM plans, VLAN plans and wireless networks of one
+//! address plan, saved together as JSON or TOML.
+//!
+//! Every file records the [`PROJECT_VERSION`] it was written with. Loading
+//! parses the file into a generic value first and runs it through the
+//! migrations from its version up to the current one, so files written by
+//! older versions keep opening after the model changes. Files from a newer
+//! version are rejected rather than read with fields silently dropped.
+//!
+//! Wireless networks are saved as their generated configuration only; the
+//! input is left out because it holds the passphrase and enable secret. A
+//! configuration generated without `mask_passphrase` still holds the
+//! passphrase in plain text, so it is only saved when its `keep_secrets` is
+//! set.
+
+use super::error::DikaiosError;
+use super::vlan::{TrunkPort, VlanAllocation, VlanInput};
+use super::vlsm::{UnusedSpace, VlsmInput, VlsmSubnet};
+use super::wireless::WirelessNetworkConfig;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// The project file version written by this build.
+pub const PROJECT_VERSION: u32 = 2;
+
+/// Upgrades a project object from one version to the next, in place.
+type Migration = fn(&mut Map<String, Value>) -> Result<(), DikaiosError>;
+
+/// `MIGRATIONS[n]` upgrades a version `n + 1` project to version `n + 2`.
+const MIGRATIONS: [Migration; (PROJECT_VERSION - 1) as usize] = [keep_saved_secrets];
+
+/// A saved address plan.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct Project {
+    #[serde(default)]
+    pub version: u32,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub vlsm: Vec<SavedVlsm>,
+    #[serde(default)]
+    pub vlans: Vec<SavedVlanPlan>,
+    #[serde(default)]
+    pub wireless: Vec<SavedWirelessConfig>,
+}
+
+/// A VLSM request together with the subnets it produced.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SavedVlsm {
+    #[serde(default)]
+    pub name: String,
+    pub input: VlsmInput,
+    pub subnets: Vec<VlsmSubnet>,
+    pub unused: UnusedSpace,
+}
+
+/// A VLAN plan request together with its allocations and the device
+/// configuration rendered for its platform.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SavedVlanPlan {
+    #[serde(default)]
+    pub name: String,
+    pub input: VlanInput,
+    pub allocations: Vec<VlanAllocation>,
+    #[serde(default)]
+    pub trunks: Vec<TrunkPort>,
+    #[serde(default)]
+    pub commands: String,
+    pub unused: UnusedSpace,
+}
+
+/// The generated configuration of a wireless network.
+///
+/// `keep_secrets` is the user's consent to saving a configuration that
+/// holds its passphrase, i.e. one that is not
+/// [masked](WirelessNetworkConfig::is_masked).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SavedWirelessConfig {
+    #[serde(default)]
+    pub name: String,
+    pub config: WirelessNetworkConfig,
+    #[serde(default)]
+    pub keep_secrets: bool,
+}
+
+impl Project {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { version: PROJECT_VERSION, name: name.into(), ..Self::default() }
+    }
+}
+
+/// The file formats a project can be saved in.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectFormat {
+    #[default]
+    Json,
+    Toml,
+}
+
+impl ProjectFormat {
+    pub const ALL: [ProjectFormat; 2] = [ProjectFormat::Json, ProjectFormat::Toml];
+
+    /// The identifier used in inputs and on the command line.
+    pub fn id(&self) -> &'static str {
+        match self {
+            ProjectFormat::Json => "json",
+            ProjectFormat::Toml => "toml",
+        }
+    }
+
+    /// The format of a project file named `path`: TOML for a `.toml`
+    /// extension, JSON otherwise.
+    pub fn from_path(path: impl AsRef<Path>) -> Self {
+        match path.as_ref().extension() {
+            Some(extension) if extension.eq_ignore_ascii_case("toml") => ProjectFormat::Toml,
+            _ => ProjectFormat::Json,
+        }
+    }
+}
+
+impl fmt::Display for ProjectFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ProjectFormat::Json => "JSON",
+            ProjectFormat::Toml => "TOML",
+        })
+    }
+}
+
+/// `project` as the text of a project file in `format`, stamped with the
+/// current [`PROJECT_VERSION`].
+///
+/// Fails without writing anything if a wireless configuration holds its
+/// passphrase and `keep_secrets` is not set for it.
+pub fn project_to_string(project: &Project, format: ProjectFormat) -> Result<String, DikaiosError> {
+    if let Some(saved) = project.wireless.iter().find(|saved| !saved.config.is_masked() && !saved.keep_secrets) {
+        return Err(DikaiosError::InvalidInput {
+            field: "wireless",
+            message: format!(
+                "The configuration of {} contains its passphrase; generate it with the passphrase masked, \
+                 or allow saving the passphrase in the project",
+                saved.config.ssid
+            ),
+        });
+    }
+
+    let project = Project { version: PROJECT_VERSION, ..project.clone() };
+    match format {
+        ProjectFormat::Json => serde_json::to_string_pretty(&project).map_err(|e| invalid_project(e.to_string())),
+        ProjectFormat::Toml => toml::to_string_pretty(&project).map_err(|e| invalid_project(e.to_string())),
+    }
+}
+
+/// Reads a project from the text of a project file in `format`, migrating
+/// it from the version it was written with.
+pub fn project_from_str(text: &str, format: ProjectFormat) -> Result<Project, DikaiosError> {
+    let value = match format {
+        ProjectFormat::Json => serde_json::from_str::<Value>(text).map_err(|e| invalid_project(e.to_string()))?,
+        ProjectFormat::Toml => toml::from_str::<Value>(text).map_err(|e| invalid_project(e.to_string()))?,
+    };
+    let Value::Object(mut object) = value else {
+        return Err(invalid_project("The file does not contain a project".to_string()));
+    };
+
+    migrate_project(&mut object)?;
+    serde_json::from_value(Value::Object(object)).map_err(|e| invalid_project(e.to_string()))
+}
+
+/// Writes `project` to `path` in the format its extension names.
+pub fn save_project(project: &Project, path: impl AsRef<Path>) -> Result<(), DikaiosError> {
+    let path = path.as_ref();
+    let text = project_to_string(project, ProjectFormat::from_path(path))?;
+    fs::write(path, text).map_err(|e| DikaiosError::Io { field: "path", message: e.to_string() })
+}
+
+/// Reads the project file at `path` in the format its extension names.
+pub fn load_project(path: impl AsRef<Path>) -> Result<Project, DikaiosError> {
+    let path = path.as_ref();
+    let text = fs::read_to_string(path).map_err(|e| DikaiosError::Io { field: "path", message: e.to_string() })?;
+    project_from_str(&text, ProjectFormat::from_path(path))
+}
+
+/// Brings a project object up to [`PROJECT_VERSION`] one version at a time.
+fn migrate_project(project: &mut Map<String, Value>) -> Result<(), DikaiosError> {
+    let version = match project.get("version") {
+        Some(version) => version.as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .filter(|version| *version >= 1)
+            .ok_or_else(|| invalid_project(format!("Unknown project file version {}", version)))?,
+        None => return Err(invalid_project("The file has no project version".to_string())),
+    };
+    if version > PROJECT_VERSION {
+        return Err(invalid_project(format!(
+            "The project was saved by a newer version of Dikaios (file version {}, this version reads up to {})",
+            version, PROJECT_VERSION
+        )));
+    }
+
+    for (from, migration) in MIGRATIONS.iter().enumerate().skip(version as usize - 1) {
+        migration(project)?;
+        project.insert("version".to_string(), Value::from(from as u32 + 2));
+    }
+    Ok(())
+}
+
+/// Version 1 saved every wireless configuration, passphrase and all. Those
+/// files already hold the secrets, so the configurations that are not
+/// masked keep being saved rather than failing the next save. A
+/// configuration without `masked` counts as holding its passphrase.
+fn keep_saved_secrets(project: &mut Map<String, Value>) -> Result<(), DikaiosError> {
+    let Some(Value::Array(wireless)) = project.get_mut("wireless") else {
+        return Ok(());
+    };
+    for saved in wireless.iter_mut().filter_map(Value::as_object_mut) {
+        let masked = saved.get("config").and_then(|config| config.get("masked")).and_then(Value::as_bool);
+        saved.insert("keep_secrets".to_string(), Value::Bool(masked != Some(true)));
+    }
+    Ok(())
+}
+
+fn invalid_project(message: String) -> DikaiosError {
+    DikaiosError::InvalidInput { field: "project", message }
+}
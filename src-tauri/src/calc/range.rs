@@ -0,0 +1,217 @@
+//! Enumeration of the usable hosts in an IPv4 subnet.
+//!
+//! A subnet can hold billions of hosts, so they are never collected all at
+//! once: [`calculate_host_page`] returns one page of them, and
+//! [`HostRange::for_each_chunk`] and [`HostRange::write_to`] stream them.
+
+use super::error::DikaiosError;
+use super::mask::parse_ipv4_mask;
+use super::prefix::{parse_ipv4, Ipv4Prefix};
+use serde::{Deserialize, Serialize};
+use std::io::{self, Write};
+use std::net::Ipv4Addr;
+
+/// Page size used when the input does not set a `limit`.
+pub const DEFAULT_PAGE_SIZE: u64 = 256;
+
+/// The largest page a single request may ask for.
+pub const MAX_PAGE_SIZE: u64 = 65_536;
+
+/// The most hosts a filtered page request looks at, a /12 worth. Matches
+/// past this point are not found; the page reports the scan as truncated.
+pub const MAX_FILTER_SCAN: u64 = 1 << 20;
+
+/// An address and mask whose hosts should be listed.
+#[derive(Debug, Deserialize, Clone)]
+pub struct IpInput {
+    pub ip_address: String,
+    pub subnet_mask: String,
+    /// Number of matching hosts to skip.
+    #[serde(default)]
+    pub offset: u64,
+    /// Number of hosts to return, [`DEFAULT_PAGE_SIZE`] if unset.
+    #[serde(default)]
+    pub limit: Option<u64>,
+    /// Only list hosts whose address contains this text.
+    #[serde(default)]
+    pub filter: Option<String>,
+}
+
+impl IpInput {
+    pub fn new(ip_address: impl Into<String>, subnet_mask: impl Into<String>) -> Self {
+        Self { ip_address: ip_address.into(), subnet_mask: subnet_mask.into(), offset: 0, limit: None, filter: None }
+    }
+
+    pub fn with_page(mut self, offset: u64, limit: u64) -> Self {
+        self.offset = offset;
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn with_filter(mut self, filter: impl Into<String>) -> Self {
+        self.filter = Some(filter.into());
+        self
+    }
+}
+
+/// One page of hosts and whether more follow it.
+#[derive(Debug, Serialize, Clone)]
+pub struct HostPage {
+    pub hosts: Vec<String>,
+    pub offset: u64,
+    pub limit: u64,
+    /// Number of hosts matching the filter overall, if known. A filtered
+    /// request stops scanning once it has filled the page, so this is only
+    /// set when the scan reached the end of the range.
+    pub total: Option<u64>,
+    /// Whether matching hosts follow this page.
+    pub more: bool,
+    /// Whether the scan stopped after [`MAX_FILTER_SCAN`] hosts, so matches
+    /// further into the range were not looked for.
+    pub truncated: bool,
+}
+
+/// The usable hosts of a subnet, excluding the network and broadcast
+/// addresses, without materialising them. Both addresses of a /31 and the
+/// single address of a /32 are hosts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HostRange {
+    start: u64,
+    end: u64,
+}
+
+impl HostRange {
+    pub fn new(prefix: Ipv4Prefix) -> Self {
+        let first_host = u32::from(prefix.first_host()) as u64;
+        let last_host = u32::from(prefix.last_host()) as u64;
+
+        Self { start: first_host, end: last_host + 1 }
+    }
+
+    /// Number of hosts in the range.
+    pub fn len(&self) -> u64 {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Ipv4Addr> {
+        (self.start..self.end).map(|ip| Ipv4Addr::from(ip as u32))
+    }
+
+    /// The hosts whose dotted address contains `filter`, or every host if
+    /// there is no filter.
+    pub fn matching<'a>(&self, filter: Option<&'a str>) -> impl Iterator<Item = String> + 'a {
+        let filter = filter.map(str::trim).filter(|filter| !filter.is_empty());
+        self.iter()
+            .map(|ip| ip.to_string())
+            .filter(move |ip| filter.is_none_or(|filter| ip.contains(filter)))
+    }
+
+    /// Passes the matching hosts to `sink` in chunks of at most `chunk_size`
+    /// and returns how many hosts were sent.
+    pub fn for_each_chunk<E>(
+        &self,
+        filter: Option<&str>,
+        chunk_size: usize,
+        mut sink: impl FnMut(Vec<String>) -> Result<(), E>,
+    ) -> Result<u64, E> {
+        let chunk_size = chunk_size.max(1);
+        let mut chunk = Vec::with_capacity(chunk_size);
+        let mut sent = 0;
+
+        for ip in self.matching(filter) {
+            chunk.push(ip);
+            if chunk.len() == chunk_size {
+                sent += chunk.len() as u64;
+                sink(std::mem::replace(&mut chunk, Vec::with_capacity(chunk_size)))?;
+            }
+        }
+        if !chunk.is_empty() {
+            sent += chunk.len() as u64;
+            sink(chunk)?;
+        }
+
+        Ok(sent)
+    }
+
+    /// Writes the matching hosts to `writer`, one per line, and returns how
+    /// many were written.
+    pub fn write_to<W: Write>(&self, filter: Option<&str>, writer: W) -> io::Result<u64> {
+        let mut writer = io::BufWriter::new(writer);
+        let mut written = 0;
+
+        for ip in self.matching(filter) {
+            writeln!(writer, "{}", ip)?;
+            written += 1;
+        }
+        writer.flush()?;
+
+        Ok(written)
+    }
+}
+
+/// Parses the subnet of `ip_address` and returns its usable hosts.
+pub fn host_range(ip_address: &str, subnet_mask: &str) -> Result<HostRange, DikaiosError> {
+    let mask_bits = parse_ipv4_mask(subnet_mask, "subnet_mask")?;
+    let prefix = Ipv4Prefix::new(parse_ipv4(ip_address, "ip_address")?, mask_bits)
+        .map_err(|e| e.with_field("subnet_mask"))?;
+
+    Ok(HostRange::new(prefix))
+}
+
+/// Returns the page of hosts selected by the input's `offset`, `limit` and
+/// `filter`.
+///
+/// Without a filter the page is computed directly; with one, the range is
+/// scanned from its start until the page and one more match are found, for
+/// at most [`MAX_FILTER_SCAN`] hosts.
+pub fn calculate_host_page(input: &IpInput) -> Result<HostPage, DikaiosError> {
+    let range = host_range(&input.ip_address, &input.subnet_mask)?;
+    let limit = input.limit.unwrap_or(DEFAULT_PAGE_SIZE);
+    if !(1..=MAX_PAGE_SIZE).contains(&limit) {
+        return Err(DikaiosError::OutOfRange { field: "limit", min: 1, max: MAX_PAGE_SIZE });
+    }
+
+    let filter = input.filter.as_deref().map(str::trim).filter(|filter| !filter.is_empty());
+    let Some(filter) = filter else {
+        let start = range.start.saturating_add(input.offset).min(range.end);
+        let end = start.saturating_add(limit).min(range.end);
+        let page = HostRange { start, end };
+        return Ok(HostPage {
+            hosts: page.iter().map(|ip| ip.to_string()).collect(),
+            offset: input.offset,
+            limit,
+            total: Some(range.len()),
+            more: end < range.end,
+            truncated: false,
+        });
+    };
+
+    let scanned = HostRange { start: range.start, end: range.end.min(range.start + MAX_FILTER_SCAN) };
+    let truncated = scanned.end < range.end;
+    let mut hosts = Vec::new();
+    let mut matched = 0;
+    let mut more = false;
+    for ip in scanned.matching(Some(filter)) {
+        if (hosts.len() as u64) == limit {
+            more = true;
+            break;
+        }
+        if matched >= input.offset {
+            hosts.push(ip);
+        }
+        matched += 1;
+    }
+
+    Ok(HostPage {
+        hosts,
+        offset: input.offset,
+        limit,
+        total: (!more && !truncated).then_some(matched),
+        more,
+        truncated,
+    })
+}
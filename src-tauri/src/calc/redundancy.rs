@@ -0,0 +1,188 @@
+//! First-hop redundancy for VLANs routed by a pair of layer-3 switches.
+//!
+//! With redundancy the gateway of every VLAN becomes a virtual address
+//! shared by both routers, and each router gets a real address of its own
+//! right after it. Hosts start after the router addresses. The routers take
+//! turns being active: the first VLAN of the plan is active on the first
+//! router, the second on the second, and so on, so both carry traffic while
+//! either can take over every VLAN.
+
+use super::diagnostic::Diagnostic;
+use super::error::DikaiosError;
+use super::platform::Platform;
+use super::prefix::parse_ipv4;
+use super::vlan::VlanPlan;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::net::Ipv4Addr;
+use std::str::FromStr;
+
+/// Priority of the router that is active for a VLAN.
+pub const ACTIVE_PRIORITY: u8 = 110;
+
+/// Priority of the router that stands by for a VLAN.
+pub const STANDBY_PRIORITY: u8 = 100;
+
+/// Addresses each VLAN needs for the routers on top of its hosts, the
+/// virtual gateway among them.
+pub const ROUTER_ADDRESSES: u32 = 2;
+
+/// The first-hop redundancy protocol the routers run.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum FhrpProtocol {
+    Hsrp,
+    Vrrp,
+    Glbp,
+}
+
+impl FhrpProtocol {
+    pub const ALL: [FhrpProtocol; 3] = [FhrpProtocol::Hsrp, FhrpProtocol::Vrrp, FhrpProtocol::Glbp];
+
+    /// The identifier used in inputs and on the command line.
+    pub fn id(&self) -> &'static str {
+        match self {
+            FhrpProtocol::Hsrp => "hsrp",
+            FhrpProtocol::Vrrp => "vrrp",
+            FhrpProtocol::Glbp => "glbp",
+        }
+    }
+
+    /// The highest group number the protocol allows.
+    pub fn max_group(&self) -> u32 {
+        match self {
+            FhrpProtocol::Hsrp => 4095,
+            FhrpProtocol::Vrrp => 255,
+            FhrpProtocol::Glbp => 1023,
+        }
+    }
+
+    /// Whether `platform` speaks the protocol. HSRP and GLBP are Cisco's own.
+    pub fn supported_on(&self, platform: Platform) -> bool {
+        *self == FhrpProtocol::Vrrp || platform == Platform::CiscoIos
+    }
+}
+
+impl fmt::Display for FhrpProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            FhrpProtocol::Hsrp => "HSRP",
+            FhrpProtocol::Vrrp => "VRRP",
+            FhrpProtocol::Glbp => "GLBP",
+        })
+    }
+}
+
+impl FromStr for FhrpProtocol {
+    type Err = DikaiosError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let id = s.trim().to_ascii_lowercase();
+        Self::ALL.into_iter()
+            .find(|protocol| protocol.id() == id)
+            .ok_or_else(|| DikaiosError::InvalidInput {
+                field: "protocol",
+                message: format!(
+                    "Unknown redundancy protocol \"{}\", expected one of {}",
+                    s.trim(),
+                    Self::ALL.map(|protocol| protocol.id()).join(", ")
+                ),
+            })
+    }
+}
+
+/// The redundant router pair of a plan, by hostname.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct RedundancyOptions {
+    pub protocol: FhrpProtocol,
+    #[serde(default = "default_routers")]
+    pub routers: [String; 2],
+}
+
+fn default_routers() -> [String; 2] {
+    ["SW1".to_string(), "SW2".to_string()]
+}
+
+impl RedundancyOptions {
+    pub fn new(protocol: FhrpProtocol) -> Self {
+        RedundancyOptions { protocol, routers: default_routers() }
+    }
+
+    /// Problems with the options that stop the plan, or that `platform`
+    /// cannot configure.
+    pub(crate) fn validate(&self, platform: Platform) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        if !self.protocol.supported_on(platform) {
+            diagnostics.push(
+                Diagnostic::error("redundancy", format!("{} does not support {}", platform, self.protocol))
+                    .suggest("Use VRRP, which every platform supports"),
+            );
+        }
+        let [first, second] = &self.routers;
+        if first.trim().is_empty() || second.trim().is_empty() {
+            diagnostics.push(
+                Diagnostic::error("redundancy", "Both routers of the redundant pair need a name")
+                    .suggest("Enter the hostnames of both layer-3 switches"),
+            );
+        } else if first.trim() == second.trim() {
+            diagnostics.push(
+                Diagnostic::error("redundancy", format!("Both routers are named {}", first.trim()))
+                    .suggest("Give the routers different hostnames"),
+            );
+        }
+
+        diagnostics
+    }
+}
+
+/// The virtual gateway of one VLAN and how the routers share it.
+///
+/// `router_addresses` and `priorities` are in the order of the routers in
+/// [`RedundancyOptions`]; `active_router` indexes them. `group` is the VLAN
+/// ID where the protocol allows it and 1 otherwise; groups only need to be
+/// unique per interface.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct RedundantGateway {
+    pub group: u32,
+    pub router_addresses: [String; 2],
+    pub priorities: [u8; 2],
+    pub active_router: usize,
+}
+
+/// Turns the gateway of every VLAN of `plan` into a virtual address shared
+/// by the routers of `options`, assigning their real addresses and moving
+/// the first host past them. The usable hosts of each VLAN no longer count
+/// the router addresses.
+///
+/// The VLAN subnets must have been sized for [`ROUTER_ADDRESSES`] on top of
+/// their hosts; a VLAN too small to hold the router addresses is rejected.
+pub fn configure_redundancy(plan: &mut VlanPlan, options: &RedundancyOptions) -> Result<(), DikaiosError> {
+    for (index, allocation) in plan.allocations.iter_mut().enumerate() {
+        let gateway = u32::from(parse_ipv4(&allocation.gateway, "gateway")?);
+        let last_host = u32::from(parse_ipv4(&allocation.last_host, "last_host")?);
+        if gateway as u64 + ROUTER_ADDRESSES as u64 > last_host as u64 {
+            return Err(DikaiosError::AllocationFailed {
+                field: "redundancy",
+                message: format!(
+                    "VLAN {} ({}) has no room for the addresses of both routers next to its gateway",
+                    allocation.vlan_id, allocation.vlan_name
+                ),
+            });
+        }
+        let router_addresses = [1, 2].map(|offset| Ipv4Addr::from(gateway + offset).to_string());
+
+        let active_router = index % 2;
+        let mut priorities = [STANDBY_PRIORITY; 2];
+        priorities[active_router] = ACTIVE_PRIORITY;
+
+        let group = if allocation.vlan_id <= options.protocol.max_group() { allocation.vlan_id } else { 1 };
+
+        allocation.first_host = Ipv4Addr::from(gateway + ROUTER_ADDRESSES + 1).to_string();
+        allocation.usable_hosts -= ROUTER_ADDRESSES;
+        allocation.redundancy = Some(RedundantGateway { group, router_addresses, priorities, active_router });
+    }
+    plan.redundancy = Some(options.clone());
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+//! The subnet mask cheat sheet shown on the reference page.
+
+use super::prefix::Ipv4Prefix;
+use serde::Serialize;
+use std::net::Ipv4Addr;
+
+/// One row of the subnet mask reference table.
+#[derive(Debug, Serialize, Clone)]
+pub struct SubnetMaskReference {
+    pub cidr: String,
+    pub total_hosts: u32,
+    pub usable_hosts: u32,
+    pub subnet_mask: String,
+}
+
+/// Builds the reference table for /32 down to /16.
+pub fn generate_subnet_references() -> Vec<SubnetMaskReference> {
+    let mut references = Vec::new();
+
+    for prefix in (16..=32).rev() {
+        let block = Ipv4Prefix::new(Ipv4Addr::UNSPECIFIED, prefix)
+            .expect("reference prefixes are within 0..=32");
+
+        references.push(SubnetMaskReference {
+            cidr: format!("/{}", prefix),
+            total_hosts: block.size() as u32,
+            usable_hosts: block.host_count(),
+            subnet_mask: block.netmask().to_string(),
+        });
+    }
+
+    references
+}
@@ -0,0 +1,164 @@
+//! Secrets in generated configuration: WPA pre-shared keys and Cisco
+//! password hashes.
+//!
+//! A WPA passphrase is turned into the 256-bit key the access point really
+//! uses with PBKDF2-HMAC-SHA1 over the SSID, so configuration can carry the
+//! key instead of the passphrase. Cisco IOS 15.3(3) and later accept enable
+//! secrets already hashed as type 8 (PBKDF2-HMAC-SHA256) or type 9 (scrypt),
+//! so the plain secret never has to appear in the commands.
+
+use super::error::DikaiosError;
+use pbkdf2::pbkdf2_hmac;
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+use sha2::Sha256;
+use std::fmt;
+use std::str::FromStr;
+
+/// The shortest and longest WPA passphrase in characters.
+pub const WPA_PASSPHRASE_LEN: std::ops::RangeInclusive<usize> = 8..=63;
+
+/// The length of a WPA pre-shared key written out in hex digits.
+pub const WPA_PSK_HEX_LEN: usize = 64;
+
+/// What every masked secret is replaced with.
+pub const MASKED: &str = "********";
+
+/// PBKDF2 iterations of a WPA pre-shared key, fixed by IEEE 802.11i.
+const WPA_PSK_ITERATIONS: u32 = 4096;
+
+/// PBKDF2 iterations of a Cisco type 8 hash.
+const TYPE8_ITERATIONS: u32 = 20_000;
+
+/// The salt length of a Cisco type 8 or 9 hash in characters.
+const CISCO_SALT_LEN: usize = 14;
+
+/// The base64 alphabet of Cisco type 8 and 9 hashes, in place of
+/// `A-Za-z0-9+/`.
+const CISCO_BASE64: &[u8; 64] = b"./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// How an enable secret is hashed in Cisco commands.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CiscoSecretType {
+    /// Type 8, PBKDF2-HMAC-SHA256.
+    Type8,
+    /// Type 9, scrypt; Cisco's recommendation.
+    #[default]
+    Type9,
+}
+
+impl CiscoSecretType {
+    pub const ALL: [CiscoSecretType; 2] = [CiscoSecretType::Type8, CiscoSecretType::Type9];
+
+    /// The identifier used in inputs and on the command line.
+    pub fn id(&self) -> &'static str {
+        match self {
+            CiscoSecretType::Type8 => "type8",
+            CiscoSecretType::Type9 => "type9",
+        }
+    }
+
+    /// The type number IOS expects in front of the hash.
+    pub fn number(&self) -> u8 {
+        match self {
+            CiscoSecretType::Type8 => 8,
+            CiscoSecretType::Type9 => 9,
+        }
+    }
+}
+
+impl fmt::Display for CiscoSecretType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CiscoSecretType::Type8 => "type 8 (PBKDF2-SHA256)",
+            CiscoSecretType::Type9 => "type 9 (scrypt)",
+        })
+    }
+}
+
+impl FromStr for CiscoSecretType {
+    type Err = DikaiosError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let id = s.trim().to_ascii_lowercase().replace([' ', '_', '-'], "");
+        let id = if id.len() == 1 { format!("type{}", id) } else { id };
+        Self::ALL.into_iter()
+            .find(|secret_type| secret_type.id() == id)
+            .ok_or_else(|| DikaiosError::InvalidInput {
+                field: "secret_type",
+                message: format!(
+                    "Unknown secret type \"{}\", expected one of {}",
+                    s.trim(),
+                    Self::ALL.map(|secret_type| secret_type.id()).join(", ")
+                ),
+            })
+    }
+}
+
+/// Whether `key` is a pre-shared key written out as 64 hex digits rather
+/// than a passphrase.
+pub fn is_hex_psk(key: &str) -> bool {
+    key.len() == WPA_PSK_HEX_LEN && key.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Whether `passphrase` follows the WPA rules: 8-63 printable ASCII
+/// characters, or a pre-shared key of 64 hex digits.
+pub fn is_valid_wpa_key(passphrase: &str) -> bool {
+    is_hex_psk(passphrase)
+        || (WPA_PASSPHRASE_LEN.contains(&passphrase.len()) && passphrase.chars().all(|c| c == ' ' || c.is_ascii_graphic()))
+}
+
+/// The 256-bit pre-shared key of `passphrase` on the network `ssid`, as 64
+/// lowercase hex digits. A passphrase that already is a hex key is returned
+/// as is.
+pub fn wpa_psk(passphrase: &str, ssid: &str) -> String {
+    if is_hex_psk(passphrase) {
+        return passphrase.to_ascii_lowercase();
+    }
+
+    let mut key = [0u8; 32];
+    pbkdf2_hmac::<Sha1>(passphrase.as_bytes(), ssid.as_bytes(), WPA_PSK_ITERATIONS, &mut key);
+    key.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// A fresh random salt for [`cisco_secret_hash`].
+pub fn cisco_salt() -> Result<String, DikaiosError> {
+    let mut bytes = [0u8; CISCO_SALT_LEN];
+    getrandom::getrandom(&mut bytes).map_err(|e| DikaiosError::Io { field: "enable_secret", message: e.to_string() })?;
+    // 64 divides 256, so every character is equally likely
+    Ok(bytes.iter().map(|byte| CISCO_BASE64[(byte % 64) as usize] as char).collect())
+}
+
+/// The `$8$salt$hash` or `$9$salt$hash` form of `secret`, as IOS shows it
+/// after `enable algorithm-type sha256|scrypt secret`.
+pub fn cisco_secret_hash(secret: &str, salt: &str, secret_type: CiscoSecretType) -> String {
+    let mut hash = [0u8; 32];
+    match secret_type {
+        CiscoSecretType::Type8 => {
+            pbkdf2_hmac::<Sha256>(secret.as_bytes(), salt.as_bytes(), TYPE8_ITERATIONS, &mut hash);
+        }
+        CiscoSecretType::Type9 => {
+            // N = 2^14, r = 1, p = 1
+            let params = scrypt::Params::new(14, 1, 1, hash.len()).expect("Cisco's scrypt parameters are valid");
+            scrypt::scrypt(secret.as_bytes(), salt.as_bytes(), &params, &mut hash)
+                .expect("the scrypt output length matches the parameters");
+        }
+    }
+
+    format!("${}${}${}", secret_type.number(), salt, cisco_base64(&hash))
+}
+
+/// `bytes` in base64 with the Cisco alphabet and without padding.
+fn cisco_base64(bytes: &[u8]) -> String {
+    let mut encoded = String::new();
+    for chunk in bytes.chunks(3) {
+        let mut group = [0u8; 3];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let bits = u32::from_be_bytes([0, group[0], group[1], group[2]]);
+        for index in 0..=chunk.len() {
+            encoded.push(CISCO_BASE64[(bits >> (18 - 6 * index) & 0x3f) as usize] as char);
+        }
+    }
+    encoded
+}
@@ -0,0 +1,462 @@
+//! Subnet allocation for VLANs. The device configuration for a plan comes
+//! from the renderers in [`super::platform`].
+
+use super::dhcp::{configure_dhcp, DhcpOptions, DhcpScope};
+use super::diagnostic::Diagnostic;
+use super::error::DikaiosError;
+use super::mask::MaskInput;
+use super::platform::Platform;
+use super::prefix::{parse_ipv4, Ipv4Prefix};
+use super::redundancy::{configure_redundancy, RedundancyOptions, RedundantGateway, ROUTER_ADDRESSES};
+use super::vlsm::{host_bits_for, UnusedSpace};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::net::Ipv4Addr;
+use std::ops::RangeInclusive;
+
+/// The highest VLAN ID 802.1Q allows; 4095 is reserved.
+pub const MAX_VLAN_ID: u32 = 4094;
+
+/// VLAN IDs Cisco IOS keeps for FDDI and Token Ring.
+pub const CISCO_RESERVED_VLANS: RangeInclusive<u32> = 1002..=1005;
+
+/// The longest VLAN name Cisco IOS accepts.
+const MAX_VLAN_NAME_LEN: usize = 32;
+
+/// A VLAN plan request. `platform` selects the syntax of the generated
+/// configuration and defaults to Cisco IOS. DHCP is only set up when `dhcp`
+/// is given, and the gateways are only shared by a router pair when
+/// `redundancy` is.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VlanInput {
+    pub base_network: String,
+    pub base_cidr: MaskInput,
+    pub vlan_configs: Vec<VlanConfig>,
+    #[serde(default)]
+    pub trunks: Vec<TrunkPort>,
+    #[serde(default)]
+    pub platform: Platform,
+    #[serde(default)]
+    pub dhcp: Option<DhcpOptions>,
+    #[serde(default)]
+    pub redundancy: Option<RedundancyOptions>,
+}
+
+/// A VLAN that needs a subnet, and the switch ports that are access ports
+/// in it.
+///
+/// Ports are written the way the target platform names them, e.g.
+/// `GigabitEthernet1/0/1-12` for Cisco IOS or `ge-0/0/[1-12]` for Junos.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VlanConfig {
+    pub vlan_id: u32,
+    pub vlan_name: String,
+    pub required_hosts: u32,
+    #[serde(default)]
+    pub access_ports: Vec<String>,
+}
+
+/// A switch port carrying several VLANs tagged, e.g. an uplink.
+///
+/// An empty `allowed_vlans` carries every VLAN of the plan. Frames of the
+/// `native_vlan` cross the trunk untagged.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct TrunkPort {
+    pub interface: String,
+    #[serde(default)]
+    pub allowed_vlans: Vec<u32>,
+    #[serde(default)]
+    pub native_vlan: Option<u32>,
+}
+
+/// The subnet assigned to a VLAN. The first usable address is the gateway.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VlanAllocation {
+    pub vlan_id: u32,
+    pub vlan_name: String,
+    pub network: String,
+    pub cidr: u32,
+    pub subnet_mask: String,
+    pub gateway: String,
+    pub first_host: String,
+    pub last_host: String,
+    pub broadcast: String,
+    pub usable_hosts: u32,
+    #[serde(default)]
+    pub access_ports: Vec<String>,
+    #[serde(default)]
+    pub dhcp: Option<DhcpScope>,
+    #[serde(default)]
+    pub redundancy: Option<RedundantGateway>,
+}
+
+/// The VLAN subnets, the trunks carrying them and the part of the parent
+/// block they leave free.
+///
+/// Every trunk lists the VLANs it actually carries, in ascending order.
+/// `warnings` describes inputs that are valid but probably wrong, such as a
+/// VLAN with access ports that no trunk carries.
+#[derive(Debug, Serialize, Clone)]
+pub struct VlanPlan {
+    pub allocations: Vec<VlanAllocation>,
+    pub trunks: Vec<TrunkPort>,
+    pub unused: UnusedSpace,
+    pub warnings: Vec<Diagnostic>,
+    pub dhcp: Option<DhcpOptions>,
+    pub redundancy: Option<RedundancyOptions>,
+}
+
+impl VlanPlan {
+    /// The device configuration for the plan in the syntax of `platform`.
+    pub fn render(&self, platform: Platform) -> String {
+        platform.renderer().render(self)
+    }
+
+    /// The allocation of the VLAN with the given ID.
+    pub fn allocation(&self, vlan_id: u32) -> Option<&VlanAllocation> {
+        self.allocations.iter().find(|allocation| allocation.vlan_id == vlan_id)
+    }
+
+    /// The trunks that carry `vlan_id` tagged or as their native VLAN.
+    pub fn trunks_carrying(&self, vlan_id: u32) -> impl Iterator<Item = &TrunkPort> {
+        self.trunks.iter()
+            .filter(move |trunk| trunk.allowed_vlans.contains(&vlan_id) || trunk.native_vlan == Some(vlan_id))
+    }
+}
+
+/// Runs [`calculate_vlans`] for a plan request, accepting any mask notation
+/// for `base_cidr`.
+///
+/// The request is checked with [`validate_vlans`] first. Any error fails the
+/// plan with [`DikaiosError::ValidationFailed`] listing everything found;
+/// warnings end up in the plan's `warnings`.
+pub fn calculate_vlan_plan(input: &VlanInput) -> Result<VlanPlan, DikaiosError> {
+    let base_cidr = input.base_cidr.ipv4_prefix_len("base_cidr")?;
+
+    let diagnostics = validate_vlans(input);
+    if let Some(error) = diagnostics.iter().find(|diagnostic| diagnostic.is_error()) {
+        return Err(DikaiosError::ValidationFailed { field: error.field, diagnostics });
+    }
+
+    // The required hosts already count the gateway, which a redundant pair
+    // turns into the virtual one, so only the router addresses come on top.
+    // Even a VLAN without hosts keeps its gateway.
+    let mut vlan_configs = input.vlan_configs.clone();
+    if input.redundancy.is_some() {
+        for config in &mut vlan_configs {
+            config.required_hosts = config.required_hosts.max(1).saturating_add(ROUTER_ADDRESSES);
+        }
+    }
+
+    let mut plan = calculate_vlans(&input.base_network, base_cidr, &vlan_configs, &input.trunks)?;
+    plan.warnings.splice(0..0, diagnostics);
+    if let Some(redundancy) = &input.redundancy {
+        configure_redundancy(&mut plan, redundancy)?;
+    }
+    if let Some(dhcp) = &input.dhcp {
+        configure_dhcp(&mut plan, dhcp)?;
+    }
+    Ok(plan)
+}
+
+/// Checks the VLAN IDs, names and ports of a plan request and reports every
+/// problem found, in input order.
+///
+/// IDs must lie in 1..=[`MAX_VLAN_ID`] and be unique; VLAN 1 only draws a
+/// warning. The IDs in [`CISCO_RESERVED_VLANS`] are an error when the plan
+/// targets Cisco IOS and a warning otherwise. Names must be unique, at most
+/// 32 characters long and free of whitespace, so that every platform's
+/// `name` command takes them as they are.
+pub fn validate_vlans(input: &VlanInput) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut used_ids: HashSet<u32> = input.vlan_configs.iter().map(|config| config.vlan_id).collect();
+    let mut seen_ids: HashMap<u32, &str> = HashMap::new();
+    let mut seen_names: HashMap<String, u32> = HashMap::new();
+    let mut port_vlans: HashMap<&str, u32> = HashMap::new();
+
+    for config in &input.vlan_configs {
+        let id = config.vlan_id;
+        let name = config.vlan_name.trim();
+
+        if id == 0 || id > MAX_VLAN_ID {
+            diagnostics.push(
+                Diagnostic::error("vlan_configs", format!(
+                    "VLAN {} ({}) is outside the VLAN ID range 1-{}", id, name, MAX_VLAN_ID
+                ))
+                .for_vlan(id)
+                .suggest(free_id_suggestion(&mut used_ids)),
+            );
+        } else if id == 1 {
+            diagnostics.push(
+                Diagnostic::warning("vlan_configs", format!(
+                    "VLAN 1 ({}) is the default VLAN of every switch port and cannot be renamed on most switches", name
+                ))
+                .for_vlan(id)
+                .suggest(free_id_suggestion(&mut used_ids)),
+            );
+        } else if CISCO_RESERVED_VLANS.contains(&id) {
+            let message = format!("VLAN {} ({}) is reserved for FDDI and Token Ring on Cisco switches", id, name);
+            let diagnostic = if input.platform == Platform::CiscoIos {
+                Diagnostic::error("vlan_configs", message)
+            } else {
+                Diagnostic::warning("vlan_configs", message)
+            };
+            diagnostics.push(diagnostic.for_vlan(id).suggest(free_id_suggestion(&mut used_ids)));
+        }
+
+        if let Some(&first) = seen_ids.get(&id) {
+            diagnostics.push(
+                Diagnostic::error("vlan_configs", format!("VLAN ID {} is used by both {} and {}", id, first, name))
+                    .for_vlan(id)
+                    .suggest(free_id_suggestion(&mut used_ids)),
+            );
+        } else {
+            seen_ids.insert(id, name);
+        }
+
+        if name.is_empty() {
+            diagnostics.push(
+                Diagnostic::error("vlan_configs", format!("VLAN {} has no name", id))
+                    .for_vlan(id)
+                    .suggest(format!("Name it, e.g. VLAN{:04}", id)),
+            );
+            continue;
+        }
+        if name.contains(char::is_whitespace) {
+            diagnostics.push(
+                Diagnostic::error("vlan_configs", format!(
+                    "VLAN {} name \"{}\" contains spaces, which break the generated name commands", id, name
+                ))
+                .for_vlan(id)
+                .suggest(format!("Rename it to {}", name.split_whitespace().collect::<Vec<_>>().join("-"))),
+            );
+        }
+        if name.chars().count() > MAX_VLAN_NAME_LEN {
+            diagnostics.push(
+                Diagnostic::error("vlan_configs", format!(
+                    "VLAN {} name \"{}\" is longer than {} characters", id, name, MAX_VLAN_NAME_LEN
+                ))
+                .for_vlan(id)
+                .suggest(format!("Shorten it to {} characters or fewer", MAX_VLAN_NAME_LEN)),
+            );
+        }
+        match seen_names.get(&name.to_lowercase()) {
+            // Reusing both the ID and the name is already reported above
+            Some(&other) if other == id => {}
+            Some(&other) => diagnostics.push(
+                Diagnostic::error("vlan_configs", format!("VLAN {} has the same name as VLAN {}: {}", id, other, name))
+                    .for_vlan(id)
+                    .suggest(format!("Rename it, e.g. to {}-{}", name, id)),
+            ),
+            None => {
+                seen_names.insert(name.to_lowercase(), id);
+            }
+        }
+
+        for ports in &config.access_ports {
+            let ports = ports.trim();
+            match port_vlans.get(ports) {
+                Some(&other) if other != id => diagnostics.push(
+                    Diagnostic::error("vlan_configs", format!(
+                        "{} is an access port of both VLAN {} and VLAN {}", ports, other, id
+                    ))
+                    .for_vlan(id)
+                    .suggest(format!("Remove {} from one of the VLANs", ports)),
+                ),
+                _ => {
+                    port_vlans.insert(ports, id);
+                }
+            }
+        }
+    }
+
+    if let Some(redundancy) = &input.redundancy {
+        diagnostics.extend(redundancy.validate(input.platform));
+    }
+
+    let mut seen_trunks = HashSet::new();
+    for trunk in &input.trunks {
+        let interface = trunk.interface.trim();
+        if interface.is_empty() {
+            diagnostics.push(
+                Diagnostic::error("trunks", "A trunk has no interface name")
+                    .suggest("Enter the interface of the uplink, e.g. GigabitEthernet1/0/48"),
+            );
+            continue;
+        }
+        if !seen_trunks.insert(interface) {
+            diagnostics.push(
+                Diagnostic::error("trunks", format!("Trunk {} is listed twice", interface))
+                    .suggest("Merge the allowed VLANs into a single trunk"),
+            );
+        }
+        if let Some(&vlan_id) = port_vlans.get(interface) {
+            diagnostics.push(
+                Diagnostic::error("trunks", format!("{} is both a trunk and an access port of VLAN {}", interface, vlan_id))
+                    .for_vlan(vlan_id)
+                    .suggest(format!("Remove {} from the access ports of VLAN {}", interface, vlan_id)),
+            );
+        }
+        for &vlan_id in trunk.allowed_vlans.iter().chain(&trunk.native_vlan) {
+            if vlan_id == 0 || vlan_id > MAX_VLAN_ID {
+                diagnostics.push(
+                    Diagnostic::error("trunks", format!(
+                        "Trunk {} refers to VLAN {}, outside the VLAN ID range 1-{}", interface, vlan_id, MAX_VLAN_ID
+                    ))
+                    .for_vlan(vlan_id)
+                    .suggest(format!("Remove VLAN {} from the trunk", vlan_id)),
+                );
+            }
+        }
+    }
+
+    diagnostics
+}
+
+/// Suggests the lowest usable VLAN ID that is still free and claims it, so
+/// that every suggestion in one validation pass is different.
+fn free_id_suggestion(used_ids: &mut HashSet<u32>) -> String {
+    match (2..=MAX_VLAN_ID).find(|id| !used_ids.contains(id) && !CISCO_RESERVED_VLANS.contains(id)) {
+        Some(id) => {
+            used_ids.insert(id);
+            format!("Use a free ID such as {}", id)
+        }
+        None => "Remove a VLAN; every usable ID is taken".to_string(),
+    }
+}
+
+/// Allocates a subnet for every VLAN inside `base_network/base_cidr`, largest
+/// first, and resolves the VLANs each of the `trunks` carries.
+///
+/// Fails with [`DikaiosError::InsufficientSpace`] when the VLAN subnets
+/// together do not fit in the parent block.
+///
+/// Unlike the VLSM allocator, VLAN subnets are never planned as /31 or /32
+/// point-to-point prefixes: every VLAN is a broadcast segment with a
+/// gateway, a broadcast address and room for hosts, DHCP pools and
+/// redundant routers, none of which fit in a /31 or /32. The smallest VLAN
+/// subnet is therefore a /30.
+pub fn calculate_vlans(
+    base_network: &str,
+    base_cidr: u32,
+    vlan_configs: &[VlanConfig],
+    trunks: &[TrunkPort],
+) -> Result<VlanPlan, DikaiosError> {
+    let parent = Ipv4Prefix::new(parse_ipv4(base_network, "base_network")?, base_cidr)
+        .map_err(|e| e.with_field("base_cidr"))?
+        .trunc();
+
+    // Sort VLAN configs by required hosts (descending order)
+    let mut sorted_configs = vlan_configs.to_vec();
+    sorted_configs.sort_by_key(|config| Reverse(config.required_hosts));
+
+    let mut sized = Vec::with_capacity(sorted_configs.len());
+    for config in sorted_configs {
+        // Never point-to-point, see above
+        let host_bits = host_bits_for(config.required_hosts as u64, false);
+
+        if host_bits > 32 || 32 - host_bits < base_cidr {
+            return Err(DikaiosError::AllocationFailed {
+                field: "vlan_configs",
+                message: format!("Cannot allocate subnet for VLAN {} with {} hosts within /{} network",
+                    config.vlan_id, config.required_hosts, base_cidr),
+            });
+        }
+
+        sized.push((config, 32 - host_bits));
+    }
+
+    let required: u128 = sized.iter().map(|&(_, cidr)| 1u128 << (32 - cidr)).sum();
+    if required > parent.size() as u128 {
+        return Err(DikaiosError::InsufficientSpace {
+            field: "vlan_configs",
+            required,
+            available: parent.size() as u128,
+        });
+    }
+
+    let mut allocations = Vec::new();
+    let mut current_ip = u32::from(parent.network()) as u64;
+
+    for (config, subnet_cidr) in sized {
+        let prefix = Ipv4Prefix::new(Ipv4Addr::from(current_ip as u32), subnet_cidr)?;
+        let gateway = prefix.first_host(); // First usable IP as gateway
+        let first_host = u32::from(gateway) + 1; // Second usable IP
+
+        allocations.push(VlanAllocation {
+            vlan_id: config.vlan_id,
+            vlan_name: config.vlan_name.clone(),
+            network: prefix.network().to_string(),
+            cidr: subnet_cidr,
+            subnet_mask: prefix.netmask().to_string(),
+            gateway: gateway.to_string(),
+            first_host: Ipv4Addr::from(first_host).to_string(),
+            last_host: prefix.last_host().to_string(),
+            broadcast: prefix.broadcast().to_string(),
+            usable_hosts: prefix.host_count(),
+            access_ports: config.access_ports.clone(),
+            dhcp: None,
+            redundancy: None,
+        });
+
+        current_ip += prefix.size();
+    }
+
+    let mut plan = VlanPlan {
+        allocations,
+        trunks: Vec::with_capacity(trunks.len()),
+        unused: UnusedSpace::ipv4(current_ip, &parent),
+        warnings: Vec::new(),
+        dhcp: None,
+        redundancy: None,
+    };
+    resolve_trunks(&mut plan, trunks);
+
+    Ok(plan)
+}
+
+/// Fills in the VLANs every trunk carries and warns about VLANs that do not
+/// reach a trunk or that a trunk expects but the plan lacks.
+fn resolve_trunks(plan: &mut VlanPlan, trunks: &[TrunkPort]) {
+    let mut all_vlans: Vec<u32> = plan.allocations.iter().map(|allocation| allocation.vlan_id).collect();
+    all_vlans.sort_unstable();
+
+    for trunk in trunks {
+        let mut allowed_vlans = if trunk.allowed_vlans.is_empty() {
+            all_vlans.clone()
+        } else {
+            trunk.allowed_vlans.clone()
+        };
+        allowed_vlans.sort_unstable();
+        allowed_vlans.dedup();
+
+        let mut carried = allowed_vlans.clone();
+        carried.extend(trunk.native_vlan.filter(|native| !allowed_vlans.contains(native)));
+        for vlan_id in carried {
+            if plan.allocation(vlan_id).is_none() {
+                plan.warnings.push(
+                    Diagnostic::warning("trunks", format!(
+                        "Trunk {} carries VLAN {}, which is not part of the plan", trunk.interface, vlan_id
+                    ))
+                    .for_vlan(vlan_id)
+                    .suggest(format!("Add VLAN {} to the plan or remove it from the trunk", vlan_id)),
+                );
+            }
+        }
+
+        plan.trunks.push(TrunkPort { interface: trunk.interface.clone(), allowed_vlans, native_vlan: trunk.native_vlan });
+    }
+
+    for allocation in &plan.allocations {
+        if !allocation.access_ports.is_empty() && plan.trunks_carrying(allocation.vlan_id).next().is_none() {
+            plan.warnings.push(
+                Diagnostic::warning("vlan_configs", format!(
+                    "VLAN {} ({}) has access ports but no trunk carries it", allocation.vlan_id, allocation.vlan_name
+                ))
+                .for_vlan(allocation.vlan_id)
+                .suggest(format!("Allow VLAN {} on an uplink trunk", allocation.vlan_id)),
+            );
+        }
+    }
+}
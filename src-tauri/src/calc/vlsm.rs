@@ -0,0 +1,533 @@
+//! Variable Length Subnet Mask allocation for IPv4 and IPv6.
+
+use super::error::DikaiosError;
+use super::mask::MaskInput;
+use super::prefix::{parse_ipv4, parse_ipv6, AddressFamily, Ipv4Prefix, Ipv6Prefix, Prefix};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+/// A VLSM plan request. IPv6 parents use `ipv6_requirements`, IPv4 parents
+/// use `host_requirements`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VlsmInput {
+    pub base_network: String,
+    pub base_cidr: MaskInput,
+    #[serde(default)]
+    pub host_requirements: Vec<HostRequirement>,
+    #[serde(default)]
+    pub ipv6_requirements: Vec<Ipv6Requirement>,
+    #[serde(flatten)]
+    pub options: VlsmOptions,
+}
+
+/// How IPv4 subnets are sized beyond the bare host count. Requirements may
+/// override each setting for themselves.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VlsmOptions {
+    /// Room to grow added to every host count.
+    #[serde(default)]
+    pub growth: Growth,
+    /// Addresses kept free at the start of every subnet for gateways, HSRP
+    /// virtual IPs and other infrastructure.
+    #[serde(default)]
+    pub reserved: u32,
+    /// Plan two-host links as /31s (RFC 3021) and single hosts such as
+    /// loopbacks as /32s instead of rounding them up to a /30.
+    #[serde(default)]
+    pub point_to_point: bool,
+}
+
+/// Room to grow on top of a host count, as a percentage.
+///
+/// Written as a number of percent, `"30%"`, `"+30%"`, `"double"` or
+/// `"triple"`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(try_from = "GrowthInput", into = "u32")]
+pub struct Growth {
+    pub percent: u32,
+}
+
+impl Growth {
+    pub const NONE: Self = Self { percent: 0 };
+    pub const DOUBLE: Self = Self { percent: 100 };
+
+    pub fn percent(percent: u32) -> Self {
+        Self { percent }
+    }
+
+    /// `hosts` plus the growth, rounded up to whole hosts. Saturates rather
+    /// than overflowing, which no subnet can hold anyway.
+    pub fn apply(&self, hosts: u32) -> u64 {
+        (hosts as u64).saturating_mul(100 + self.percent as u64).div_ceil(100)
+    }
+}
+
+impl From<Growth> for u32 {
+    fn from(growth: Growth) -> Self {
+        growth.percent
+    }
+}
+
+impl FromStr for Growth {
+    type Err = DikaiosError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "" | "none" => Ok(Self::NONE),
+            "double" => Ok(Self::DOUBLE),
+            "triple" => Ok(Self::percent(200)),
+            text => {
+                let text = text.strip_prefix('+').unwrap_or(text);
+                let text = text.strip_suffix('%').unwrap_or(text);
+                text.trim().parse()
+                    .map(Self::percent)
+                    .map_err(|_| DikaiosError::InvalidInput {
+                        field: "growth",
+                        message: format!("Growth must be a percentage such as +30% or \"double\", not \"{}\"", s.trim()),
+                    })
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum GrowthInput {
+    Percent(u32),
+    Text(String),
+}
+
+impl TryFrom<GrowthInput> for Growth {
+    type Error = DikaiosError;
+
+    fn try_from(input: GrowthInput) -> Result<Self, Self::Error> {
+        match input {
+            GrowthInput::Percent(percent) => Ok(Self::percent(percent)),
+            GrowthInput::Text(text) => text.parse(),
+        }
+    }
+}
+
+/// An IPv4 subnet request: what it is for and how many hosts it needs.
+///
+/// A bare number is accepted in place of the object for an unnamed
+/// requirement, and [`FromStr`] parses the `"Sales LAN: 50"` shorthand,
+/// optionally with its own growth as in `"Sales LAN: 50+30%"`. `growth` and
+/// `reserved` override the plan-wide [`VlsmOptions`] when set.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(from = "HostRequirementInput")]
+pub struct HostRequirement {
+    pub name: String,
+    pub hosts: u32,
+    pub growth: Option<Growth>,
+    pub reserved: Option<u32>,
+}
+
+impl HostRequirement {
+    pub fn new(name: impl Into<String>, hosts: u32) -> Self {
+        Self { name: name.into(), hosts, growth: None, reserved: None }
+    }
+
+    pub fn with_growth(mut self, growth: Growth) -> Self {
+        self.growth = Some(growth);
+        self
+    }
+
+    pub fn with_reserved(mut self, reserved: u32) -> Self {
+        self.reserved = Some(reserved);
+        self
+    }
+
+    /// The number of addresses to plan for besides the network and broadcast
+    /// addresses: the hosts after growth plus the reserved addresses.
+    pub fn planned_hosts(&self, options: &VlsmOptions) -> u64 {
+        let growth = self.growth.unwrap_or(options.growth);
+        growth.apply(self.hosts).saturating_add(self.reserved.unwrap_or(options.reserved) as u64)
+    }
+}
+
+impl From<u32> for HostRequirement {
+    fn from(hosts: u32) -> Self {
+        Self::new("", hosts)
+    }
+}
+
+impl FromStr for HostRequirement {
+    type Err = DikaiosError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, size) = s.rsplit_once(':').unwrap_or(("", s));
+        let (hosts, growth) = match size.split_once('+') {
+            Some((hosts, growth)) => (hosts, Some(growth.parse()?)),
+            None => (size, None),
+        };
+        let hosts = hosts.trim().parse()
+            .map_err(|_| DikaiosError::InvalidNumber { field: "host_requirements" })?;
+        Ok(Self { growth, ..Self::new(name.trim(), hosts) })
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum HostRequirementInput {
+    Hosts(u32),
+    Named {
+        #[serde(default)]
+        name: String,
+        hosts: u32,
+        #[serde(default)]
+        growth: Option<Growth>,
+        #[serde(default)]
+        reserved: Option<u32>,
+    },
+}
+
+impl From<HostRequirementInput> for HostRequirement {
+    fn from(input: HostRequirementInput) -> Self {
+        match input {
+            HostRequirementInput::Hosts(hosts) => Self::from(hosts),
+            HostRequirementInput::Named { name, hosts, growth, reserved } => Self { growth, reserved, ..Self::new(name, hosts) },
+        }
+    }
+}
+
+/// An IPv6 subnet request: what it is for and how large it must be, e.g.
+/// `{"name": "Campus", "prefix_length": 56}`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Ipv6Requirement {
+    #[serde(default)]
+    pub name: String,
+    #[serde(flatten)]
+    pub size: Ipv6Size,
+}
+
+impl Ipv6Requirement {
+    pub fn new(name: impl Into<String>, size: Ipv6Size) -> Self {
+        Self { name: name.into(), size }
+    }
+}
+
+/// How much space an IPv6 subnet needs.
+///
+/// Host counts make little sense for IPv6, so subnets are requested either
+/// by prefix length or by the number of /64 LANs they need to hold.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Ipv6Size {
+    PrefixLength(u32),
+    #[serde(rename = "subnets_64")]
+    Subnets64(u64),
+}
+
+/// A subnet carved out of the parent block by the VLSM allocator.
+///
+/// Subnets are returned in allocation order; `index` is the position of the
+/// requirement in the input, so callers can restore the input order. For
+/// IPv4, `required_hosts` is the requested host count and `planned_hosts`
+/// the capacity the subnet was sized for after growth and reservations.
+/// The `reserved_hosts` addresses sit at the start of the subnet, so
+/// `first_host` and `usable_hosts` leave them out.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VlsmSubnet {
+    pub name: String,
+    pub index: usize,
+    pub family: AddressFamily,
+    pub network: String,
+    pub cidr: u32,
+    pub subnet_mask: String,
+    pub first_host: String,
+    pub last_host: String,
+    pub broadcast: Option<String>,
+    pub required_hosts: Option<u32>,
+    pub planned_hosts: Option<u64>,
+    pub reserved_hosts: Option<u32>,
+    pub usable_hosts: Option<u32>,
+    pub address_count: String,
+}
+
+/// The subnets of a VLSM plan together with the space they leave free.
+#[derive(Debug, Serialize, Clone)]
+pub struct VlsmPlan {
+    pub subnets: Vec<VlsmSubnet>,
+    pub unused: UnusedSpace,
+}
+
+/// The part of the parent block left over after the last allocated subnet.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct UnusedSpace {
+    /// The free addresses as the fewest CIDR blocks, lowest first.
+    pub blocks: Vec<String>,
+    /// Number of free addresses as a decimal string.
+    pub address_count: String,
+}
+
+impl UnusedSpace {
+    /// The space from `next` to the end of `parent`. `next` is one past the
+    /// last allocated address and may be 2^32 when the block is full.
+    pub(crate) fn ipv4(next: u64, parent: &Ipv4Prefix) -> Self {
+        let end = u32::from(parent.broadcast()) as u64;
+        if next > end {
+            return Self { blocks: Vec::new(), address_count: "0".to_string() };
+        }
+
+        Self {
+            blocks: Ipv4Prefix::summarize(Ipv4Addr::from(next as u32), parent.broadcast())
+                .iter()
+                .map(Ipv4Prefix::to_string)
+                .collect(),
+            address_count: (end - next + 1).to_string(),
+        }
+    }
+
+    /// The space from `next` to the end of `parent`, where `None` means the
+    /// allocation reached the very end of the address space.
+    pub(crate) fn ipv6(next: Option<u128>, parent: &Ipv6Prefix) -> Self {
+        let end = u128::from(parent.last());
+        let Some(next) = next.filter(|&next| next <= end) else {
+            return Self { blocks: Vec::new(), address_count: "0".to_string() };
+        };
+
+        Self {
+            blocks: Ipv6Prefix::summarize(Ipv6Addr::from(next), parent.last())
+                .iter()
+                .map(Ipv6Prefix::to_string)
+                .collect(),
+            // Only an untouched /0 overflows, and then the whole parent is free
+            address_count: (end - next).checked_add(1)
+                .map(|count| count.to_string())
+                .unwrap_or_else(|| Prefix::V6(*parent).address_count()),
+        }
+    }
+}
+
+/// Number of host bits needed for `required_hosts` plus the network and
+/// broadcast addresses, never smaller than a /30.
+///
+/// With `point_to_point`, one host gets a /32 and two hosts a /31, which
+/// need neither a network nor a broadcast address. Counts too large for any
+/// power of two come out as 64 bits, more than any IPv4 subnet has.
+pub(crate) fn host_bits_for(required_hosts: u64, point_to_point: bool) -> u32 {
+    if point_to_point && required_hosts <= 2 {
+        return required_hosts.max(1).next_power_of_two().trailing_zeros();
+    }
+
+    let total_needed = required_hosts.saturating_add(2);
+
+    // Find the smallest power of 2 that can accommodate the hosts
+    total_needed.max(4).checked_next_power_of_two().map_or(u64::BITS, |size| size.trailing_zeros())
+}
+
+/// Runs the IPv4 or IPv6 allocator depending on the family of the parent.
+pub fn calculate_vlsm_plan(input: &VlsmInput) -> Result<VlsmPlan, DikaiosError> {
+    if input.base_network.contains(':') {
+        let base_cidr = input.base_cidr.ipv6_prefix_len("base_cidr")?;
+        calculate_ipv6_vlsm_subnets(&input.base_network, base_cidr, &input.ipv6_requirements)
+    } else {
+        let base_cidr = input.base_cidr.ipv4_prefix_len("base_cidr")?;
+        calculate_vlsm_subnets(&input.base_network, base_cidr, &input.host_requirements, &input.options)
+    }
+}
+
+/// Allocates one IPv4 subnet per entry of `host_requirements` inside
+/// `base_network/base_cidr`, largest planned size first. Requirements of the
+/// same size keep their input order.
+///
+/// Each subnet is sized for its hosts after growth plus its reserved
+/// addresses, as set by `options` unless the requirement overrides them.
+/// Fails with [`DikaiosError::InsufficientSpace`] when the subnets together
+/// do not fit in the parent block.
+pub fn calculate_vlsm_subnets(
+    base_network: &str,
+    base_cidr: u32,
+    host_requirements: &[HostRequirement],
+    options: &VlsmOptions,
+) -> Result<VlsmPlan, DikaiosError> {
+    let parent = Ipv4Prefix::new(parse_ipv4(base_network, "base_network")?, base_cidr)
+        .map_err(|e| e.with_field("base_cidr"))?
+        .trunc();
+
+    // Sort requirements in descending order (largest to smallest)
+    let mut sorted_requirements: Vec<(usize, &HostRequirement, u64)> = host_requirements.iter()
+        .enumerate()
+        .map(|(index, requirement)| (index, requirement, requirement.planned_hosts(options)))
+        .collect();
+    sorted_requirements.sort_by_key(|&(_, _, planned_hosts)| Reverse(planned_hosts));
+
+    let mut sized = Vec::with_capacity(sorted_requirements.len());
+    for (index, requirement, planned_hosts) in sorted_requirements {
+        let host_bits = host_bits_for(planned_hosts, options.point_to_point);
+
+        if host_bits > 32 || 32 - host_bits < base_cidr {
+            return Err(DikaiosError::AllocationFailed {
+                field: "host_requirements",
+                message: if planned_hosts == requirement.hosts as u64 {
+                    format!("Cannot allocate subnet for {} hosts within /{} network", requirement.hosts, base_cidr)
+                } else {
+                    format!("Cannot allocate subnet for {} hosts ({} planned) within /{} network",
+                        requirement.hosts, planned_hosts, base_cidr)
+                },
+            });
+        }
+
+        sized.push((index, requirement, planned_hosts, 32 - host_bits));
+    }
+
+    // Blocks placed largest first never leave gaps, so the plan fits exactly
+    // when the sizes add up to no more than the parent
+    let required: u128 = sized.iter().map(|&(_, _, _, cidr)| 1u128 << (32 - cidr)).sum();
+    if required > parent.size() as u128 {
+        return Err(DikaiosError::InsufficientSpace {
+            field: "host_requirements",
+            required,
+            available: parent.size() as u128,
+        });
+    }
+
+    let mut subnets = Vec::new();
+    let mut current_ip = u32::from(parent.network()) as u64;
+
+    for (index, requirement, planned_hosts, subnet_cidr) in sized {
+        let prefix = Ipv4Prefix::new(Ipv4Addr::from(current_ip as u32), subnet_cidr)?;
+        // The reserved addresses come first, so hosts start after them
+        let reserved = requirement.reserved.unwrap_or(options.reserved);
+        let first_host = u32::from(prefix.first_host())
+            .checked_add(reserved)
+            .filter(|&first_host| first_host <= u32::from(prefix.last_host()))
+            .map(Ipv4Addr::from)
+            .ok_or_else(|| DikaiosError::AllocationFailed {
+                field: "host_requirements",
+                message: format!(
+                    "{} reserves every usable address of its /{} subnet, leaving none for hosts",
+                    if requirement.name.is_empty() { format!("Subnet {}", index + 1) } else { requirement.name.clone() },
+                    subnet_cidr
+                ),
+            })?;
+
+        subnets.push(VlsmSubnet {
+            name: requirement.name.clone(),
+            index,
+            family: AddressFamily::Ipv4,
+            network: prefix.network().to_string(),
+            cidr: subnet_cidr,
+            subnet_mask: prefix.netmask().to_string(),
+            first_host: first_host.to_string(),
+            last_host: prefix.last_host().to_string(),
+            broadcast: prefix.broadcast_addr().map(|broadcast| broadcast.to_string()),
+            required_hosts: Some(requirement.hosts),
+            planned_hosts: Some(planned_hosts),
+            reserved_hosts: Some(reserved),
+            usable_hosts: Some(prefix.host_count() - reserved),
+            address_count: prefix.size().to_string(),
+        });
+
+        // Move to next available network address
+        current_ip += prefix.size();
+    }
+
+    Ok(VlsmPlan { subnets, unused: UnusedSpace::ipv4(current_ip, &parent) })
+}
+
+/// Allocates one IPv6 subnet per requirement inside `base_network/base_cidr`,
+/// shortest prefix first.
+pub fn calculate_ipv6_vlsm_subnets(base_network: &str, base_cidr: u32, requirements: &[Ipv6Requirement]) -> Result<VlsmPlan, DikaiosError> {
+    let parent = Ipv6Prefix::new(parse_ipv6(base_network, "base_network")?, base_cidr)
+        .map_err(|e| e.with_field("base_cidr"))?
+        .trunc();
+    let parent_last = u128::from(parent.last());
+
+    // Resolve every requirement to a prefix length up front
+    let mut prefixes = Vec::with_capacity(requirements.len());
+    for (index, requirement) in requirements.iter().enumerate() {
+        let prefix = match requirement.size {
+            Ipv6Size::PrefixLength(prefix) => {
+                if prefix > Ipv6Prefix::MAX_LEN {
+                    return Err(DikaiosError::OutOfRange {
+                        field: "ipv6_requirements",
+                        min: base_cidr as u64,
+                        max: Ipv6Prefix::MAX_LEN as u64,
+                    });
+                }
+                prefix
+            }
+            Ipv6Size::Subnets64(count) => {
+                if count == 0 {
+                    return Err(DikaiosError::InvalidInput {
+                        field: "ipv6_requirements",
+                        message: "Number of /64 subnets must be at least 1".to_string(),
+                    });
+                }
+                let bits = count.checked_next_power_of_two()
+                    .map(|size| size.trailing_zeros())
+                    .ok_or(DikaiosError::AllocationFailed {
+                        field: "ipv6_requirements",
+                        message: format!("Cannot fit {} /64 subnets in any IPv6 prefix", count),
+                    })?;
+                64 - bits
+            }
+        };
+
+        if prefix < base_cidr {
+            return Err(DikaiosError::AllocationFailed {
+                field: "ipv6_requirements",
+                message: format!("Cannot allocate a /{} subnet within /{} network", prefix, base_cidr),
+            });
+        }
+
+        prefixes.push((index, requirement, prefix));
+    }
+
+    // Shortest prefix (largest block) first, same as the IPv4 allocator
+    prefixes.sort_by_key(|&(_, _, prefix)| prefix);
+
+    // Only sizes that fit in a u128 can be reported; anything larger is
+    // caught while placing the subnets below
+    let required = prefixes.iter().try_fold(0u128, |total, &(_, _, prefix_len)| {
+        Ipv6Prefix::new(Ipv6Addr::UNSPECIFIED, prefix_len).ok()?.size()?.checked_add(total)
+    });
+    if let (Some(required), Some(available)) = (required, parent.size()) {
+        if required > available {
+            return Err(DikaiosError::InsufficientSpace { field: "ipv6_requirements", required, available });
+        }
+    }
+
+    let mut subnets = Vec::new();
+    let mut current = Some(u128::from(parent.network()));
+
+    for (index, requirement, prefix_len) in prefixes {
+        let hostmask = Ipv6Prefix::new(Ipv6Addr::UNSPECIFIED, prefix_len)?.hostmask_bits();
+
+        // Round up to the next boundary of this block size
+        let network = current
+            .and_then(|ip| ip.checked_add(hostmask))
+            .map(|ip| ip & !hostmask)
+            .filter(|network| (network | hostmask) <= parent_last)
+            .ok_or(DikaiosError::AllocationFailed {
+                field: "ipv6_requirements",
+                message: format!("Not enough space left in /{} network for a /{} subnet", base_cidr, prefix_len),
+            })?;
+        let prefix = Ipv6Prefix::new(Ipv6Addr::from(network), prefix_len)?;
+
+        subnets.push(VlsmSubnet {
+            name: requirement.name.clone(),
+            index,
+            family: AddressFamily::Ipv6,
+            network: prefix.network().to_string(),
+            cidr: prefix_len,
+            subnet_mask: prefix.netmask().to_string(),
+            first_host: prefix.network().to_string(),
+            last_host: prefix.last().to_string(),
+            broadcast: None,
+            required_hosts: None,
+            planned_hosts: None,
+            reserved_hosts: None,
+            usable_hosts: None,
+            address_count: Prefix::V6(prefix).address_count(),
+        });
+
+        current = u128::from(prefix.last()).checked_add(1);
+    }
+
+    Ok(VlsmPlan { subnets, unused: UnusedSpace::ipv6(current, &parent) })
+}
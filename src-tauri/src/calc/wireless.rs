@@ -0,0 +1,621 @@
+//! Wireless router and access point setup for Cisco Packet Tracer and
+//! Linux access points.
+//!
+//! Besides the Packet Tracer commands, every configuration carries a
+//! `hostapd.conf` for a Linux access point together with a `dnsmasq.conf`
+//! and a Kea DHCPv4 snippet that serve the same DHCP pool as the router.
+//! All outputs are written from the same validated input.
+//!
+//! With `mask_passphrase` the passphrase is replaced by `********` wherever
+//! it would appear and the derived WPA pre-shared key is written instead.
+//! Enable secrets only ever appear as Cisco type 8 or 9 hashes.
+
+use super::dhcp::DEFAULT_LEASE_TIME;
+use super::diagnostic::Diagnostic;
+use super::error::DikaiosError;
+use super::mask::MaskInput;
+use super::prefix::{parse_ipv4, Ipv4Prefix};
+use super::secret::{
+    cisco_salt, cisco_secret_hash, is_hex_psk, is_valid_wpa_key, wpa_psk, CiscoSecretType, MASKED, WPA_PASSPHRASE_LEN,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fmt;
+use std::net::Ipv4Addr;
+use std::str::FromStr;
+
+/// The longest prefix that still holds the router, the access point and a
+/// DHCP pool.
+const MAX_WIRELESS_PREFIX_LEN: u32 = 28;
+
+/// The longest SSID in bytes.
+const MAX_SSID_LEN: usize = 32;
+
+/// The DNS servers handed out with every lease.
+pub(crate) const DNS_SERVERS: [&str; 2] = ["8.8.8.8", "8.8.4.4"];
+
+/// Countries that stop the 2.4 GHz band at channel 11.
+const NORTH_AMERICA: [&str; 2] = ["US", "CA"];
+
+/// The 5 GHz channels that need radar detection (DFS).
+const DFS_CHANNELS: std::ops::RangeInclusive<u32> = 52..=144;
+
+/// How the wireless network authenticates clients.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WirelessSecurity {
+    /// WPA2 with a pre-shared key.
+    #[default]
+    Wpa2,
+    /// WPA3 with SAE; clients must support WPA3.
+    Wpa3,
+    /// WPA3 for clients that support it and WPA2 for the rest.
+    Wpa2Wpa3,
+}
+
+impl WirelessSecurity {
+    pub const ALL: [WirelessSecurity; 3] = [WirelessSecurity::Wpa2, WirelessSecurity::Wpa3, WirelessSecurity::Wpa2Wpa3];
+
+    /// The identifier used in inputs and on the command line.
+    pub fn id(&self) -> &'static str {
+        match self {
+            WirelessSecurity::Wpa2 => "wpa2",
+            WirelessSecurity::Wpa3 => "wpa3",
+            WirelessSecurity::Wpa2Wpa3 => "wpa2_wpa3",
+        }
+    }
+}
+
+impl fmt::Display for WirelessSecurity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            WirelessSecurity::Wpa2 => "WPA2-PSK",
+            WirelessSecurity::Wpa3 => "WPA3-SAE",
+            WirelessSecurity::Wpa2Wpa3 => "WPA2-PSK/WPA3-SAE",
+        })
+    }
+}
+
+impl FromStr for WirelessSecurity {
+    type Err = DikaiosError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let id = s.trim().to_ascii_lowercase().replace(['-', '/'], "_");
+        Self::ALL.into_iter()
+            .find(|security| security.id() == id)
+            .ok_or_else(|| DikaiosError::InvalidInput {
+                field: "security",
+                message: format!(
+                    "Unknown wireless security \"{}\", expected one of {}",
+                    s.trim(),
+                    Self::ALL.map(|security| security.id()).join(", ")
+                ),
+            })
+    }
+}
+
+/// The hostapd `hw_mode` of a channel: `g` for 2.4 GHz and `a` for 5 GHz,
+/// or `None` when the channel is neither.
+fn hw_mode(channel: u32) -> Option<&'static str> {
+    match channel {
+        1..=14 => Some("g"),
+        36..=64 | 100..=144 if channel.is_multiple_of(4) => Some("a"),
+        149..=165 if channel % 4 == 1 => Some("a"),
+        _ => None,
+    }
+}
+
+/// What the wireless setup page asks for.
+///
+/// When `auto_generate` is set, `base_network` and `base_cidr` are ignored
+/// and 192.168.1.0/24 is used instead. `country_code`, `channel` and
+/// `interface` only end up in the Linux access point files. `wpa_password`
+/// is a passphrase or a pre-shared key of 64 hex digits. `enable_secret`,
+/// when given, is set on both devices hashed as `secret_type`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct WirelessNetworkInput {
+    pub base_network: Option<String>,
+    pub base_cidr: Option<MaskInput>,
+    pub auto_generate: bool,
+    pub ssid: String,
+    pub wpa_password: String,
+    pub router_hostname: String,
+    pub ap_hostname: String,
+    #[serde(default)]
+    pub security: WirelessSecurity,
+    #[serde(default = "default_country_code")]
+    pub country_code: String,
+    #[serde(default = "default_channel")]
+    pub channel: u32,
+    #[serde(default = "default_interface")]
+    pub interface: String,
+    #[serde(default)]
+    pub mask_passphrase: bool,
+    #[serde(default)]
+    pub enable_secret: Option<String>,
+    #[serde(default)]
+    pub secret_type: CiscoSecretType,
+}
+
+fn default_country_code() -> String {
+    "US".to_string()
+}
+
+fn default_channel() -> u32 {
+    6
+}
+
+fn default_interface() -> String {
+    "wlan0".to_string()
+}
+
+/// The addressing plan and device configuration for a wireless network.
+///
+/// `warnings` are not read back when a saved configuration is loaded.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WirelessNetworkConfig {
+    pub network: String,
+    pub cidr: u32,
+    pub subnet_mask: String,
+    pub router_ip: String,
+    pub ap_ip: String,
+    pub dhcp_start: String,
+    pub dhcp_end: String,
+    pub broadcast: String,
+    pub usable_hosts: u32,
+    pub ssid: String,
+    pub security: WirelessSecurity,
+    /// The 256-bit WPA pre-shared key derived from the passphrase, in hex.
+    pub psk: String,
+    /// Whether the configuration was generated with `mask_passphrase`.
+    #[serde(default)]
+    pub masked: bool,
+    pub router_commands: String,
+    pub ap_commands: String,
+    pub setup_steps: Vec<String>,
+    pub hostapd_conf: String,
+    pub dnsmasq_conf: String,
+    pub kea_config: String,
+    #[serde(default, skip_deserializing)]
+    pub warnings: Vec<Diagnostic>,
+}
+
+impl WirelessNetworkConfig {
+    /// Whether the configuration was generated with `mask_passphrase`, so
+    /// that no output holds the passphrase in plain text.
+    pub fn is_masked(&self) -> bool {
+        self.masked
+    }
+}
+
+/// Checks the input shared by every output, collecting all problems.
+///
+/// The SSID must be 1-32 bytes without control characters and the
+/// passphrase 8-63 printable ASCII characters or 64 hex digits; the
+/// hostnames and the interface must be single words, the country code two
+/// letters and the channel a 2.4 or 5 GHz channel.
+pub fn validate_wireless(input: &WirelessNetworkInput) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    if input.ssid.is_empty() {
+        diagnostics.push(Diagnostic::error("ssid", "The SSID is empty").suggest("Enter a network name"));
+    } else if input.ssid.len() > MAX_SSID_LEN {
+        diagnostics.push(
+            Diagnostic::error("ssid", format!(
+                "The SSID is {} bytes long, more than the {} an SSID can hold", input.ssid.len(), MAX_SSID_LEN
+            ))
+            .suggest("Shorten the network name"),
+        );
+    }
+    if input.ssid.contains(char::is_control) {
+        diagnostics.push(Diagnostic::error("ssid", "The SSID contains control characters"));
+    }
+
+    let passphrase_len = input.wpa_password.chars().count();
+    if !input.wpa_password.chars().all(|c| c == ' ' || c.is_ascii_graphic()) {
+        diagnostics.push(
+            Diagnostic::error("wpa_password", "The passphrase contains characters other than printable ASCII")
+                .suggest("Use letters, digits, spaces and ASCII punctuation only"),
+        );
+    } else if !is_valid_wpa_key(&input.wpa_password) {
+        diagnostics.push(
+            Diagnostic::error("wpa_password", format!(
+                "The passphrase is {} characters long, WPA needs {} to {} or a key of 64 hex digits",
+                passphrase_len, WPA_PASSPHRASE_LEN.start(), WPA_PASSPHRASE_LEN.end()
+            ))
+            .suggest(format!("Use {} to {} characters", WPA_PASSPHRASE_LEN.start(), WPA_PASSPHRASE_LEN.end())),
+        );
+    } else if is_hex_psk(&input.wpa_password) && input.security != WirelessSecurity::Wpa2 {
+        diagnostics.push(
+            Diagnostic::error("security", format!("{} needs a passphrase, not a hex pre-shared key", input.security))
+                .suggest("Enter the passphrase or use WPA2-PSK"),
+        );
+    }
+
+    if let Some(secret) = &input.enable_secret {
+        if secret.is_empty() || secret.starts_with(' ') || !secret.chars().all(|c| c == ' ' || c.is_ascii_graphic()) {
+            diagnostics.push(
+                Diagnostic::error("enable_secret", "The enable secret must be printable ASCII without leading spaces")
+                    .suggest("Use letters, digits and ASCII punctuation only"),
+            );
+        } else if secret.contains('?') {
+            diagnostics.push(
+                Diagnostic::error("enable_secret", "The enable secret contains ?, which IOS reads as a help request")
+                    .suggest("Leave out the question mark"),
+            );
+        }
+    }
+    if input.mask_passphrase && input.security != WirelessSecurity::Wpa2 {
+        diagnostics.push(
+            Diagnostic::warning("security", format!(
+                "{} cannot use the pre-shared key, so the masked hostapd.conf has no SAE password", input.security
+            ))
+            .suggest("Fill in sae_password in hostapd.conf before starting hostapd"),
+        );
+    }
+
+    for (field, name, value) in [
+        ("router_hostname", "router hostname", &input.router_hostname),
+        ("ap_hostname", "access point hostname", &input.ap_hostname),
+        ("interface", "wireless interface", &input.interface),
+    ] {
+        if value.is_empty() {
+            diagnostics.push(Diagnostic::error(field, format!("The {} is empty", name)));
+        } else if value.contains(|c: char| c.is_whitespace() || c.is_control()) {
+            diagnostics.push(
+                Diagnostic::error(field, format!("The {} \"{}\" contains spaces", name, value))
+                    .suggest(format!("Rename it to {}", value.split_whitespace().collect::<Vec<_>>().join("-"))),
+            );
+        }
+    }
+
+    let country_code = input.country_code.trim();
+    if country_code.len() != 2 || !country_code.chars().all(|c| c.is_ascii_alphabetic()) {
+        diagnostics.push(
+            Diagnostic::error("country_code", format!("\"{}\" is not a two-letter country code", input.country_code))
+                .suggest("Use the ISO 3166-1 code of the country the access point is in, such as US or DE"),
+        );
+    }
+
+    match hw_mode(input.channel) {
+        None => diagnostics.push(
+            Diagnostic::error("channel", format!("{} is not a 2.4 or 5 GHz Wi-Fi channel", input.channel))
+                .suggest("Use 1, 6 or 11 on 2.4 GHz or 36-48 on 5 GHz"),
+        ),
+        Some(_) if input.channel == 14 && !country_code.eq_ignore_ascii_case("JP") => diagnostics.push(
+            Diagnostic::error("channel", "Channel 14 is only allowed in Japan").suggest("Use 1, 6 or 11"),
+        ),
+        Some(_) if (12..=13).contains(&input.channel) && NORTH_AMERICA.contains(&country_code.to_ascii_uppercase().as_str()) => {
+            diagnostics.push(
+                Diagnostic::warning("channel", format!(
+                    "Channel {} is not allowed in {}", input.channel, country_code.to_ascii_uppercase()
+                ))
+                .suggest("Use 1, 6 or 11"),
+            )
+        }
+        Some(_) if DFS_CHANNELS.contains(&input.channel) => diagnostics.push(
+            Diagnostic::warning("channel", format!(
+                "Channel {} needs radar detection and may take a minute to come up", input.channel
+            ))
+            .suggest("Use 36-48 to avoid the radar check"),
+        ),
+        Some(_) => {}
+    }
+
+    diagnostics
+}
+
+/// Plans the wireless subnet and renders the router and access point
+/// commands plus step-by-step setup instructions, and the hostapd, dnsmasq
+/// and Kea files for a Linux access point.
+///
+/// Fails with [`DikaiosError::ValidationFailed`] when [`validate_wireless`]
+/// finds errors; its warnings are returned in the configuration.
+pub fn generate_wireless_config(input: WirelessNetworkInput) -> Result<WirelessNetworkConfig, DikaiosError> {
+    let diagnostics = validate_wireless(&input);
+    if diagnostics.iter().any(Diagnostic::is_error) {
+        return Err(DikaiosError::ValidationFailed { field: "wireless", diagnostics });
+    }
+
+    let psk = wpa_psk(&input.wpa_password, &input.ssid);
+    let (passphrase, psk_line) = if input.mask_passphrase {
+        (MASKED, format!("\n! WPA PSK: {}", psk))
+    } else {
+        (input.wpa_password.as_str(), String::new())
+    };
+    let enable_secret = |salt: String| match &input.enable_secret {
+        Some(secret) => format!(
+            "\nenable secret {} {}", input.secret_type.number(), cisco_secret_hash(secret, &salt, input.secret_type)
+        ),
+        None => String::new(),
+    };
+    let (router_secret, ap_secret) = match input.enable_secret {
+        Some(_) => (enable_secret(cisco_salt()?), enable_secret(cisco_salt()?)),
+        None => (String::new(), String::new()),
+    };
+
+    let WirelessAddressing { prefix, router_ip, ap_ip, dhcp_start, dhcp_end } = wireless_addressing(&input)?;
+    let network = prefix.network().to_string();
+    let base_cidr = prefix.prefix_len();
+    let usable_hosts = prefix.host_count();
+
+    let subnet_mask = prefix.netmask().to_string();
+    let router_ip_str = Ipv4Addr::from(router_ip).to_string();
+    let ap_ip_str = Ipv4Addr::from(ap_ip).to_string();
+    let dhcp_start_str = Ipv4Addr::from(dhcp_start).to_string();
+    let dhcp_end_str = Ipv4Addr::from(dhcp_end).to_string();
+    let broadcast_str = prefix.broadcast().to_string();
+
+    // Generate Router Commands (typical wireless router with DHCP)
+    let router_commands = format!(
+r#"! Wireless Router Configuration for {}
+enable
+configure terminal
+hostname {}{}
+!
+! Configure Wireless Interface
+! NOTE: Interface name may vary by device model in Packet Tracer
+! Common names: GigabitEthernet0/0/0, FastEthernet0/0, GigabitEthernet0/1
+! Adjust the interface name below to match your specific router model
+interface GigabitEthernet0/0/0
+ip address {} {}
+no shutdown
+exit
+!
+! Configure DHCP Pool
+ip dhcp pool WLAN-POOL
+network {} {}
+default-router {}
+dns-server {}
+exit
+!
+! Keep the router, the AP and static hosts out of the DHCP pool
+ip dhcp excluded-address {} {}
+!
+! Configure Wireless Settings (if supported in PT)
+! Note: In Cisco Packet Tracer, wireless settings might be configured via GUI
+! SSID: {}
+! Security: {}
+! Password: {}{}
+!
+! Save configuration
+end
+write memory
+exit"#,
+        input.ssid,
+        input.router_hostname, router_secret,
+        router_ip_str, subnet_mask,
+        network, subnet_mask,
+        router_ip_str,
+        DNS_SERVERS.join(" "),
+        router_ip_str, Ipv4Addr::from(dhcp_start - 1),
+        input.ssid,
+        input.security,
+        passphrase, psk_line
+    );
+
+    // Generate Access Point Commands
+    let ap_commands = format!(
+r#"! Wireless Access Point Configuration for {}
+enable
+configure terminal
+hostname {}{}
+!
+! Configure Access Point Interface
+! NOTE: Interface name may vary by device model in Packet Tracer
+! Common names: GigabitEthernet0/0, FastEthernet0/0, GigabitEthernet0/1
+! Adjust the interface name below to match your specific access point model
+interface GigabitEthernet0/0
+ip address {} {}
+no shutdown
+exit
+!
+! Set default gateway to wireless router
+ip default-gateway {}
+!
+! Configure Wireless Settings (typically done via GUI in Packet Tracer)
+! SSID: {}
+! Security Mode: {}
+! Password: {}{}
+! 
+! Steps to configure wireless in Packet Tracer GUI:
+! 1. Click on the Access Point
+! 2. Go to Config tab
+! 3. Select Port 1 (or wireless interface)
+! 4. Set SSID: {}
+! 5. Set Authentication: {}
+! 6. Set PSK Pass Phrase: {}
+! 7. Select the appropriate radio band (2.4GHz or 5GHz)
+!
+! Save configuration
+end
+write memory
+exit"#,
+        input.ssid,
+        input.ap_hostname, ap_secret,
+        ap_ip_str, subnet_mask,
+        router_ip_str,
+        input.ssid,
+        input.security,
+        passphrase, psk_line,
+        input.ssid,
+        input.security,
+        passphrase
+    );
+
+    let hostapd_conf = hostapd_conf(&input, &psk);
+
+    // The access point serves the router's DHCP pool when it runs dnsmasq
+    // or Kea instead
+    let mut dnsmasq_conf = format!("# dnsmasq configuration for {}\n", input.ssid);
+    dnsmasq_conf.push_str(&format!("# {} needs the static address {}/{}\n", input.interface, ap_ip_str, base_cidr));
+    dnsmasq_conf.push_str(&format!("interface={}\n", input.interface));
+    dnsmasq_conf.push_str("bind-interfaces\n");
+    dnsmasq_conf.push_str(&format!(
+        "dhcp-range={},{},{},{}\n", dhcp_start_str, dhcp_end_str, subnet_mask, DEFAULT_LEASE_TIME
+    ));
+    dnsmasq_conf.push_str(&format!("dhcp-option=option:router,{}\n", router_ip_str));
+    dnsmasq_conf.push_str(&format!("dhcp-option=option:dns-server,{}\n", DNS_SERVERS.join(",")));
+
+    let kea = json!({
+        "Dhcp4": {
+            "interfaces-config": { "interfaces": [input.interface] },
+            "subnet4": [{
+                "id": 1,
+                "subnet": format!("{}/{}", prefix.network(), base_cidr),
+                "pools": [{ "pool": format!("{} - {}", dhcp_start_str, dhcp_end_str) }],
+                "option-data": [
+                    { "name": "routers", "data": router_ip_str },
+                    { "name": "domain-name-servers", "data": DNS_SERVERS.join(", ") },
+                ],
+                "valid-lifetime": DEFAULT_LEASE_TIME,
+                "user-context": { "ssid": input.ssid },
+            }],
+        },
+    });
+    let kea_config = serde_json::to_string_pretty(&kea).unwrap_or_default() + "\n";
+
+    // Generate setup steps
+    let setup_steps = vec![
+        "1. Add a Wireless Router to your Packet Tracer workspace".to_string(),
+        "2. Add an Access Point (if separate from router) to your workspace".to_string(),
+        "3. Connect the Access Point to the Wireless Router using a copper straight-through cable".to_string(),
+        "4. Click on the Wireless Router and open CLI or Config tab".to_string(),
+        "5. Apply the router configuration commands (adjust interface names if needed)".to_string(),
+        "6. Click on the Access Point and open CLI or Config tab".to_string(),
+        "7. Apply the AP configuration commands (adjust interface names if needed)".to_string(),
+        "8. Configure wireless settings via the GUI (Config tab > Port 1):".to_string(),
+        format!("   - Set SSID to: {}", input.ssid),
+        format!("   - Set Authentication to: {}", input.security),
+        format!("   - Set PSK Pass Phrase to: {}", passphrase),
+        "9. Add wireless devices (laptops, smartphones) to the workspace".to_string(),
+        "10. Click on each wireless device, go to Desktop > PC Wireless".to_string(),
+        format!("11. Connect to SSID: {} using the password", input.ssid),
+        "12. Verify connectivity by pinging the router or other devices".to_string(),
+        "13. Devices should automatically receive IP addresses via DHCP".to_string(),
+    ];
+
+    Ok(WirelessNetworkConfig {
+        network,
+        cidr: base_cidr,
+        subnet_mask,
+        router_ip: router_ip_str,
+        ap_ip: ap_ip_str,
+        dhcp_start: dhcp_start_str,
+        dhcp_end: dhcp_end_str,
+        broadcast: broadcast_str,
+        usable_hosts,
+        ssid: input.ssid,
+        security: input.security,
+        psk,
+        masked: input.mask_passphrase,
+        router_commands,
+        ap_commands,
+        setup_steps,
+        hostapd_conf,
+        dnsmasq_conf,
+        kea_config,
+        warnings: diagnostics,
+    })
+}
+
+/// Where the router, the access point and the DHCP pool of a wireless
+/// network sit in its subnet, as numeric IPv4 addresses.
+pub(crate) struct WirelessAddressing {
+    /// The network as given, or the default one with `auto_generate`, with
+    /// its host bits cleared.
+    pub prefix: Ipv4Prefix,
+    pub router_ip: u32,
+    pub ap_ip: u32,
+    pub dhcp_start: u32,
+    pub dhcp_end: u32,
+}
+
+/// Plans the subnet of the wireless network described by `input`.
+pub(crate) fn wireless_addressing(input: &WirelessNetworkInput) -> Result<WirelessAddressing, DikaiosError> {
+    // Generate or use provided network
+    let (base_network, base_cidr) = if input.auto_generate {
+        // Use a common private network for WLAN
+        ("192.168.1.0".to_string(), 24u32)
+    } else {
+        let network = input.base_network.clone().ok_or(DikaiosError::Missing { field: "base_network" })?;
+        let cidr = input.base_cidr.as_ref().ok_or(DikaiosError::Missing { field: "base_cidr" })?
+            .ipv4_prefix_len("base_cidr")?;
+        (network, cidr)
+    };
+
+    // The router, the access point and a DHCP pool from the tenth address
+    // need at least a /28
+    if base_cidr > MAX_WIRELESS_PREFIX_LEN {
+        return Err(DikaiosError::OutOfRange { field: "base_cidr", min: 0, max: MAX_WIRELESS_PREFIX_LEN as u64 });
+    }
+
+    let prefix = Ipv4Prefix::new(parse_ipv4(&base_network, "base_network")?, base_cidr)
+        .map_err(|e| e.with_field("base_cidr"))?
+        .trunc();
+    let network_int = u32::from(prefix.network());
+
+    // Assign IPs
+    Ok(WirelessAddressing {
+        prefix,
+        router_ip: network_int + 1,
+        ap_ip: network_int + 2,
+        dhcp_start: network_int + 10,
+        dhcp_end: u32::from(prefix.last_host()),
+    })
+}
+
+/// The hostapd configuration that broadcasts the network from `interface`.
+///
+/// WPA2 uses the pre-shared key `psk` when the passphrase is masked or is a
+/// hex key already. SAE always needs the passphrase, so a masked
+/// configuration leaves `sae_password` commented out.
+fn hostapd_conf(input: &WirelessNetworkInput, psk: &str) -> String {
+    let hw_mode = hw_mode(input.channel).unwrap_or("g");
+
+    let mut conf = format!("# hostapd configuration for {}\n", input.ssid);
+    conf.push_str(&format!("interface={}\n", input.interface));
+    conf.push_str("driver=nl80211\n");
+    conf.push_str(&format!("ssid={}\n", input.ssid));
+    if !input.ssid.is_ascii() {
+        conf.push_str("utf8_ssid=1\n");
+    }
+    conf.push_str(&format!("country_code={}\n", input.country_code.trim().to_ascii_uppercase()));
+    conf.push_str("ieee80211d=1\n");
+    if DFS_CHANNELS.contains(&input.channel) {
+        conf.push_str("ieee80211h=1\n");
+    }
+    conf.push_str(&format!("hw_mode={}\n", hw_mode));
+    conf.push_str(&format!("channel={}\n", input.channel));
+    conf.push_str("ieee80211n=1\n");
+    if hw_mode == "a" {
+        conf.push_str("ieee80211ac=1\n");
+    }
+    conf.push_str("wmm_enabled=1\n");
+    conf.push_str("auth_algs=1\n");
+    conf.push_str("wpa=2\n");
+    conf.push_str("rsn_pairwise=CCMP\n");
+    conf.push_str(match input.security {
+        WirelessSecurity::Wpa2 => "wpa_key_mgmt=WPA-PSK\n",
+        WirelessSecurity::Wpa3 => "wpa_key_mgmt=SAE\nieee80211w=2\n",
+        WirelessSecurity::Wpa2Wpa3 => "wpa_key_mgmt=WPA-PSK SAE\nieee80211w=1\n",
+    });
+    if input.security != WirelessSecurity::Wpa3 {
+        if input.mask_passphrase || is_hex_psk(&input.wpa_password) {
+            conf.push_str(&format!("wpa_psk={}\n", psk));
+        } else {
+            conf.push_str(&format!("wpa_passphrase={}\n", input.wpa_password));
+        }
+    }
+    if input.security != WirelessSecurity::Wpa2 {
+        if input.mask_passphrase {
+            conf.push_str("# SAE cannot use the pre-shared key, fill in the passphrase\n");
+            conf.push_str("#sae_password=\n");
+        } else {
+            conf.push_str(&format!("sae_password={}\n", input.wpa_password));
+        }
+    }
+
+    conf
+}
+
@@ -0,0 +1,317 @@
+//! The Tauri commands invoked by the frontend pages.
+//!
+//! Failures are returned as [`DikaiosError`], which Tauri serializes into the
+//! rejected promise so the page can point at the offending field.
+
+use crate::calc::{
+    self, ConfigFile, ConflictInput, ConflictReport, DhcpExportInput, Diagnostic, DikaiosError, FreeSpaceInput,
+    FreeSpaceReport, HostPage, IpCalculatorInput, IpInput, LinuxExportInput, NetworkInfo, Platform, Project,
+    SubnetMaskReference, TrunkPort, UnusedSpace, VlanAllocation, VlanInput, VlsmInput, VlsmSubnet, WifiOnboarding,
+    WirelessNetworkConfig, WirelessNetworkInput,
+};
+use crate::ipam::{Assignment, Block, CommittedPlan, IpamStore, Reservation, SearchResults, Subnet, Vlan};
+use serde::Serialize;
+use std::fs::File;
+use std::sync::{Mutex, MutexGuard};
+use tauri::ipc::Channel;
+use tauri::State;
+
+/// Hosts sent per message by [`stream_ip_range`] unless the page asks otherwise.
+const DEFAULT_STREAM_CHUNK: usize = 4096;
+
+/// The IPAM database, opened once at startup and shared by every command.
+pub struct Ipam(Mutex<IpamStore>);
+
+impl Ipam {
+    pub fn new(store: IpamStore) -> Self {
+        Ipam(Mutex::new(store))
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, IpamStore>, DikaiosError> {
+        self.0.lock().map_err(|e| DikaiosError::Io { field: "ipam", message: e.to_string() })
+    }
+}
+
+#[derive(Serialize)]
+pub struct IpCalculatorResult {
+    info: NetworkInfo,
+}
+
+#[derive(Serialize)]
+pub struct VlsmResult {
+    subnets: Vec<VlsmSubnet>,
+    unused: UnusedSpace,
+}
+
+#[derive(Serialize)]
+pub struct VlanResult {
+    allocations: Vec<VlanAllocation>,
+    trunks: Vec<TrunkPort>,
+    platform: Platform,
+    commands: String,
+    unused: UnusedSpace,
+    warnings: Vec<Diagnostic>,
+}
+
+#[derive(Serialize)]
+pub struct WirelessNetworkResult {
+    config: WirelessNetworkConfig,
+}
+
+#[tauri::command]
+pub fn generate_subnet_references() -> Vec<SubnetMaskReference> {
+    calc::generate_subnet_references()
+}
+
+#[tauri::command]
+pub fn calculate_ip_range(input: IpInput) -> Result<HostPage, DikaiosError> {
+    calc::calculate_host_page(&input)
+}
+
+/// Sends every host matching the input's filter over `on_chunk`, ignoring
+/// `offset` and `limit`, and returns how many were sent.
+#[tauri::command(async)]
+pub fn stream_ip_range(
+    input: IpInput,
+    chunk_size: Option<usize>,
+    on_chunk: Channel<Vec<String>>,
+) -> Result<u64, DikaiosError> {
+    let range = calc::host_range(&input.ip_address, &input.subnet_mask)?;
+    range.for_each_chunk(input.filter.as_deref(), chunk_size.unwrap_or(DEFAULT_STREAM_CHUNK), |chunk| {
+        on_chunk.send(chunk)
+            .map_err(|e| DikaiosError::Io { field: "on_chunk", message: e.to_string() })
+    })
+}
+
+/// Writes every host matching the input's filter to `path`, one per line,
+/// and returns how many were written.
+#[tauri::command(async)]
+pub fn export_ip_range(input: IpInput, path: String) -> Result<u64, DikaiosError> {
+    let range = calc::host_range(&input.ip_address, &input.subnet_mask)?;
+    File::create(&path)
+        .and_then(|file| range.write_to(input.filter.as_deref(), file))
+        .map_err(|e| DikaiosError::Io { field: "path", message: e.to_string() })
+}
+
+#[tauri::command]
+pub fn calculate_network_info(input: IpCalculatorInput) -> Result<IpCalculatorResult, DikaiosError> {
+    let info = calc::calculate_ip_info(&input.ip_address, &input.network_bits)?;
+    Ok(IpCalculatorResult { info })
+}
+
+#[tauri::command]
+pub fn calculate_vlsm(input: VlsmInput) -> Result<VlsmResult, DikaiosError> {
+    let plan = calc::calculate_vlsm_plan(&input)?;
+    Ok(VlsmResult { subnets: plan.subnets, unused: plan.unused })
+}
+
+#[tauri::command]
+pub fn calculate_free_space(input: FreeSpaceInput) -> Result<FreeSpaceReport, DikaiosError> {
+    calc::free_space_report(&input)
+}
+
+/// Reports every overlapping, duplicate or nested pair of the labelled prefixes.
+#[tauri::command]
+pub fn check_conflicts(input: ConflictInput) -> Result<ConflictReport, DikaiosError> {
+    calc::check_conflicts(&input.prefixes)
+}
+
+#[tauri::command]
+pub fn calculate_vlan_allocation(input: VlanInput) -> Result<VlanResult, DikaiosError> {
+    let plan = calc::calculate_vlan_plan(&input)?;
+    let commands = plan.render(input.platform);
+    Ok(VlanResult {
+        allocations: plan.allocations,
+        trunks: plan.trunks,
+        platform: input.platform,
+        commands,
+        unused: plan.unused,
+        warnings: plan.warnings,
+    })
+}
+
+/// Turns the allocations of a VLAN plan into Linux subinterface
+/// configuration files.
+#[tauri::command]
+pub fn export_linux_network_config(input: LinuxExportInput) -> Result<Vec<ConfigFile>, DikaiosError> {
+    calc::export_linux_config(&input.allocations, &input.parent_interface, input.uplink_vlan, input.format)
+}
+
+/// Collects the DHCP scopes of a VLAN plan into one Kea or ISC dhcpd
+/// configuration.
+#[tauri::command]
+pub fn export_dhcp_server_config(input: DhcpExportInput) -> String {
+    calc::dhcp_server_config(&input.allocations, input.server)
+}
+
+#[tauri::command]
+pub fn configure_wireless_network(input: WirelessNetworkInput) -> Result<WirelessNetworkResult, DikaiosError> {
+    let config = calc::generate_wireless_config(input)?;
+    Ok(WirelessNetworkResult { config })
+}
+
+/// The join QR code and printable onboarding card of a wireless network.
+#[tauri::command]
+pub fn create_wifi_onboarding(input: WirelessNetworkInput) -> Result<WifiOnboarding, DikaiosError> {
+    calc::generate_wifi_onboarding(&input)
+}
+
+/// Writes the join QR code of a wireless network to `path` as a PNG image.
+#[tauri::command(async)]
+pub fn export_wifi_qr_png(payload: String, path: String) -> Result<(), DikaiosError> {
+    let png = calc::qr_png(&payload, calc::QR_PNG_MODULE_SIZE)?;
+    std::fs::write(&path, png).map_err(|e| DikaiosError::Io { field: "path", message: e.to_string() })
+}
+
+/// Saves `project` to `path`, as TOML if the name ends in `.toml` and as
+/// JSON otherwise.
+#[tauri::command(async)]
+pub fn save_project(project: Project, path: String) -> Result<(), DikaiosError> {
+    calc::save_project(&project, &path)
+}
+
+/// Opens the project file at `path`, upgrading it if an older version wrote
+/// it.
+#[tauri::command(async)]
+pub fn open_project(path: String) -> Result<Project, DikaiosError> {
+    calc::load_project(&path)
+}
+
+#[tauri::command]
+pub fn ipam_list_blocks(ipam: State<'_, Ipam>) -> Result<Vec<Block>, DikaiosError> {
+    ipam.lock()?.blocks()
+}
+
+#[tauri::command]
+pub fn ipam_add_block(ipam: State<'_, Ipam>, block: Block) -> Result<Block, DikaiosError> {
+    ipam.lock()?.add_block(&block)
+}
+
+#[tauri::command]
+pub fn ipam_update_block(ipam: State<'_, Ipam>, block: Block) -> Result<Block, DikaiosError> {
+    ipam.lock()?.update_block(&block)
+}
+
+#[tauri::command]
+pub fn ipam_delete_block(ipam: State<'_, Ipam>, id: i64) -> Result<(), DikaiosError> {
+    ipam.lock()?.delete_block(id)
+}
+
+#[tauri::command]
+pub fn ipam_list_reservations(ipam: State<'_, Ipam>, block_id: Option<i64>) -> Result<Vec<Reservation>, DikaiosError> {
+    ipam.lock()?.reservations(block_id)
+}
+
+#[tauri::command]
+pub fn ipam_add_reservation(ipam: State<'_, Ipam>, reservation: Reservation) -> Result<Reservation, DikaiosError> {
+    ipam.lock()?.add_reservation(&reservation)
+}
+
+#[tauri::command]
+pub fn ipam_delete_reservation(ipam: State<'_, Ipam>, id: i64) -> Result<(), DikaiosError> {
+    ipam.lock()?.delete_reservation(id)
+}
+
+#[tauri::command]
+pub fn ipam_list_subnets(ipam: State<'_, Ipam>, block_id: Option<i64>) -> Result<Vec<Subnet>, DikaiosError> {
+    ipam.lock()?.subnets(block_id)
+}
+
+#[tauri::command]
+pub fn ipam_add_subnet(ipam: State<'_, Ipam>, subnet: Subnet) -> Result<Subnet, DikaiosError> {
+    ipam.lock()?.add_subnet(&subnet)
+}
+
+#[tauri::command]
+pub fn ipam_update_subnet(ipam: State<'_, Ipam>, subnet: Subnet) -> Result<Subnet, DikaiosError> {
+    ipam.lock()?.update_subnet(&subnet)
+}
+
+#[tauri::command]
+pub fn ipam_delete_subnet(ipam: State<'_, Ipam>, id: i64) -> Result<(), DikaiosError> {
+    ipam.lock()?.delete_subnet(id)
+}
+
+#[tauri::command]
+pub fn ipam_list_vlans(ipam: State<'_, Ipam>) -> Result<Vec<Vlan>, DikaiosError> {
+    ipam.lock()?.vlans()
+}
+
+#[tauri::command]
+pub fn ipam_add_vlan(ipam: State<'_, Ipam>, vlan: Vlan) -> Result<Vlan, DikaiosError> {
+    ipam.lock()?.add_vlan(&vlan)
+}
+
+#[tauri::command]
+pub fn ipam_update_vlan(ipam: State<'_, Ipam>, vlan: Vlan) -> Result<Vlan, DikaiosError> {
+    ipam.lock()?.update_vlan(&vlan)
+}
+
+#[tauri::command]
+pub fn ipam_delete_vlan(ipam: State<'_, Ipam>, vlan_id: u32) -> Result<(), DikaiosError> {
+    ipam.lock()?.delete_vlan(vlan_id)
+}
+
+#[tauri::command]
+pub fn ipam_list_assignments(ipam: State<'_, Ipam>, subnet_id: Option<i64>) -> Result<Vec<Assignment>, DikaiosError> {
+    ipam.lock()?.assignments(subnet_id)
+}
+
+#[tauri::command]
+pub fn ipam_add_assignment(ipam: State<'_, Ipam>, assignment: Assignment) -> Result<Assignment, DikaiosError> {
+    ipam.lock()?.add_assignment(&assignment)
+}
+
+#[tauri::command]
+pub fn ipam_update_assignment(ipam: State<'_, Ipam>, assignment: Assignment) -> Result<Assignment, DikaiosError> {
+    ipam.lock()?.update_assignment(&assignment)
+}
+
+#[tauri::command]
+pub fn ipam_delete_assignment(ipam: State<'_, Ipam>, id: i64) -> Result<(), DikaiosError> {
+    ipam.lock()?.delete_assignment(id)
+}
+
+/// Stores the lowest free, aligned `/prefix_len` of `parent` as a new subnet.
+#[tauri::command]
+pub fn ipam_allocate_next_subnet(
+    ipam: State<'_, Ipam>,
+    parent: String,
+    prefix_len: u32,
+    name: Option<String>,
+) -> Result<Subnet, DikaiosError> {
+    ipam.lock()?.allocate_next_subnet(&parent, prefix_len, name.as_deref().unwrap_or_default())
+}
+
+/// Assigns the first free host address of the stored subnet `subnet`.
+#[tauri::command]
+pub fn ipam_allocate_next_address(
+    ipam: State<'_, Ipam>,
+    subnet: String,
+    hostname: Option<String>,
+) -> Result<Assignment, DikaiosError> {
+    ipam.lock()?.allocate_next_address(&subnet, hostname.as_deref().unwrap_or_default())
+}
+
+#[tauri::command]
+pub fn ipam_search(ipam: State<'_, Ipam>, query: String) -> Result<SearchResults, DikaiosError> {
+    ipam.lock()?.search(&query)
+}
+
+/// Calculates a VLSM plan and stores its subnets in the IPAM database.
+#[tauri::command]
+pub fn ipam_commit_vlsm(ipam: State<'_, Ipam>, input: VlsmInput) -> Result<CommittedPlan, DikaiosError> {
+    ipam.lock()?.commit_vlsm(&input)
+}
+
+/// Calculates a VLAN plan and stores its VLANs, subnets and gateways in the
+/// IPAM database.
+#[tauri::command]
+pub fn ipam_commit_vlan_plan(ipam: State<'_, Ipam>, input: VlanInput) -> Result<CommittedPlan, DikaiosError> {
+    ipam.lock()?.commit_vlan_plan(&input)
+}
+
+#[tauri::command]
+pub fn exit_app() {
+    std::process::exit(0x0);
+}
@@ -0,0 +1,163 @@
+//! Handing out the next free subnet of a parent prefix and the next free
+//! address of a subnet.
+//!
+//! Both look at what is stored, skip reserved ranges and store what they
+//! found in the same write transaction, so two requests running at once,
+//! from this process or another one, never receive the same block or
+//! address.
+
+use super::record::{Assignment, Subnet};
+use super::store::{
+    addr_key, db_error, family_number, insert_assignment, insert_subnet, parse_address, parse_network, range_keys,
+    IpamStore,
+};
+use crate::calc::{DikaiosError, Ipv4Prefix, Ipv6Prefix, Prefix};
+use rusqlite::{params, Connection, OptionalExtension, Params};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+impl IpamStore {
+    /// Stores the lowest free `/prefix_len` of `parent` as a subnet named
+    /// `name`. The subnet is aligned on its own size and overlaps no stored
+    /// subnet and no reserved range; `parent` must lie in a stored block.
+    pub fn allocate_next_subnet(&mut self, parent: &str, prefix_len: u32, name: &str) -> Result<Subnet, DikaiosError> {
+        let parent = parse_network(parent, "parent")?;
+        let max_len = match parent {
+            Prefix::V4(_) => Ipv4Prefix::MAX_LEN,
+            Prefix::V6(_) => Ipv6Prefix::MAX_LEN,
+        };
+        if !(parent.prefix_len()..=max_len).contains(&prefix_len) {
+            return Err(DikaiosError::OutOfRange {
+                field: "prefix_length",
+                min: parent.prefix_len() as u64,
+                max: max_len as u64,
+            });
+        }
+        let host_bits = max_len - prefix_len;
+        let mask = if host_bits == 128 { u128::MAX } else { (1u128 << host_bits) - 1 };
+
+        self.write(|tx| {
+            let (start, end) = range_keys(&parent);
+            let block: Option<i64> = tx.query_row(
+                "SELECT id FROM blocks WHERE family = ?1 AND range_start <= ?2 AND range_end >= ?3",
+                params![family_number(&parent), start, end],
+                |row| row.get(0),
+            ).optional().map_err(db_error)?;
+            if block.is_none() {
+                return Err(DikaiosError::InvalidInput {
+                    field: "parent",
+                    message: format!("No block holds {}, add it as a block first", parent),
+                });
+            }
+
+            let occupied = occupied_ranges(
+                tx,
+                "SELECT range_start, range_end FROM subnets WHERE family = ?1 AND range_start <= ?3 AND range_end >= ?2
+                 UNION ALL
+                 SELECT range_start, range_end FROM reservations WHERE family = ?1 AND range_start <= ?3 AND range_end >= ?2
+                 ORDER BY 1",
+                params![family_number(&parent), start, end],
+            )?;
+            let (first, last) = parent.bounds();
+            let network = first_free(first, last, mask, &occupied).ok_or_else(|| DikaiosError::AllocationFailed {
+                field: "prefix_length",
+                message: format!("No free /{} is left in {}", prefix_len, parent),
+            })?;
+
+            let prefix = match parent {
+                Prefix::V4(_) => Prefix::V4(Ipv4Prefix::new(Ipv4Addr::from(network as u32), prefix_len)?),
+                Prefix::V6(_) => Prefix::V6(Ipv6Prefix::new(Ipv6Addr::from(network), prefix_len)?),
+            };
+            insert_subnet(tx, &Subnet { prefix: prefix.to_string(), name: name.to_string(), ..Subnet::default() })
+        })
+    }
+
+    /// Assigns the lowest free host address of the stored subnet `subnet` to
+    /// `hostname`, skipping assigned addresses, the subnet's gateway and
+    /// reserved ranges. An IPv6 subnet never hands out its first address,
+    /// the subnet-router anycast address.
+    pub fn allocate_next_address(&mut self, subnet: &str, hostname: &str) -> Result<Assignment, DikaiosError> {
+        let prefix = parse_network(subnet, "subnet")?;
+
+        self.write(|tx| {
+            let (subnet_id, gateway): (i64, Option<String>) = tx.query_row(
+                "SELECT id, gateway FROM subnets WHERE prefix = ?1",
+                [prefix.to_string()],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            ).optional().map_err(db_error)?.ok_or_else(|| DikaiosError::InvalidInput {
+                field: "subnet",
+                message: format!("{} is not a stored subnet", prefix),
+            })?;
+
+            let (start, end) = range_keys(&prefix);
+            let mut occupied = occupied_ranges(
+                tx,
+                "SELECT sort_key, sort_key FROM addresses WHERE subnet_id = ?1
+                 UNION ALL
+                 SELECT range_start, range_end FROM reservations WHERE family = ?2 AND range_start <= ?4 AND range_end >= ?3
+                 ORDER BY 1",
+                params![subnet_id, family_number(&prefix), start, end],
+            )?;
+            if let Some(gateway) = gateway {
+                let gateway = u128::from_be_bytes(addr_key(parse_address(&gateway, "gateway")?));
+                occupied.push((gateway, gateway));
+                occupied.sort_unstable();
+            }
+
+            let (first, last) = match prefix {
+                Prefix::V4(prefix) => (u32::from(prefix.first_host()) as u128, u32::from(prefix.last_host()) as u128),
+                Prefix::V6(v6) => {
+                    let (network, last) = prefix.bounds();
+                    (if v6.prefix_len() < Ipv6Prefix::MAX_LEN { network + 1 } else { network }, last)
+                }
+            };
+            let addr = first_free(first, last, 0, &occupied).ok_or_else(|| DikaiosError::AllocationFailed {
+                field: "subnet",
+                message: format!("No free address is left in {}", prefix),
+            })?;
+
+            let addr = match prefix {
+                Prefix::V4(_) => IpAddr::V4(Ipv4Addr::from(addr as u32)),
+                Prefix::V6(_) => IpAddr::V6(Ipv6Addr::from(addr)),
+            };
+            insert_assignment(tx, &Assignment {
+                address: addr.to_string(),
+                hostname: hostname.to_string(),
+                ..Assignment::default()
+            })
+        })
+    }
+}
+
+/// The first and last address of every range a query returns, as integers.
+fn occupied_ranges(conn: &Connection, sql: &str, params: impl Params) -> Result<Vec<(u128, u128)>, DikaiosError> {
+    let mut statement = conn.prepare(sql).map_err(db_error)?;
+    let rows = statement
+        .query_map(params, |row| {
+            Ok((u128::from_be_bytes(row.get(0)?), u128::from_be_bytes(row.get(1)?)))
+        })
+        .map_err(db_error)?;
+    rows.collect::<Result<_, _>>().map_err(db_error)
+}
+
+/// The lowest `start` in `first..=last`, aligned on `mask + 1`, whose block
+/// `start..=start + mask` ends by `last` and misses every range of
+/// `occupied`, which must be sorted by first address.
+fn first_free(first: u128, last: u128, mask: u128, occupied: &[(u128, u128)]) -> Option<u128> {
+    let align = |value: u128| value.checked_add(mask).map(|value| value & !mask);
+    let fits = |candidate: u128| candidate <= last && last - candidate >= mask;
+
+    let mut candidate = align(first)?;
+    for &(start, end) in occupied {
+        if !fits(candidate) {
+            return None;
+        }
+        if end < candidate {
+            continue;
+        }
+        if candidate + mask < start {
+            break;
+        }
+        candidate = align(end.checked_add(1)?)?;
+    }
+    fits(candidate).then_some(candidate)
+}
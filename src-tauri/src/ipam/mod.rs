@@ -0,0 +1,32 @@
+//! The local IP address management database.
+//!
+//! An [`IpamStore`] keeps parent blocks, the subnets allocated from them,
+//! the addresses assigned to hosts in each subnet and the VLANs subnets
+//! belong to in one SQLite file. A plan from the VLSM or VLAN calculator is
+//! committed with a single call, which stores all of it or, if anything
+//! conflicts with what is already recorded, none of it. New subnets and
+//! addresses can also be taken from the lowest free space, outside the
+//! reserved ranges of each block.
+//!
+//! ```
+//! use dikaios_lib::ipam::{Assignment, Block, IpamStore, Subnet};
+//!
+//! let mut store = IpamStore::open_in_memory().unwrap();
+//! store.add_block(&Block { prefix: "10.0.0.0/16".to_string(), ..Block::default() }).unwrap();
+//! let lab = store.add_subnet(&Subnet { prefix: "10.0.1.0/24".to_string(), ..Subnet::default() }).unwrap();
+//! store.add_assignment(&Assignment { address: "10.0.1.10".to_string(), ..Assignment::default() }).unwrap();
+//! assert_eq!(store.assignments(Some(lab.id)).unwrap().len(), 1);
+//! assert!(store.add_subnet(&Subnet { prefix: "10.0.1.128/25".to_string(), ..Subnet::default() }).is_err());
+//! ```
+
+mod allocate;
+mod record;
+mod schema;
+mod store;
+
+pub use record::{AddressStatus, Assignment, Block, CommittedPlan, Reservation, SearchResults, Subnet, Vlan};
+pub use schema::SCHEMA_VERSION;
+pub use store::IpamStore;
+
+/// The name of the database file in the application data directory.
+pub const DATABASE_FILE: &str = "ipam.sqlite3";
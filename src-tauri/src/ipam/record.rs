@@ -0,0 +1,168 @@
+//! The rows of the IPAM database as they travel to and from the frontend.
+
+use crate::calc::DikaiosError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// A parent block that subnets are allocated from, e.g. `10.0.0.0/8`.
+///
+/// Blocks never overlap each other, so every subnet and address belongs to
+/// exactly one block.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct Block {
+    #[serde(default)]
+    pub id: i64,
+    pub prefix: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+/// A subnet allocated from a block.
+///
+/// `block_id` is filled in by the store from the block containing `prefix`;
+/// `vlan_id` refers to a stored [`Vlan`] and `gateway` must be a host
+/// address of the subnet.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct Subnet {
+    #[serde(default)]
+    pub id: i64,
+    #[serde(default)]
+    pub block_id: i64,
+    pub prefix: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub vlan_id: Option<u32>,
+    #[serde(default)]
+    pub gateway: Option<String>,
+    #[serde(default)]
+    pub description: String,
+}
+
+/// A range of a block kept out of automatic allocation, e.g. the first
+/// addresses of every subnet set aside for network equipment.
+///
+/// `block_id` is filled in by the store from the block holding the range.
+/// Subnets and addresses can still be added inside it by hand.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct Reservation {
+    #[serde(default)]
+    pub id: i64,
+    #[serde(default)]
+    pub block_id: i64,
+    pub first: String,
+    pub last: String,
+    #[serde(default)]
+    pub reason: String,
+}
+
+/// A VLAN, identified by its 802.1Q ID.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Vlan {
+    pub vlan_id: u32,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+/// What an assigned address is used for.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AddressStatus {
+    /// In use by the host it is assigned to.
+    #[default]
+    Active,
+    /// Set aside, e.g. for a gateway or a device still to be installed.
+    Reserved,
+    /// Handed out by a DHCP server, usually as a static lease.
+    Dhcp,
+    /// Still configured somewhere but due to be freed.
+    Deprecated,
+}
+
+impl AddressStatus {
+    pub const ALL: [AddressStatus; 4] =
+        [AddressStatus::Active, AddressStatus::Reserved, AddressStatus::Dhcp, AddressStatus::Deprecated];
+
+    /// The identifier used in inputs and in the database.
+    pub fn id(&self) -> &'static str {
+        match self {
+            AddressStatus::Active => "active",
+            AddressStatus::Reserved => "reserved",
+            AddressStatus::Dhcp => "dhcp",
+            AddressStatus::Deprecated => "deprecated",
+        }
+    }
+}
+
+impl fmt::Display for AddressStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            AddressStatus::Active => "Active",
+            AddressStatus::Reserved => "Reserved",
+            AddressStatus::Dhcp => "DHCP",
+            AddressStatus::Deprecated => "Deprecated",
+        })
+    }
+}
+
+impl FromStr for AddressStatus {
+    type Err = DikaiosError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let id = s.trim().to_ascii_lowercase();
+        Self::ALL.into_iter()
+            .find(|status| status.id() == id)
+            .ok_or_else(|| DikaiosError::InvalidInput {
+                field: "status",
+                message: format!(
+                    "Unknown address status \"{}\", expected one of {}",
+                    s.trim(),
+                    Self::ALL.map(|status| status.id()).join(", ")
+                ),
+            })
+    }
+}
+
+/// An address assigned to a host inside a subnet.
+///
+/// `subnet_id` is filled in by the store from the subnet containing
+/// `address`. `mac` is stored in lowercase colon-separated form.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct Assignment {
+    #[serde(default)]
+    pub id: i64,
+    #[serde(default)]
+    pub subnet_id: i64,
+    pub address: String,
+    #[serde(default)]
+    pub hostname: String,
+    #[serde(default)]
+    pub mac: Option<String>,
+    #[serde(default)]
+    pub owner: String,
+    #[serde(default)]
+    pub status: AddressStatus,
+    #[serde(default)]
+    pub notes: String,
+}
+
+/// Everything a search matched, each list in address order.
+#[derive(Debug, Serialize, Clone, Default, PartialEq, Eq)]
+pub struct SearchResults {
+    pub blocks: Vec<Block>,
+    pub subnets: Vec<Subnet>,
+    pub vlans: Vec<Vlan>,
+    pub assignments: Vec<Assignment>,
+}
+
+/// What committing a calculated plan stored: the block it was placed in
+/// and the records it created.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct CommittedPlan {
+    pub block: Block,
+    pub subnets: Vec<Subnet>,
+    pub vlans: Vec<Vlan>,
+    pub assignments: Vec<Assignment>,
+}
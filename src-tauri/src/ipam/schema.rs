@@ -0,0 +1,95 @@
+//! The SQLite schema of the IPAM database and its migrations.
+//!
+//! The schema version is kept in SQLite's `user_version` pragma. Opening a
+//! database applies every migration past its version in one transaction,
+//! so a database written by an older version is upgraded in place.
+//!
+//! Prefixes and addresses are stored as text for people and tools reading
+//! the file, next to their first and last address as 16-byte big-endian
+//! blobs. SQLite compares blobs byte by byte, so those columns order rows
+//! numerically and answer range queries for both address families.
+
+use super::store::db_error;
+use crate::calc::DikaiosError;
+use rusqlite::Connection;
+
+/// `MIGRATIONS[n]` brings a database at schema version `n` to version
+/// `n + 1`.
+const MIGRATIONS: [&str; 2] = [
+    "CREATE TABLE blocks (
+        id INTEGER PRIMARY KEY,
+        prefix TEXT NOT NULL UNIQUE,
+        family INTEGER NOT NULL,
+        range_start BLOB NOT NULL,
+        range_end BLOB NOT NULL,
+        description TEXT NOT NULL DEFAULT ''
+    );
+    CREATE TABLE vlans (
+        vlan_id INTEGER PRIMARY KEY CHECK (vlan_id BETWEEN 1 AND 4094),
+        name TEXT NOT NULL,
+        description TEXT NOT NULL DEFAULT ''
+    );
+    CREATE TABLE subnets (
+        id INTEGER PRIMARY KEY,
+        block_id INTEGER NOT NULL REFERENCES blocks(id) ON DELETE CASCADE,
+        prefix TEXT NOT NULL UNIQUE,
+        family INTEGER NOT NULL,
+        range_start BLOB NOT NULL,
+        range_end BLOB NOT NULL,
+        name TEXT NOT NULL DEFAULT '',
+        vlan_id INTEGER REFERENCES vlans(vlan_id) ON DELETE SET NULL,
+        gateway TEXT,
+        description TEXT NOT NULL DEFAULT ''
+    );
+    CREATE INDEX subnets_by_block ON subnets(block_id, range_start);
+    CREATE TABLE addresses (
+        id INTEGER PRIMARY KEY,
+        subnet_id INTEGER NOT NULL REFERENCES subnets(id) ON DELETE CASCADE,
+        address TEXT NOT NULL UNIQUE,
+        family INTEGER NOT NULL,
+        sort_key BLOB NOT NULL,
+        hostname TEXT NOT NULL DEFAULT '',
+        mac TEXT,
+        owner TEXT NOT NULL DEFAULT '',
+        status TEXT NOT NULL DEFAULT 'active',
+        notes TEXT NOT NULL DEFAULT ''
+    );
+    CREATE INDEX addresses_by_subnet ON addresses(subnet_id, sort_key);",
+    "CREATE TABLE reservations (
+        id INTEGER PRIMARY KEY,
+        block_id INTEGER NOT NULL REFERENCES blocks(id) ON DELETE CASCADE,
+        first TEXT NOT NULL,
+        last TEXT NOT NULL,
+        family INTEGER NOT NULL,
+        range_start BLOB NOT NULL,
+        range_end BLOB NOT NULL,
+        reason TEXT NOT NULL DEFAULT ''
+    );
+    CREATE INDEX reservations_by_block ON reservations(block_id, range_start);",
+];
+
+/// The schema version written by this build.
+pub const SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32;
+
+/// Turns on foreign keys and brings the schema up to [`SCHEMA_VERSION`].
+pub(crate) fn migrate(conn: &mut Connection) -> Result<(), DikaiosError> {
+    conn.pragma_update(None, "foreign_keys", true).map_err(db_error)?;
+
+    let version: u32 = conn.pragma_query_value(None, "user_version", |row| row.get(0)).map_err(db_error)?;
+    if version > SCHEMA_VERSION {
+        return Err(DikaiosError::InvalidInput {
+            field: "ipam",
+            message: format!(
+                "The database was written by a newer version of Dikaios (schema {}, this version reads up to {})",
+                version, SCHEMA_VERSION
+            ),
+        });
+    }
+
+    let tx = conn.transaction().map_err(db_error)?;
+    for migration in &MIGRATIONS[version as usize..] {
+        tx.execute_batch(migration).map_err(db_error)?;
+    }
+    tx.pragma_update(None, "user_version", SCHEMA_VERSION).map_err(db_error)?;
+    tx.commit().map_err(db_error)
+}
@@ -0,0 +1,836 @@
+//! Reading and writing the IPAM database.
+//!
+//! Every write runs in its own transaction, which takes the database's write
+//! lock before reading anything, so concurrent writers are serialized even
+//! across processes. Each row is checked against the rest of the database
+//! first: blocks may not overlap, a subnet must lie in a block without
+//! overlapping another subnet, and an address must be a host address of a
+//! subnet and assigned only once. Problems are reported as [`DikaiosError`]
+//! against the offending field, like calculator input.
+
+use super::record::{AddressStatus, Assignment, Block, CommittedPlan, Reservation, SearchResults, Subnet, Vlan};
+use super::schema;
+use crate::calc::prefix::{parse_ipv4, parse_ipv6};
+use crate::calc::vlan::MAX_VLAN_ID;
+use crate::calc::{self, DikaiosError, Ipv4Prefix, Ipv6Prefix, Prefix, VlanInput, VlsmInput};
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use rusqlite::{params, Connection, OptionalExtension, Row, TransactionBehavior};
+use std::net::IpAddr;
+use std::path::Path;
+
+const BLOCK_COLUMNS: &str = "id, prefix, description";
+const SUBNET_COLUMNS: &str = "id, block_id, prefix, name, vlan_id, gateway, description";
+const VLAN_COLUMNS: &str = "vlan_id, name, description";
+const ASSIGNMENT_COLUMNS: &str = "id, subnet_id, address, hostname, mac, owner, status, notes";
+const RESERVATION_COLUMNS: &str = "id, block_id, first, last, reason";
+
+/// An open IPAM database.
+pub struct IpamStore {
+    conn: Connection,
+}
+
+impl IpamStore {
+    /// Opens the database at `path`, creating it or upgrading its schema as
+    /// needed.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, DikaiosError> {
+        Self::with_connection(Connection::open(path).map_err(db_error)?)
+    }
+
+    /// Opens a database that lives in memory and is gone once dropped.
+    pub fn open_in_memory() -> Result<Self, DikaiosError> {
+        Self::with_connection(Connection::open_in_memory().map_err(db_error)?)
+    }
+
+    fn with_connection(mut conn: Connection) -> Result<Self, DikaiosError> {
+        schema::migrate(&mut conn)?;
+        Ok(Self { conn })
+    }
+
+    /// Runs `write` in a transaction that is committed only if it succeeds.
+    pub(super) fn write<T>(
+        &mut self,
+        write: impl FnOnce(&Connection) -> Result<T, DikaiosError>,
+    ) -> Result<T, DikaiosError> {
+        let tx = self.conn.transaction_with_behavior(TransactionBehavior::Immediate).map_err(db_error)?;
+        let result = write(&tx)?;
+        tx.commit().map_err(db_error)?;
+        Ok(result)
+    }
+
+    pub fn blocks(&self) -> Result<Vec<Block>, DikaiosError> {
+        let sql = format!("SELECT {} FROM blocks ORDER BY family, range_start", BLOCK_COLUMNS);
+        query_all(&self.conn, &sql, [], block_row)
+    }
+
+    pub fn add_block(&mut self, block: &Block) -> Result<Block, DikaiosError> {
+        self.write(|tx| insert_block(tx, block))
+    }
+
+    /// Changes the prefix or description of `block.id`. The new prefix must
+    /// still hold every subnet and reservation of the block.
+    pub fn update_block(&mut self, block: &Block) -> Result<Block, DikaiosError> {
+        self.write(|tx| {
+            let prefix = parse_network(&block.prefix, "prefix")?;
+            let (start, end) = range_keys(&prefix);
+            check_block_free(tx, &prefix, block.id)?;
+
+            let outside: Option<String> = tx.query_row(
+                "SELECT prefix FROM subnets WHERE block_id = ?1
+                 AND NOT (family = ?2 AND range_start >= ?3 AND range_end <= ?4) LIMIT 1",
+                params![block.id, family_number(&prefix), start, end],
+                |row| row.get(0),
+            ).optional().map_err(db_error)?;
+            if let Some(subnet) = outside {
+                return Err(DikaiosError::InvalidInput {
+                    field: "prefix",
+                    message: format!("Subnet {} of the block would fall outside {}", subnet, prefix),
+                });
+            }
+            let outside: Option<(String, String)> = tx.query_row(
+                "SELECT first, last FROM reservations WHERE block_id = ?1
+                 AND NOT (family = ?2 AND range_start >= ?3 AND range_end <= ?4) LIMIT 1",
+                params![block.id, family_number(&prefix), start, end],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            ).optional().map_err(db_error)?;
+            if let Some((first, last)) = outside {
+                return Err(DikaiosError::InvalidInput {
+                    field: "prefix",
+                    message: format!("Reserved range {} - {} of the block would fall outside {}", first, last, prefix),
+                });
+            }
+
+            let updated = Block { id: block.id, prefix: prefix.to_string(), description: block.description.trim().to_string() };
+            let changed = tx.execute(
+                "UPDATE blocks SET prefix = ?2, family = ?3, range_start = ?4, range_end = ?5, description = ?6 WHERE id = ?1",
+                params![updated.id, updated.prefix, family_number(&prefix), start, end, updated.description],
+            ).map_err(db_error)?;
+            found(changed, "block", block.id)?;
+            Ok(updated)
+        })
+    }
+
+    /// Deletes a block together with its subnets, their addresses and its
+    /// reservations.
+    pub fn delete_block(&mut self, id: i64) -> Result<(), DikaiosError> {
+        self.write(|tx| {
+            let changed = tx.execute("DELETE FROM blocks WHERE id = ?1", [id]).map_err(db_error)?;
+            found(changed, "block", id)
+        })
+    }
+
+    /// The reserved ranges of the block `block_id`, or of every block for
+    /// `None`.
+    pub fn reservations(&self, block_id: Option<i64>) -> Result<Vec<Reservation>, DikaiosError> {
+        let sql = format!(
+            "SELECT {} FROM reservations WHERE ?1 IS NULL OR block_id = ?1 ORDER BY family, range_start",
+            RESERVATION_COLUMNS
+        );
+        query_all(&self.conn, &sql, [block_id], reservation_row)
+    }
+
+    /// Reserves `reservation.first` to `reservation.last`, which must lie in
+    /// one block.
+    pub fn add_reservation(&mut self, reservation: &Reservation) -> Result<Reservation, DikaiosError> {
+        self.write(|tx| {
+            let first = parse_address(&reservation.first, "first")?;
+            let last = parse_address(&reservation.last, "last")?;
+            if first.is_ipv4() != last.is_ipv4() || addr_key(first) > addr_key(last) {
+                return Err(DikaiosError::InvalidInput {
+                    field: "last",
+                    message: format!("{} - {} is not a range of addresses", first, last),
+                });
+            }
+
+            let block_id: i64 = tx.query_row(
+                "SELECT id FROM blocks WHERE family = ?1 AND range_start <= ?2 AND range_end >= ?3",
+                params![addr_family_number(first), addr_key(first), addr_key(last)],
+                |row| row.get(0),
+            ).optional().map_err(db_error)?.ok_or_else(|| DikaiosError::InvalidInput {
+                field: "first",
+                message: format!("No block holds all of {} - {}", first, last),
+            })?;
+
+            let reason = reservation.reason.trim().to_string();
+            tx.execute(
+                "INSERT INTO reservations (block_id, first, last, family, range_start, range_end, reason)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    block_id, first.to_string(), last.to_string(), addr_family_number(first),
+                    addr_key(first), addr_key(last), reason,
+                ],
+            ).map_err(db_error)?;
+            Ok(Reservation { id: tx.last_insert_rowid(), block_id, first: first.to_string(), last: last.to_string(), reason })
+        })
+    }
+
+    pub fn delete_reservation(&mut self, id: i64) -> Result<(), DikaiosError> {
+        self.write(|tx| {
+            let changed = tx.execute("DELETE FROM reservations WHERE id = ?1", [id]).map_err(db_error)?;
+            found(changed, "reservation", id)
+        })
+    }
+
+    /// The subnets of the block `block_id`, or of every block for `None`.
+    pub fn subnets(&self, block_id: Option<i64>) -> Result<Vec<Subnet>, DikaiosError> {
+        let sql = format!(
+            "SELECT {} FROM subnets WHERE ?1 IS NULL OR block_id = ?1 ORDER BY family, range_start",
+            SUBNET_COLUMNS
+        );
+        query_all(&self.conn, &sql, [block_id], subnet_row)
+    }
+
+    pub fn add_subnet(&mut self, subnet: &Subnet) -> Result<Subnet, DikaiosError> {
+        self.write(|tx| insert_subnet(tx, subnet))
+    }
+
+    /// Changes `subnet.id`. A new prefix must still hold every address
+    /// assigned in the subnet.
+    pub fn update_subnet(&mut self, subnet: &Subnet) -> Result<Subnet, DikaiosError> {
+        self.write(|tx| {
+            let (updated, prefix) = check_subnet(tx, subnet, subnet.id)?;
+            let (start, end) = range_keys(&prefix);
+
+            let outside: Option<String> = tx.query_row(
+                "SELECT address FROM addresses WHERE subnet_id = ?1
+                 AND NOT (family = ?2 AND sort_key >= ?3 AND sort_key <= ?4) LIMIT 1",
+                params![subnet.id, family_number(&prefix), start, end],
+                |row| row.get(0),
+            ).optional().map_err(db_error)?;
+            if let Some(address) = outside {
+                return Err(DikaiosError::InvalidInput {
+                    field: "prefix",
+                    message: format!("Address {} assigned in the subnet would fall outside {}", address, prefix),
+                });
+            }
+
+            let changed = tx.execute(
+                "UPDATE subnets SET block_id = ?2, prefix = ?3, family = ?4, range_start = ?5, range_end = ?6,
+                 name = ?7, vlan_id = ?8, gateway = ?9, description = ?10 WHERE id = ?1",
+                params![
+                    updated.id, updated.block_id, updated.prefix, family_number(&prefix), start, end,
+                    updated.name, updated.vlan_id, updated.gateway, updated.description,
+                ],
+            ).map_err(db_error)?;
+            found(changed, "subnet", subnet.id)?;
+            Ok(updated)
+        })
+    }
+
+    /// Deletes a subnet together with the addresses assigned in it.
+    pub fn delete_subnet(&mut self, id: i64) -> Result<(), DikaiosError> {
+        self.write(|tx| {
+            let changed = tx.execute("DELETE FROM subnets WHERE id = ?1", [id]).map_err(db_error)?;
+            found(changed, "subnet", id)
+        })
+    }
+
+    pub fn vlans(&self) -> Result<Vec<Vlan>, DikaiosError> {
+        let sql = format!("SELECT {} FROM vlans ORDER BY vlan_id", VLAN_COLUMNS);
+        query_all(&self.conn, &sql, [], vlan_row)
+    }
+
+    pub fn add_vlan(&mut self, vlan: &Vlan) -> Result<Vlan, DikaiosError> {
+        self.write(|tx| {
+            let vlan = check_vlan(vlan)?;
+            let existing: Option<String> = tx
+                .query_row("SELECT name FROM vlans WHERE vlan_id = ?1", [vlan.vlan_id], |row| row.get(0))
+                .optional()
+                .map_err(db_error)?;
+            if let Some(name) = existing {
+                return Err(DikaiosError::InvalidInput {
+                    field: "vlan_id",
+                    message: format!("VLAN {} already exists as {}", vlan.vlan_id, name),
+                });
+            }
+            tx.execute(
+                "INSERT INTO vlans (vlan_id, name, description) VALUES (?1, ?2, ?3)",
+                params![vlan.vlan_id, vlan.name, vlan.description],
+            ).map_err(db_error)?;
+            Ok(vlan)
+        })
+    }
+
+    /// Renames or redescribes the VLAN `vlan.vlan_id`.
+    pub fn update_vlan(&mut self, vlan: &Vlan) -> Result<Vlan, DikaiosError> {
+        self.write(|tx| {
+            let vlan = check_vlan(vlan)?;
+            let changed = tx.execute(
+                "UPDATE vlans SET name = ?2, description = ?3 WHERE vlan_id = ?1",
+                params![vlan.vlan_id, vlan.name, vlan.description],
+            ).map_err(db_error)?;
+            found(changed, "VLAN", vlan.vlan_id as i64)?;
+            Ok(vlan)
+        })
+    }
+
+    /// Deletes a VLAN; its subnets stay, without a VLAN.
+    pub fn delete_vlan(&mut self, vlan_id: u32) -> Result<(), DikaiosError> {
+        self.write(|tx| {
+            let changed = tx.execute("DELETE FROM vlans WHERE vlan_id = ?1", [vlan_id]).map_err(db_error)?;
+            found(changed, "VLAN", vlan_id as i64)
+        })
+    }
+
+    /// The addresses assigned in the subnet `subnet_id`, or in every subnet
+    /// for `None`.
+    pub fn assignments(&self, subnet_id: Option<i64>) -> Result<Vec<Assignment>, DikaiosError> {
+        let sql = format!(
+            "SELECT {} FROM addresses WHERE ?1 IS NULL OR subnet_id = ?1 ORDER BY family, sort_key",
+            ASSIGNMENT_COLUMNS
+        );
+        query_all(&self.conn, &sql, [subnet_id], assignment_row)
+    }
+
+    pub fn add_assignment(&mut self, assignment: &Assignment) -> Result<Assignment, DikaiosError> {
+        self.write(|tx| insert_assignment(tx, assignment))
+    }
+
+    /// Changes `assignment.id`, moving it to another subnet if the address
+    /// changed.
+    pub fn update_assignment(&mut self, assignment: &Assignment) -> Result<Assignment, DikaiosError> {
+        self.write(|tx| {
+            let (updated, addr) = check_assignment(tx, assignment, assignment.id)?;
+            let changed = tx.execute(
+                "UPDATE addresses SET subnet_id = ?2, address = ?3, family = ?4, sort_key = ?5, hostname = ?6,
+                 mac = ?7, owner = ?8, status = ?9, notes = ?10 WHERE id = ?1",
+                params![
+                    updated.id, updated.subnet_id, updated.address, addr_family_number(addr), addr_key(addr),
+                    updated.hostname, updated.mac, updated.owner, updated.status, updated.notes,
+                ],
+            ).map_err(db_error)?;
+            found(changed, "address assignment", assignment.id)?;
+            Ok(updated)
+        })
+    }
+
+    pub fn delete_assignment(&mut self, id: i64) -> Result<(), DikaiosError> {
+        self.write(|tx| {
+            let changed = tx.execute("DELETE FROM addresses WHERE id = ?1", [id]).map_err(db_error)?;
+            found(changed, "address assignment", id)
+        })
+    }
+
+    /// Every record whose text contains `query`, ignoring case. A query that
+    /// is an address or prefix also finds the blocks and subnets overlapping
+    /// it and the addresses inside it; a number also finds the VLAN with
+    /// that ID.
+    pub fn search(&self, query: &str) -> Result<SearchResults, DikaiosError> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(SearchResults::default());
+        }
+
+        let pattern = format!("%{}%", query.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_"));
+        // Family 0 matches no row when the query is not an address
+        let (family, start, end) = match search_range(query) {
+            Some(prefix) => {
+                let (start, end) = range_keys(&prefix);
+                (family_number(&prefix), start, end)
+            }
+            None => (0, [0; 16], [0; 16]),
+        };
+        let vlan_id: Option<u32> = query.parse().ok();
+
+        let blocks = query_all(
+            &self.conn,
+            &format!(
+                "SELECT {} FROM blocks
+                 WHERE prefix LIKE ?1 ESCAPE '\\' OR description LIKE ?1 ESCAPE '\\'
+                 OR (family = ?2 AND range_start <= ?4 AND range_end >= ?3)
+                 ORDER BY family, range_start",
+                BLOCK_COLUMNS
+            ),
+            params![pattern, family, start, end],
+            block_row,
+        )?;
+        let subnets = query_all(
+            &self.conn,
+            &format!(
+                "SELECT {} FROM subnets
+                 WHERE prefix LIKE ?1 ESCAPE '\\' OR name LIKE ?1 ESCAPE '\\' OR description LIKE ?1 ESCAPE '\\'
+                 OR gateway LIKE ?1 ESCAPE '\\' OR vlan_id = ?5
+                 OR (family = ?2 AND range_start <= ?4 AND range_end >= ?3)
+                 ORDER BY family, range_start",
+                SUBNET_COLUMNS
+            ),
+            params![pattern, family, start, end, vlan_id],
+            subnet_row,
+        )?;
+        let vlans = query_all(
+            &self.conn,
+            &format!(
+                "SELECT {} FROM vlans
+                 WHERE name LIKE ?1 ESCAPE '\\' OR description LIKE ?1 ESCAPE '\\' OR vlan_id = ?2
+                 ORDER BY vlan_id",
+                VLAN_COLUMNS
+            ),
+            params![pattern, vlan_id],
+            vlan_row,
+        )?;
+        let assignments = query_all(
+            &self.conn,
+            &format!(
+                "SELECT {} FROM addresses
+                 WHERE address LIKE ?1 ESCAPE '\\' OR hostname LIKE ?1 ESCAPE '\\' OR mac LIKE ?1 ESCAPE '\\'
+                 OR owner LIKE ?1 ESCAPE '\\' OR notes LIKE ?1 ESCAPE '\\'
+                 OR (family = ?2 AND sort_key >= ?3 AND sort_key <= ?4)
+                 ORDER BY family, sort_key",
+                ASSIGNMENT_COLUMNS
+            ),
+            params![pattern, family, start, end],
+            assignment_row,
+        )?;
+
+        Ok(SearchResults { blocks, subnets, vlans, assignments })
+    }
+
+    /// Calculates the VLSM plan of `input` and stores its subnets in the
+    /// block holding the base network, creating the block if none does.
+    /// Nothing is stored if any subnet conflicts with the database.
+    pub fn commit_vlsm(&mut self, input: &VlsmInput) -> Result<CommittedPlan, DikaiosError> {
+        let parent = if input.base_network.contains(':') {
+            Prefix::from(
+                Ipv6Prefix::new(parse_ipv6(&input.base_network, "base_network")?, input.base_cidr.ipv6_prefix_len("base_cidr")?)
+                    .map_err(|e| e.with_field("base_cidr"))?,
+            )
+        } else {
+            Prefix::from(
+                Ipv4Prefix::new(parse_ipv4(&input.base_network, "base_network")?, input.base_cidr.ipv4_prefix_len("base_cidr")?)
+                    .map_err(|e| e.with_field("base_cidr"))?,
+            )
+        };
+        let plan = calc::calculate_vlsm_plan(input)?;
+
+        self.write(|tx| {
+            let block = block_for_plan(tx, &parent.trunc())?;
+            let subnets = plan.subnets.iter()
+                .map(|subnet| {
+                    let subnet = Subnet {
+                        prefix: format!("{}/{}", subnet.network, subnet.cidr),
+                        name: subnet.name.clone(),
+                        ..Subnet::default()
+                    };
+                    insert_subnet(tx, &subnet)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(CommittedPlan { block, subnets, vlans: Vec::new(), assignments: Vec::new() })
+        })
+    }
+
+    /// Calculates the VLAN plan of `input` and stores its VLANs, their
+    /// subnets with gateways, and the gateway and router addresses as
+    /// reserved assignments. VLANs already in the database take the names
+    /// of the plan. Nothing is stored if any subnet conflicts with the
+    /// database.
+    pub fn commit_vlan_plan(&mut self, input: &VlanInput) -> Result<CommittedPlan, DikaiosError> {
+        let parent = Ipv4Prefix::new(
+            parse_ipv4(&input.base_network, "base_network")?,
+            input.base_cidr.ipv4_prefix_len("base_cidr")?,
+        )
+        .map_err(|e| e.with_field("base_cidr"))?;
+        let plan = calc::calculate_vlan_plan(input)?;
+
+        self.write(|tx| {
+            let block = block_for_plan(tx, &Prefix::V4(parent.trunc()))?;
+            let mut committed = CommittedPlan { block, subnets: Vec::new(), vlans: Vec::new(), assignments: Vec::new() };
+
+            for allocation in &plan.allocations {
+                let vlan = check_vlan(&Vlan {
+                    vlan_id: allocation.vlan_id,
+                    name: allocation.vlan_name.clone(),
+                    description: String::new(),
+                })?;
+                tx.execute(
+                    "INSERT INTO vlans (vlan_id, name) VALUES (?1, ?2)
+                     ON CONFLICT (vlan_id) DO UPDATE SET name = excluded.name",
+                    params![vlan.vlan_id, vlan.name],
+                ).map_err(db_error)?;
+                committed.vlans.push(vlan);
+
+                let subnet = insert_subnet(tx, &Subnet {
+                    prefix: format!("{}/{}", allocation.network, allocation.cidr),
+                    name: allocation.vlan_name.clone(),
+                    vlan_id: Some(allocation.vlan_id),
+                    gateway: Some(allocation.gateway.clone()),
+                    ..Subnet::default()
+                })?;
+
+                let mut reserved = vec![(allocation.gateway.clone(), String::new(), "Gateway".to_string())];
+                if let (Some(gateway), Some(options)) = (&allocation.redundancy, &plan.redundancy) {
+                    for (address, router) in gateway.router_addresses.iter().zip(&options.routers) {
+                        reserved.push((address.clone(), router.clone(), format!("{} router", options.protocol)));
+                    }
+                }
+                for (address, hostname, notes) in reserved {
+                    let assignment = Assignment {
+                        address,
+                        hostname,
+                        status: AddressStatus::Reserved,
+                        notes: format!("{} of VLAN {}", notes, allocation.vlan_id),
+                        ..Assignment::default()
+                    };
+                    committed.assignments.push(insert_assignment(tx, &assignment)?);
+                }
+                committed.subnets.push(subnet);
+            }
+            Ok(committed)
+        })
+    }
+}
+
+pub(crate) fn db_error(e: rusqlite::Error) -> DikaiosError {
+    DikaiosError::Io { field: "ipam", message: e.to_string() }
+}
+
+/// Fails unless a write by ID changed a row.
+fn found(changed: usize, kind: &str, id: i64) -> Result<(), DikaiosError> {
+    if changed == 0 {
+        return Err(DikaiosError::InvalidInput { field: "id", message: format!("There is no {} with ID {}", kind, id) });
+    }
+    Ok(())
+}
+
+fn query_all<T>(
+    conn: &Connection,
+    sql: &str,
+    params: impl rusqlite::Params,
+    map: impl FnMut(&Row<'_>) -> rusqlite::Result<T>,
+) -> Result<Vec<T>, DikaiosError> {
+    let mut statement = conn.prepare(sql).map_err(db_error)?;
+    let rows = statement.query_map(params, map).map_err(db_error)?;
+    rows.collect::<Result<_, _>>().map_err(db_error)
+}
+
+fn block_row(row: &Row<'_>) -> rusqlite::Result<Block> {
+    Ok(Block { id: row.get(0)?, prefix: row.get(1)?, description: row.get(2)? })
+}
+
+fn subnet_row(row: &Row<'_>) -> rusqlite::Result<Subnet> {
+    Ok(Subnet {
+        id: row.get(0)?,
+        block_id: row.get(1)?,
+        prefix: row.get(2)?,
+        name: row.get(3)?,
+        vlan_id: row.get(4)?,
+        gateway: row.get(5)?,
+        description: row.get(6)?,
+    })
+}
+
+fn reservation_row(row: &Row<'_>) -> rusqlite::Result<Reservation> {
+    Ok(Reservation { id: row.get(0)?, block_id: row.get(1)?, first: row.get(2)?, last: row.get(3)?, reason: row.get(4)? })
+}
+
+fn vlan_row(row: &Row<'_>) -> rusqlite::Result<Vlan> {
+    Ok(Vlan { vlan_id: row.get(0)?, name: row.get(1)?, description: row.get(2)? })
+}
+
+fn assignment_row(row: &Row<'_>) -> rusqlite::Result<Assignment> {
+    Ok(Assignment {
+        id: row.get(0)?,
+        subnet_id: row.get(1)?,
+        address: row.get(2)?,
+        hostname: row.get(3)?,
+        mac: row.get(4)?,
+        owner: row.get(5)?,
+        status: row.get(6)?,
+        notes: row.get(7)?,
+    })
+}
+
+impl ToSql for AddressStatus {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.id()))
+    }
+}
+
+impl FromSql for AddressStatus {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        value.as_str()?.parse().map_err(|e| FromSqlError::Other(Box::new(e)))
+    }
+}
+
+/// `prefix` with its host bits cleared.
+pub(crate) fn parse_network(prefix: &str, field: &'static str) -> Result<Prefix, DikaiosError> {
+    prefix.parse::<Prefix>()
+        .map(|prefix| prefix.trunc())
+        .map_err(|e| e.with_field(field))
+}
+
+pub(super) fn parse_address(address: &str, field: &'static str) -> Result<IpAddr, DikaiosError> {
+    if address.contains(':') {
+        parse_ipv6(address, field).map(IpAddr::V6)
+    } else {
+        parse_ipv4(address, field).map(IpAddr::V4)
+    }
+}
+
+/// What the query of a search names, if it is an address or a prefix.
+fn search_range(query: &str) -> Option<Prefix> {
+    if query.contains('/') {
+        query.parse::<Prefix>().ok().map(|prefix| prefix.trunc())
+    } else {
+        match query.parse::<IpAddr>().ok()? {
+            IpAddr::V4(addr) => Ipv4Prefix::new(addr, Ipv4Prefix::MAX_LEN).ok().map(Prefix::V4),
+            IpAddr::V6(addr) => Ipv6Prefix::new(addr, Ipv6Prefix::MAX_LEN).ok().map(Prefix::V6),
+        }
+    }
+}
+
+pub(crate) fn family_number(prefix: &Prefix) -> u8 {
+    match prefix {
+        Prefix::V4(_) => 4,
+        Prefix::V6(_) => 6,
+    }
+}
+
+pub(super) fn addr_family_number(addr: IpAddr) -> u8 {
+    if addr.is_ipv4() { 4 } else { 6 }
+}
+
+/// The first and last address of `prefix` as sortable blobs.
+pub(crate) fn range_keys(prefix: &Prefix) -> ([u8; 16], [u8; 16]) {
+    let (start, end) = prefix.bounds();
+    (start.to_be_bytes(), end.to_be_bytes())
+}
+
+pub(crate) fn addr_key(addr: IpAddr) -> [u8; 16] {
+    match addr {
+        IpAddr::V4(addr) => (u32::from(addr) as u128).to_be_bytes(),
+        IpAddr::V6(addr) => u128::from(addr).to_be_bytes(),
+    }
+}
+
+/// Fails if `prefix` overlaps a block other than `id`.
+fn check_block_free(conn: &Connection, prefix: &Prefix, id: i64) -> Result<(), DikaiosError> {
+    let (start, end) = range_keys(prefix);
+    let overlapping: Option<String> = conn.query_row(
+        "SELECT prefix FROM blocks WHERE family = ?1 AND range_start <= ?3 AND range_end >= ?2 AND id != ?4 LIMIT 1",
+        params![family_number(prefix), start, end, id],
+        |row| row.get(0),
+    ).optional().map_err(db_error)?;
+    match overlapping {
+        Some(block) => Err(DikaiosError::InvalidInput {
+            field: "prefix",
+            message: format!("{} overlaps the block {}", prefix, block),
+        }),
+        None => Ok(()),
+    }
+}
+
+fn insert_block(conn: &Connection, block: &Block) -> Result<Block, DikaiosError> {
+    let prefix = parse_network(&block.prefix, "prefix")?;
+    check_block_free(conn, &prefix, 0)?;
+
+    let (start, end) = range_keys(&prefix);
+    let description = block.description.trim().to_string();
+    conn.execute(
+        "INSERT INTO blocks (prefix, family, range_start, range_end, description) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![prefix.to_string(), family_number(&prefix), start, end, description],
+    ).map_err(db_error)?;
+    Ok(Block { id: conn.last_insert_rowid(), prefix: prefix.to_string(), description })
+}
+
+/// The block a plan for `parent` goes into: the block holding it, or a new
+/// one if it overlaps none.
+fn block_for_plan(conn: &Connection, parent: &Prefix) -> Result<Block, DikaiosError> {
+    let (start, end) = range_keys(parent);
+    let holding = conn.query_row(
+        &format!(
+            "SELECT {} FROM blocks WHERE family = ?1 AND range_start <= ?2 AND range_end >= ?3",
+            BLOCK_COLUMNS
+        ),
+        params![family_number(parent), start, end],
+        block_row,
+    ).optional().map_err(db_error)?;
+    match holding {
+        Some(block) => Ok(block),
+        None => insert_block(conn, &Block { prefix: parent.to_string(), ..Block::default() })
+            .map_err(|e| e.with_field("base_network")),
+    }
+}
+
+/// Checks `subnet` against the subnets other than `id`, returning it in
+/// stored form together with its prefix.
+fn check_subnet(conn: &Connection, subnet: &Subnet, id: i64) -> Result<(Subnet, Prefix), DikaiosError> {
+    let prefix = parse_network(&subnet.prefix, "prefix")?;
+    let (start, end) = range_keys(&prefix);
+
+    let block_id: i64 = conn.query_row(
+        "SELECT id FROM blocks WHERE family = ?1 AND range_start <= ?2 AND range_end >= ?3",
+        params![family_number(&prefix), start, end],
+        |row| row.get(0),
+    ).optional().map_err(db_error)?.ok_or_else(|| DikaiosError::InvalidInput {
+        field: "prefix",
+        message: format!("No block holds {}, add its parent block first", prefix),
+    })?;
+
+    let overlapping: Option<String> = conn.query_row(
+        "SELECT prefix FROM subnets WHERE family = ?1 AND range_start <= ?3 AND range_end >= ?2 AND id != ?4 LIMIT 1",
+        params![family_number(&prefix), start, end, id],
+        |row| row.get(0),
+    ).optional().map_err(db_error)?;
+    if let Some(other) = overlapping {
+        return Err(DikaiosError::InvalidInput {
+            field: "prefix",
+            message: format!("{} overlaps the subnet {}", prefix, other),
+        });
+    }
+
+    if let Some(vlan_id) = subnet.vlan_id {
+        let known: Option<u32> = conn
+            .query_row("SELECT vlan_id FROM vlans WHERE vlan_id = ?1", [vlan_id], |row| row.get(0))
+            .optional()
+            .map_err(db_error)?;
+        if known.is_none() {
+            return Err(DikaiosError::InvalidInput {
+                field: "vlan_id",
+                message: format!("VLAN {} is not in the database, add it first", vlan_id),
+            });
+        }
+    }
+
+    let gateway = match subnet.gateway.as_deref().map(str::trim).filter(|gateway| !gateway.is_empty()) {
+        Some(gateway) => {
+            let addr = parse_address(gateway, "gateway")?;
+            check_host(&prefix, addr, "gateway")?;
+            Some(addr.to_string())
+        }
+        None => None,
+    };
+
+    let checked = Subnet {
+        id,
+        block_id,
+        prefix: prefix.to_string(),
+        name: subnet.name.trim().to_string(),
+        vlan_id: subnet.vlan_id,
+        gateway,
+        description: subnet.description.trim().to_string(),
+    };
+    Ok((checked, prefix))
+}
+
+pub(super) fn insert_subnet(conn: &Connection, subnet: &Subnet) -> Result<Subnet, DikaiosError> {
+    let (mut subnet, prefix) = check_subnet(conn, subnet, 0)?;
+    let (start, end) = range_keys(&prefix);
+    conn.execute(
+        "INSERT INTO subnets (block_id, prefix, family, range_start, range_end, name, vlan_id, gateway, description)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![
+            subnet.block_id, subnet.prefix, family_number(&prefix), start, end,
+            subnet.name, subnet.vlan_id, subnet.gateway, subnet.description,
+        ],
+    ).map_err(db_error)?;
+    subnet.id = conn.last_insert_rowid();
+    Ok(subnet)
+}
+
+fn check_vlan(vlan: &Vlan) -> Result<Vlan, DikaiosError> {
+    if !(1..=MAX_VLAN_ID).contains(&vlan.vlan_id) {
+        return Err(DikaiosError::OutOfRange { field: "vlan_id", min: 1, max: MAX_VLAN_ID as u64 });
+    }
+    let name = vlan.name.trim();
+    if name.is_empty() {
+        return Err(DikaiosError::Missing { field: "name" });
+    }
+    Ok(Vlan { vlan_id: vlan.vlan_id, name: name.to_string(), description: vlan.description.trim().to_string() })
+}
+
+/// Fails unless `addr` can be given to a host in `prefix`: an IPv4 subnet's
+/// network and broadcast addresses cannot.
+pub(crate) fn check_host(prefix: &Prefix, addr: IpAddr, field: &'static str) -> Result<(), DikaiosError> {
+    if !prefix.contains_addr(addr) {
+        return Err(DikaiosError::InvalidInput { field, message: format!("{} is not in {}", addr, prefix) });
+    }
+    if let (Prefix::V4(prefix), IpAddr::V4(addr)) = (prefix, addr) {
+        if prefix.has_broadcast() && addr == prefix.network() {
+            return Err(DikaiosError::InvalidInput {
+                field,
+                message: format!("{} is the network address of {}", addr, prefix),
+            });
+        }
+        if prefix.broadcast_addr() == Some(addr) {
+            return Err(DikaiosError::InvalidInput {
+                field,
+                message: format!("{} is the broadcast address of {}", addr, prefix),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// `mac` as six lowercase colon-separated pairs, from any of the usual
+/// notations such as `00-1A-2B-3C-4D-5E` or `001a.2b3c.4d5e`.
+fn normalize_mac(mac: &str) -> Result<String, DikaiosError> {
+    let digits: String = mac.chars().filter(|c| !matches!(c, ':' | '-' | '.')).collect();
+    if digits.len() != 12 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(DikaiosError::InvalidInput {
+            field: "mac",
+            message: format!("\"{}\" is not a MAC address", mac.trim()),
+        });
+    }
+    let digits = digits.to_ascii_lowercase();
+    Ok((0..12).step_by(2).map(|i| &digits[i..i + 2]).collect::<Vec<_>>().join(":"))
+}
+
+/// Checks `assignment` against the assignments other than `id`, returning
+/// it in stored form together with its address.
+fn check_assignment(conn: &Connection, assignment: &Assignment, id: i64) -> Result<(Assignment, IpAddr), DikaiosError> {
+    let addr = parse_address(&assignment.address, "address")?;
+    let key = addr_key(addr);
+
+    let (subnet_id, subnet_prefix): (i64, String) = conn.query_row(
+        "SELECT id, prefix FROM subnets WHERE family = ?1 AND range_start <= ?2 AND range_end >= ?2",
+        params![addr_family_number(addr), key],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    ).optional().map_err(db_error)?.ok_or_else(|| DikaiosError::InvalidInput {
+        field: "address",
+        message: format!("No subnet holds {}, add the subnet first", addr),
+    })?;
+    check_host(&parse_network(&subnet_prefix, "prefix")?, addr, "address")?;
+
+    let taken: Option<String> = conn.query_row(
+        "SELECT hostname FROM addresses WHERE address = ?1 AND id != ?2",
+        params![addr.to_string(), id],
+        |row| row.get(0),
+    ).optional().map_err(db_error)?;
+    if let Some(hostname) = taken {
+        let holder = if hostname.is_empty() { String::new() } else { format!(" to {}", hostname) };
+        return Err(DikaiosError::InvalidInput {
+            field: "address",
+            message: format!("{} is already assigned{}", addr, holder),
+        });
+    }
+
+    let mac = match assignment.mac.as_deref().map(str::trim).filter(|mac| !mac.is_empty()) {
+        Some(mac) => Some(normalize_mac(mac)?),
+        None => None,
+    };
+
+    let checked = Assignment {
+        id,
+        subnet_id,
+        address: addr.to_string(),
+        hostname: assignment.hostname.trim().to_string(),
+        mac,
+        owner: assignment.owner.trim().to_string(),
+        status: assignment.status,
+        notes: assignment.notes.trim().to_string(),
+    };
+    Ok((checked, addr))
+}
+
+pub(super) fn insert_assignment(conn: &Connection, assignment: &Assignment) -> Result<Assignment, DikaiosError> {
+    let (mut assignment, addr) = check_assignment(conn, assignment, 0)?;
+    conn.execute(
+        "INSERT INTO addresses (subnet_id, address, family, sort_key, hostname, mac, owner, status, notes)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![
+            assignment.subnet_id, assignment.address, addr_family_number(addr), addr_key(addr),
+            assignment.hostname, assignment.mac, assignment.owner, assignment.status, assignment.notes,
+        ],
+    ).map_err(db_error)?;
+    assignment.id = conn.last_insert_rowid();
+    Ok(assignment)
+}
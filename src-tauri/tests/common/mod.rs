@@ -0,0 +1,61 @@
+//! Inputs shared by the integration tests. Each test changes only the
+//! fields it is about.
+
+#![allow(dead_code)]
+
+use dikaios_lib::calc::{
+    CiscoSecretType, MaskInput, Platform, VlanConfig, VlanInput, WirelessNetworkInput, WirelessSecurity,
+};
+
+pub fn vlan(vlan_id: u32, vlan_name: &str, required_hosts: u32, access_ports: &[&str]) -> VlanConfig {
+    VlanConfig {
+        vlan_id,
+        vlan_name: vlan_name.to_string(),
+        required_hosts,
+        access_ports: access_ports.iter().map(|ports| ports.to_string()).collect(),
+    }
+}
+
+/// Sales, Guest WiFi and Management, which fill 192.168.10.0/24 with a /26,
+/// a /27 and a /29.
+pub fn office_vlans() -> Vec<VlanConfig> {
+    vec![
+        vlan(10, "Sales", 50, &["Gi1/0/1-12"]),
+        vlan(20, "Guest WiFi", 20, &["Gi1/0/13-16", "Gi1/0/20"]),
+        vlan(99, "Management", 5, &[]),
+    ]
+}
+
+/// A Cisco IOS plan without trunks, DHCP or redundancy.
+pub fn vlan_input(base_network: &str, base_cidr: &str, vlan_configs: Vec<VlanConfig>) -> VlanInput {
+    VlanInput {
+        base_network: base_network.to_string(),
+        base_cidr: MaskInput::from(base_cidr.to_string()),
+        vlan_configs,
+        trunks: Vec::new(),
+        platform: Platform::CiscoIos,
+        dhcp: None,
+        redundancy: None,
+    }
+}
+
+/// A WPA2 network "Office" with the passphrase "correct horse" on channel 6
+/// in the US, addressed from the default 192.168.1.0/24.
+pub fn wireless_input() -> WirelessNetworkInput {
+    WirelessNetworkInput {
+        base_network: None,
+        base_cidr: None,
+        auto_generate: true,
+        ssid: "Office".to_string(),
+        wpa_password: "correct horse".to_string(),
+        router_hostname: "R1".to_string(),
+        ap_hostname: "AP1".to_string(),
+        security: WirelessSecurity::Wpa2,
+        country_code: "US".to_string(),
+        channel: 6,
+        interface: "wlan0".to_string(),
+        mask_passphrase: false,
+        enable_secret: None,
+        secret_type: CiscoSecretType::default(),
+    }
+}
@@ -0,0 +1,77 @@
+//! Overlap, duplicate and containment detection across labelled prefixes.
+
+use dikaios_lib::calc::{check_conflicts, ConflictKind, DikaiosError, LabelledPrefix};
+use std::time::Instant;
+
+fn pairs(prefixes: &[(&str, &str)]) -> Vec<(ConflictKind, String, String)> {
+    let prefixes: Vec<LabelledPrefix> =
+        prefixes.iter().map(|&(label, prefix)| LabelledPrefix::new(label, prefix)).collect();
+    check_conflicts(&prefixes)
+        .unwrap()
+        .conflicts
+        .into_iter()
+        .map(|conflict| (conflict.kind, conflict.outer.label, conflict.inner.label))
+        .collect()
+}
+
+fn pair(kind: ConflictKind, outer: &str, inner: &str) -> (ConflictKind, String, String) {
+    (kind, outer.to_string(), inner.to_string())
+}
+
+#[test]
+fn nested_and_duplicate_prefixes_are_reported() {
+    let found = pairs(&[
+        ("Campus", "10.0.0.0/16"),
+        ("Sales", "10.0.1.0/24"),
+        ("Guest", "192.168.0.0/24"),
+        ("Sales again", "10.0.1.77/24"),
+        ("Printers", "10.0.1.64/26"),
+        ("Lab", "10.1.0.0/24"),
+    ]);
+
+    assert_eq!(found, [
+        pair(ConflictKind::Contains, "Campus", "Sales"),
+        pair(ConflictKind::Contains, "Campus", "Sales again"),
+        pair(ConflictKind::Duplicate, "Sales", "Sales again"),
+        pair(ConflictKind::Contains, "Campus", "Printers"),
+        pair(ConflictKind::Contains, "Sales", "Printers"),
+        pair(ConflictKind::Contains, "Sales again", "Printers"),
+    ]);
+}
+
+#[test]
+fn families_are_checked_separately() {
+    let found = pairs(&[
+        ("Site", "2001:db8::/48"),
+        ("Users", "2001:db8:0:10::/64"),
+        ("Mapped", "0.0.0.0/0"),
+        ("Elsewhere", "2001:db9::/64"),
+    ]);
+    assert_eq!(found, [pair(ConflictKind::Contains, "Site", "Users")]);
+
+    let report = check_conflicts(&[LabelledPrefix::new("", "::/0"), LabelledPrefix::new("", "10.0.0.0/8")]).unwrap();
+    assert_eq!((report.duplicates, report.containments), (0, 0));
+}
+
+#[test]
+fn bad_prefixes_name_their_label() {
+    let result = check_conflicts(&[LabelledPrefix::new("Voice", "10.0.0.0/33")]);
+    match result {
+        Err(DikaiosError::InvalidInput { field: "prefixes", message }) => assert!(message.starts_with("Voice: ")),
+        other => panic!("expected the prefix to be rejected, got {:?}", other),
+    }
+}
+
+#[test]
+fn tens_of_thousands_of_prefixes_are_checked_quickly() {
+    let mut prefixes: Vec<LabelledPrefix> = (0..40_000u32)
+        .map(|n| LabelledPrefix::new(format!("v4-{}", n), format!("10.{}.{}.0/24", n >> 8, n & 0xff)))
+        .chain((0..20_000u32).map(|n| LabelledPrefix::new(format!("v6-{}", n), format!("2001:db8:{:x}::/64", n))))
+        .collect();
+    prefixes.push(LabelledPrefix::new("Overlap", "10.7.0.0/16"));
+
+    let started = Instant::now();
+    let report = check_conflicts(&prefixes).unwrap();
+    assert!(started.elapsed().as_secs() < 5);
+    assert_eq!((report.containments, report.duplicates), (256, 0));
+}
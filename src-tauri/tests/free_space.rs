@@ -0,0 +1,76 @@
+//! The free-space and fragmentation report of a parent block.
+
+use dikaios_lib::calc::{free_space_report, DikaiosError, FreeSpaceInput, FreeSpaceReport};
+
+fn report(parent: &str, used: &[&str]) -> FreeSpaceReport {
+    free_space_report(&FreeSpaceInput {
+        parent: parent.to_string(),
+        used: used.iter().map(|prefix| prefix.to_string()).collect(),
+        cell_prefix_length: None,
+    })
+    .unwrap()
+}
+
+#[test]
+fn gaps_become_the_fewest_free_blocks() {
+    let report = report("192.168.1.0/24", &["192.168.1.0/26", "192.168.1.128/27", "192.168.1.16/28"]);
+
+    assert_eq!(report.free_blocks, ["192.168.1.64/26", "192.168.1.160/27", "192.168.1.192/26"]);
+    assert_eq!((report.free_count.as_str(), report.used_count.as_str()), ("160", "96"));
+    assert_eq!(report.largest_free.as_deref(), Some("192.168.1.64/26"));
+    assert!((report.fragmentation - 0.6).abs() < 1e-9);
+
+    let runs: Vec<(&str, &str, bool)> =
+        report.runs.iter().map(|run| (run.first.as_str(), run.last.as_str(), run.used)).collect();
+    assert_eq!(runs, [
+        ("192.168.1.0", "192.168.1.63", true),
+        ("192.168.1.64", "192.168.1.127", false),
+        ("192.168.1.128", "192.168.1.159", true),
+        ("192.168.1.160", "192.168.1.255", false),
+    ]);
+    assert_eq!((report.runs[1].offset, report.runs[1].width), (0.25, 0.25));
+}
+
+#[test]
+fn empty_and_full_blocks_are_not_fragmented() {
+    let empty = report("10.0.0.0/8", &[]);
+    assert_eq!((empty.free_blocks.as_slice(), empty.fragmentation), (["10.0.0.0/8".to_string()].as_slice(), 0.0));
+
+    let full = report("10.0.0.0/30", &["10.0.0.0/31", "10.0.0.2/31"]);
+    assert!(full.free_blocks.is_empty() && full.largest_free.is_none());
+    assert_eq!((full.free_count.as_str(), full.fragmentation), ("0", 0.0));
+    assert_eq!(full.heatmap.len(), 4);
+
+    let everything = report("::/0", &[]);
+    assert_eq!(everything.free_count, "340282366920938463463374607431768211456");
+}
+
+#[test]
+fn the_heatmap_measures_each_cell() {
+    let report = report("2001:db8::/48", &["2001:db8::/50", "2001:db8:0:4000::/52", "2001:db8:0:ff00::/56"]);
+
+    assert_eq!(report.heatmap.len(), 64);
+    assert_eq!(report.heatmap[0].prefix, "2001:db8::/54");
+    let used: Vec<f64> = report.heatmap.iter().map(|cell| cell.used).collect();
+    assert!(used[..20].iter().all(|&used| used == 1.0));
+    assert!(used[20..63].iter().all(|&used| used == 0.0));
+    assert_eq!(used[63], 0.25);
+    assert_eq!(report.largest_free.as_deref(), Some("2001:db8:0:8000::/50"));
+}
+
+#[test]
+fn subnets_outside_the_parent_are_rejected() {
+    let result = free_space_report(&FreeSpaceInput {
+        parent: "10.0.0.0/24".to_string(),
+        used: vec!["10.0.1.0/25".to_string()],
+        cell_prefix_length: None,
+    });
+    assert!(matches!(result, Err(DikaiosError::InvalidInput { field: "used", .. })));
+
+    let result = free_space_report(&FreeSpaceInput {
+        parent: "10.0.0.0/24".to_string(),
+        used: Vec::new(),
+        cell_prefix_length: Some(8),
+    });
+    assert!(matches!(result, Err(DikaiosError::OutOfRange { field: "cell_prefix_length", min: 24, max: 32 })));
+}
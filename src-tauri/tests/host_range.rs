@@ -0,0 +1,64 @@
+//! Paging, filtering and streaming the hosts of an IPv4 subnet.
+
+use dikaios_lib::calc::{calculate_host_page, host_range, DikaiosError, IpInput, MAX_FILTER_SCAN};
+use std::time::Instant;
+
+#[test]
+fn pages_cover_the_usable_hosts() {
+    let first = calculate_host_page(&IpInput::new("192.168.1.77", "255.255.255.0").with_page(0, 100)).unwrap();
+    assert_eq!((first.hosts.len(), first.total, first.more), (100, Some(254), true));
+    assert_eq!(first.hosts[0], "192.168.1.1");
+
+    let last = calculate_host_page(&IpInput::new("192.168.1.77", "24").with_page(200, 100)).unwrap();
+    assert_eq!((last.hosts.len(), last.more), (54, false));
+    assert_eq!(last.hosts.last().unwrap(), "192.168.1.254");
+
+    assert!(matches!(
+        calculate_host_page(&IpInput::new("192.168.1.77", "24").with_page(0, 0)),
+        Err(DikaiosError::OutOfRange { field: "limit", .. })
+    ));
+}
+
+#[test]
+fn filtered_pages_count_matches_to_the_end_of_small_ranges() {
+    let page = calculate_host_page(&IpInput::new("10.0.0.0", "24").with_page(1, 2).with_filter(".1")).unwrap();
+    assert_eq!(page.hosts, ["10.0.0.10", "10.0.0.11"]);
+    assert!(page.more && page.total.is_none());
+
+    let page = calculate_host_page(&IpInput::new("10.0.0.0", "24").with_page(0, 256).with_filter("0.25")).unwrap();
+    assert_eq!(page.hosts, ["10.0.0.25", "10.0.0.250", "10.0.0.251", "10.0.0.252", "10.0.0.253", "10.0.0.254"]);
+    assert_eq!((page.total, page.more, page.truncated), (Some(6), false, false));
+}
+
+#[test]
+fn filtered_pages_of_huge_ranges_stop_early() {
+    let started = Instant::now();
+    let page = calculate_host_page(&IpInput::new("0.0.0.0", "0").with_page(0, 10).with_filter("7")).unwrap();
+    assert_eq!(page.hosts[0], "0.0.0.7");
+    assert!(page.more && page.total.is_none());
+
+    let page = calculate_host_page(&IpInput::new("10.0.0.0", "8").with_page(0, 10).with_filter("no match")).unwrap();
+    assert!(page.hosts.is_empty() && page.truncated && !page.more);
+    assert_eq!(page.total, None);
+    assert!(started.elapsed().as_secs() < 10, "a filtered page scans at most {} hosts", MAX_FILTER_SCAN);
+}
+
+#[test]
+fn streaming_and_writing_send_every_match() {
+    let range = host_range("172.16.0.0", "255.255.254.0").unwrap();
+    assert_eq!(range.len(), 510);
+
+    let mut chunks = Vec::new();
+    let sent = range
+        .for_each_chunk(Some("172.16.1.1"), 4, |chunk| {
+            chunks.push(chunk.len());
+            Ok::<_, ()>(())
+        })
+        .unwrap();
+    assert_eq!(sent, 111);
+    assert_eq!((chunks.len(), chunks.last().copied()), (28, Some(3)));
+
+    let mut written = Vec::new();
+    assert_eq!(host_range("10.0.0.0", "30").unwrap().write_to(None, &mut written).unwrap(), 2);
+    assert_eq!(String::from_utf8(written).unwrap(), "10.0.0.1\n10.0.0.2\n");
+}
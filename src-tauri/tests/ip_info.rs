@@ -0,0 +1,57 @@
+//! The IP calculator's details about an address and its network, IPv6
+//! included.
+
+use dikaios_lib::calc::info::ipv6_scope;
+use dikaios_lib::calc::{calculate_ip_info, AddressFamily, DikaiosError};
+use std::net::Ipv6Addr;
+
+#[test]
+fn ipv6_networks_are_described_in_both_notations() {
+    let info = calculate_ip_info("2001:DB8::1", "64").unwrap();
+
+    assert_eq!((info.family, info.prefix_length), (AddressFamily::Ipv6, 64));
+    assert_eq!((info.address.as_str(), info.network.as_str()), ("2001:db8::1", "2001:db8::"));
+    assert_eq!((info.netmask.as_str(), info.wildcard.as_str()), ("ffff:ffff:ffff:ffff::", "::ffff:ffff:ffff:ffff"));
+    assert_eq!((info.hostmin.as_str(), info.hostmax.as_str()), ("2001:db8::", "2001:db8::ffff:ffff:ffff:ffff"));
+    assert_eq!(info.address_count, "18446744073709551616");
+    assert_eq!((info.broadcast, info.hosts, info.ip_class), (None, None, None));
+    assert_eq!(info.compressed.as_deref(), Some("2001:db8::1"));
+    assert_eq!(info.expanded.as_deref(), Some("2001:0db8:0000:0000:0000:0000:0000:0001"));
+    assert_eq!((info.scope.as_deref(), info.is_private), (Some("global-unicast"), false));
+
+    let whole = calculate_ip_info("::", "0").unwrap();
+    assert_eq!(whole.address_count, "340282366920938463463374607431768211456");
+    let single = calculate_ip_info("fd00::7", "128").unwrap();
+    assert_eq!((single.address_count.as_str(), single.hostmin.as_str()), ("1", "fd00::7"));
+    assert!(single.is_private);
+}
+
+#[test]
+fn ipv6_scopes_are_classified() {
+    for (address, scope) in [
+        ("::", "unspecified"),
+        ("::1", "loopback"),
+        ("ff02::1", "multicast"),
+        ("fe80::1", "link-local"),
+        ("febf::1", "link-local"),
+        ("fc00::1", "unique-local"),
+        ("fdff::1", "unique-local"),
+        ("::ffff:192.0.2.1", "ipv4-mapped"),
+        ("2001:db8::1", "global-unicast"),
+        ("3fff::1", "global-unicast"),
+        ("4000::1", "reserved"),
+        ("fec0::1", "reserved"),
+    ] {
+        assert_eq!(ipv6_scope(&address.parse::<Ipv6Addr>().unwrap()), scope, "{}", address);
+    }
+}
+
+#[test]
+fn bad_ipv6_input_names_its_field() {
+    assert_eq!(calculate_ip_info("2001:db8::1", "129").unwrap_err().field(), "network_bits");
+    assert_eq!(calculate_ip_info("2001:db8::g", "64").unwrap_err().field(), "ip_address");
+    assert!(matches!(
+        calculate_ip_info("2001:db8:::1", "64"),
+        Err(DikaiosError::InvalidAddress { field: "ip_address" })
+    ));
+}
@@ -0,0 +1,131 @@
+//! Handing out the next free subnet and address from the IPAM database.
+
+use dikaios_lib::calc::DikaiosError;
+use dikaios_lib::ipam::{Assignment, Block, IpamStore, Reservation, Subnet};
+use std::collections::HashSet;
+use std::thread;
+
+fn block(prefix: &str) -> Block {
+    Block { prefix: prefix.to_string(), ..Block::default() }
+}
+
+fn reservation(first: &str, last: &str) -> Reservation {
+    Reservation { first: first.to_string(), last: last.to_string(), ..Reservation::default() }
+}
+
+fn exhausted<T: std::fmt::Debug>(result: Result<T, DikaiosError>) -> (&'static str, String) {
+    match result {
+        Err(DikaiosError::AllocationFailed { field, message }) => (field, message),
+        other => panic!("expected the allocation to fail, got {:?}", other),
+    }
+}
+
+#[test]
+fn subnets_fill_the_lowest_aligned_gaps() {
+    let mut store = IpamStore::open_in_memory().unwrap();
+    store.add_block(&block("10.0.0.0/16")).unwrap();
+    store.add_subnet(&Subnet { prefix: "10.0.0.0/26".to_string(), ..Subnet::default() }).unwrap();
+    store.add_subnet(&Subnet { prefix: "10.0.1.0/24".to_string(), ..Subnet::default() }).unwrap();
+
+    let users = store.allocate_next_subnet("10.0.0.0/16", 24, "Users").unwrap();
+    assert_eq!((users.prefix.as_str(), users.name.as_str()), ("10.0.2.0/24", "Users"));
+    assert_eq!(store.allocate_next_subnet("10.0.0.0/16", 26, "").unwrap().prefix, "10.0.0.64/26");
+    assert_eq!(store.allocate_next_subnet("10.0.0.0/16", 25, "").unwrap().prefix, "10.0.0.128/25");
+    assert_eq!(store.allocate_next_subnet("10.0.0.0/16", 26, "").unwrap().prefix, "10.0.3.0/26");
+
+    store.add_block(&block("2001:db8::/48")).unwrap();
+    assert_eq!(store.allocate_next_subnet("2001:db8::/48", 64, "").unwrap().prefix, "2001:db8::/64");
+    assert_eq!(store.allocate_next_subnet("2001:db8::/48", 56, "").unwrap().prefix, "2001:db8:0:100::/56");
+}
+
+#[test]
+fn reserved_ranges_are_skipped() {
+    let mut store = IpamStore::open_in_memory().unwrap();
+    let campus = store.add_block(&block("192.168.0.0/22")).unwrap();
+    let kept = store.add_reservation(&Reservation {
+        reason: "Lab".to_string(),
+        ..reservation("192.168.0.0", "192.168.1.10")
+    }).unwrap();
+    assert_eq!(kept.block_id, campus.id);
+
+    assert_eq!(store.allocate_next_subnet("192.168.0.0/22", 24, "").unwrap().prefix, "192.168.2.0/24");
+    assert_eq!(store.allocate_next_subnet("192.168.0.0/22", 28, "").unwrap().prefix, "192.168.1.16/28");
+
+    store.delete_reservation(kept.id).unwrap();
+    assert_eq!(store.allocate_next_subnet("192.168.0.0/22", 24, "").unwrap().prefix, "192.168.0.0/24");
+}
+
+#[test]
+fn addresses_skip_the_gateway_assignments_and_reservations() {
+    let mut store = IpamStore::open_in_memory().unwrap();
+    store.add_block(&block("10.0.0.0/16")).unwrap();
+    store.add_subnet(&Subnet {
+        prefix: "10.0.5.0/29".to_string(),
+        gateway: Some("10.0.5.1".to_string()),
+        ..Subnet::default()
+    }).unwrap();
+    store.add_assignment(&Assignment { address: "10.0.5.2".to_string(), ..Assignment::default() }).unwrap();
+    store.add_reservation(&reservation("10.0.5.3", "10.0.5.4")).unwrap();
+
+    let printer = store.allocate_next_address("10.0.5.0/29", "printer").unwrap();
+    assert_eq!((printer.address.as_str(), printer.hostname.as_str()), ("10.0.5.5", "printer"));
+    assert_eq!(store.allocate_next_address("10.0.5.0/29", "").unwrap().address, "10.0.5.6");
+    let (field, message) = exhausted(store.allocate_next_address("10.0.5.0/29", ""));
+    assert_eq!((field, message.as_str()), ("subnet", "No free address is left in 10.0.5.0/29"));
+
+    store.add_block(&block("2001:db8::/32")).unwrap();
+    store.add_subnet(&Subnet { prefix: "2001:db8:0:1::/64".to_string(), ..Subnet::default() }).unwrap();
+    assert_eq!(store.allocate_next_address("2001:db8:0:1::/64", "").unwrap().address, "2001:db8:0:1::1");
+}
+
+#[test]
+fn impossible_requests_are_refused() {
+    let mut store = IpamStore::open_in_memory().unwrap();
+    store.add_block(&block("10.0.0.0/24")).unwrap();
+    store.add_reservation(&reservation("10.0.0.200", "10.0.0.200")).unwrap();
+
+    store.allocate_next_subnet("10.0.0.0/24", 25, "").unwrap();
+    let (field, message) = exhausted(store.allocate_next_subnet("10.0.0.0/24", 25, ""));
+    assert_eq!((field, message.as_str()), ("prefix_length", "No free /25 is left in 10.0.0.0/24"));
+    assert_eq!(store.allocate_next_subnet("10.0.0.0/24", 26, "").unwrap().prefix, "10.0.0.128/26");
+
+    assert!(matches!(
+        store.allocate_next_subnet("10.0.0.0/24", 20, ""),
+        Err(DikaiosError::OutOfRange { field: "prefix_length", min: 24, max: 32 })
+    ));
+    assert!(matches!(
+        store.allocate_next_subnet("172.16.0.0/16", 24, ""),
+        Err(DikaiosError::InvalidInput { field: "parent", .. })
+    ));
+    assert!(matches!(
+        store.allocate_next_address("10.0.0.192/26", ""),
+        Err(DikaiosError::InvalidInput { field: "subnet", .. })
+    ));
+    assert!(matches!(
+        store.add_reservation(&reservation("10.0.0.9", "10.0.0.1")),
+        Err(DikaiosError::InvalidInput { field: "last", .. })
+    ));
+}
+
+#[test]
+fn concurrent_allocations_never_collide() {
+    let path = std::env::temp_dir().join(format!("dikaios-allocation-{}.sqlite3", std::process::id()));
+    IpamStore::open(&path).unwrap().add_block(&block("10.0.0.0/16")).unwrap();
+
+    let workers: Vec<_> = (0..4)
+        .map(|_| {
+            let path = path.clone();
+            thread::spawn(move || {
+                let mut store = IpamStore::open(&path).unwrap();
+                (0..8)
+                    .map(|_| store.allocate_next_subnet("10.0.0.0/16", 24, "").unwrap().prefix)
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+    let prefixes: Vec<String> = workers.into_iter().flat_map(|worker| worker.join().unwrap()).collect();
+
+    assert_eq!(prefixes.iter().collect::<HashSet<_>>().len(), 32);
+    assert_eq!(IpamStore::open(&path).unwrap().subnets(None).unwrap().last().unwrap().prefix, "10.0.31.0/24");
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,176 @@
+//! The IPAM database: records, the checks on every write, search and
+//! committing calculated plans.
+
+mod common;
+
+use common::{vlan, vlan_input};
+use dikaios_lib::calc::{
+    DikaiosError, FhrpProtocol, HostRequirement, MaskInput, RedundancyOptions, VlanInput, VlsmInput, VlsmOptions,
+};
+use dikaios_lib::calc::vlan::MAX_VLAN_ID;
+use dikaios_lib::ipam::{AddressStatus, Assignment, Block, IpamStore, Subnet, Vlan};
+
+fn block(prefix: &str) -> Block {
+    Block { prefix: prefix.to_string(), ..Block::default() }
+}
+
+fn subnet(prefix: &str) -> Subnet {
+    Subnet { prefix: prefix.to_string(), ..Subnet::default() }
+}
+
+fn assignment(address: &str, hostname: &str) -> Assignment {
+    Assignment { address: address.to_string(), hostname: hostname.to_string(), ..Assignment::default() }
+}
+
+fn rejected<T: std::fmt::Debug>(result: Result<T, DikaiosError>) -> (&'static str, String) {
+    match result {
+        Err(DikaiosError::InvalidInput { field, message }) => (field, message),
+        other => panic!("expected the write to be rejected, got {:?}", other),
+    }
+}
+
+#[test]
+fn records_are_normalized_and_kept_in_address_order() {
+    let mut store = IpamStore::open_in_memory().unwrap();
+    let campus = store.add_block(&block("10.0.0.0/16")).unwrap();
+    store.add_block(&block("2001:db8::/32")).unwrap();
+    assert_eq!(store.add_block(&block("192.168.1.77/24")).unwrap().prefix, "192.168.1.0/24");
+
+    store.add_vlan(&Vlan { vlan_id: 20, name: " Voice ".to_string(), description: String::new() }).unwrap();
+    for prefix in ["10.0.10.0/24", "10.0.9.0/24", "2001:db8:0:1::/64"] {
+        store.add_subnet(&subnet(prefix)).unwrap();
+    }
+    let voice = store.add_subnet(&Subnet {
+        vlan_id: Some(20),
+        gateway: Some("10.0.2.1".to_string()),
+        ..subnet("10.0.2.0/24")
+    }).unwrap();
+    assert_eq!(voice.block_id, campus.id);
+
+    let prefixes: Vec<String> = store.subnets(Some(campus.id)).unwrap().into_iter().map(|subnet| subnet.prefix).collect();
+    assert_eq!(prefixes, ["10.0.2.0/24", "10.0.9.0/24", "10.0.10.0/24"]);
+    assert_eq!(store.vlans().unwrap()[0].name, "Voice");
+
+    let phone = store.add_assignment(&Assignment {
+        mac: Some("001A.2B3C.4D5E".to_string()),
+        status: AddressStatus::Dhcp,
+        ..assignment("10.0.2.20", "phone-1")
+    }).unwrap();
+    assert_eq!((phone.subnet_id, phone.mac.as_deref()), (voice.id, Some("00:1a:2b:3c:4d:5e")));
+    store.add_assignment(&assignment("2001:db8:0:1:0:0:0:10", "printer")).unwrap();
+    assert_eq!(store.assignments(None).unwrap()[1].address, "2001:db8:0:1::10");
+}
+
+#[test]
+fn conflicting_writes_are_rejected() {
+    let mut store = IpamStore::open_in_memory().unwrap();
+    store.add_block(&block("10.0.0.0/16")).unwrap();
+    let lab = store.add_subnet(&subnet("10.0.1.0/24")).unwrap();
+    store.add_assignment(&assignment("10.0.1.10", "db-1")).unwrap();
+
+    assert_eq!(rejected(store.add_block(&block("10.0.128.0/17"))).1, "10.0.128.0/17 overlaps the block 10.0.0.0/16");
+    assert_eq!(rejected(store.add_subnet(&subnet("10.0.1.128/25"))).1, "10.0.1.128/25 overlaps the subnet 10.0.1.0/24");
+    assert_eq!(
+        rejected(store.add_subnet(&subnet("172.16.0.0/24"))).1,
+        "No block holds 172.16.0.0/24, add its parent block first"
+    );
+    assert_eq!(
+        rejected(store.add_subnet(&Subnet { vlan_id: Some(30), ..subnet("10.0.2.0/24") })),
+        ("vlan_id", "VLAN 30 is not in the database, add it first".to_string())
+    );
+    assert_eq!(rejected(store.add_assignment(&assignment("10.0.1.10", "db-2"))).1, "10.0.1.10 is already assigned to db-1");
+    assert_eq!(rejected(store.add_assignment(&assignment("10.0.1.255", ""))).1, "10.0.1.255 is the broadcast address of 10.0.1.0/24");
+    assert_eq!(
+        rejected(store.add_assignment(&Assignment { mac: Some("00:11:22".to_string()), ..assignment("10.0.1.12", "") })).0,
+        "mac"
+    );
+    assert_eq!(
+        store.add_vlan(&Vlan { vlan_id: MAX_VLAN_ID + 1, name: "Too high".to_string(), description: String::new() }),
+        Err(DikaiosError::OutOfRange { field: "vlan_id", min: 1, max: MAX_VLAN_ID as u64 })
+    );
+
+    // Shrinking the subnet would strand db-1
+    assert_eq!(
+        rejected(store.update_subnet(&Subnet { id: lab.id, ..subnet("10.0.1.0/29") })).1,
+        "Address 10.0.1.10 assigned in the subnet would fall outside 10.0.1.0/29"
+    );
+    assert_eq!(rejected(store.delete_subnet(999)), ("id", "There is no subnet with ID 999".to_string()));
+
+    store.delete_subnet(lab.id).unwrap();
+    assert!(store.assignments(None).unwrap().is_empty());
+}
+
+#[test]
+fn search_matches_text_and_addresses() {
+    let mut store = IpamStore::open_in_memory().unwrap();
+    store.add_block(&Block { description: "Head office".to_string(), ..block("10.0.0.0/16") }).unwrap();
+    store.add_vlan(&Vlan { vlan_id: 10, name: "Sales".to_string(), description: String::new() }).unwrap();
+    store.add_subnet(&Subnet { name: "Sales floor".to_string(), vlan_id: Some(10), ..subnet("10.0.10.0/24") }).unwrap();
+    store.add_subnet(&subnet("10.0.20.0/24")).unwrap();
+    store.add_assignment(&Assignment { owner: "Jo 100%".to_string(), ..assignment("10.0.20.5", "nas") }).unwrap();
+
+    let results = store.search("sales").unwrap();
+    assert_eq!((results.subnets.len(), results.vlans.len(), results.blocks.len()), (1, 1, 0));
+
+    let results = store.search("10.0.20.5").unwrap();
+    assert_eq!(results.blocks.len(), 1);
+    assert_eq!(results.subnets[0].prefix, "10.0.20.0/24");
+    assert_eq!(results.assignments[0].hostname, "nas");
+
+    assert_eq!(store.search("10.0.0.0/19").unwrap().subnets.len(), 2);
+    assert_eq!(store.search("10").unwrap().vlans[0].vlan_id, 10);
+    assert_eq!(store.search("0%").unwrap().assignments.len(), 1);
+    assert_eq!(store.search("1_0").unwrap(), Default::default());
+}
+
+#[test]
+fn plans_are_committed_whole_or_not_at_all() {
+    let mut store = IpamStore::open_in_memory().unwrap();
+    let vlsm = VlsmInput {
+        base_network: "192.168.1.0".to_string(),
+        base_cidr: MaskInput::from("24".to_string()),
+        host_requirements: vec![HostRequirement::new("LAN", 50), HostRequirement::new("WAN", 2)],
+        ipv6_requirements: Vec::new(),
+        options: VlsmOptions::default(),
+    };
+    let committed = store.commit_vlsm(&vlsm).unwrap();
+    assert_eq!(committed.block.prefix, "192.168.1.0/24");
+    assert_eq!(committed.subnets.iter().map(|subnet| subnet.prefix.as_str()).collect::<Vec<_>>(), ["192.168.1.0/26", "192.168.1.64/30"]);
+
+    // The same plan again overlaps what is stored and leaves nothing behind
+    assert_eq!(rejected(store.commit_vlsm(&vlsm)).0, "prefix");
+    assert_eq!(store.subnets(None).unwrap().len(), 2);
+
+    store.add_vlan(&Vlan { vlan_id: 10, name: "Old".to_string(), description: String::new() }).unwrap();
+    let vlans = VlanInput {
+        redundancy: Some(RedundancyOptions::new(FhrpProtocol::Vrrp)),
+        ..vlan_input("10.1.0.0", "16", vec![vlan(10, "Sales", 20, &[])])
+    };
+    let committed = store.commit_vlan_plan(&vlans).unwrap();
+    let sales = &committed.subnets[0];
+    assert_eq!((sales.vlan_id, sales.gateway.as_deref()), (Some(10), Some("10.1.0.1")));
+    assert_eq!(store.vlans().unwrap()[0].name, "Sales");
+
+    let reserved: Vec<(&str, &str, &str)> = committed.assignments.iter()
+        .map(|assignment| (assignment.address.as_str(), assignment.hostname.as_str(), assignment.notes.as_str()))
+        .collect();
+    assert_eq!(reserved, [
+        ("10.1.0.1", "", "Gateway of VLAN 10"),
+        ("10.1.0.2", "SW1", "VRRP router of VLAN 10"),
+        ("10.1.0.3", "SW2", "VRRP router of VLAN 10"),
+    ]);
+    assert!(committed.assignments.iter().all(|assignment| assignment.status == AddressStatus::Reserved));
+}
+
+#[test]
+fn the_database_survives_reopening() {
+    let path = std::env::temp_dir().join(format!("dikaios-ipam-{}.sqlite3", std::process::id()));
+    {
+        let mut store = IpamStore::open(&path).unwrap();
+        store.add_block(&block("10.0.0.0/8")).unwrap();
+    }
+    let store = IpamStore::open(&path).unwrap();
+    assert_eq!(store.blocks().unwrap()[0].prefix, "10.0.0.0/8");
+    drop(store);
+    std::fs::remove_file(&path).unwrap();
+}
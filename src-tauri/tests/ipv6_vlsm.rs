@@ -0,0 +1,62 @@
+//! IPv6 VLSM plans requested by prefix length or by the number of /64 LANs.
+
+use dikaios_lib::calc::{calculate_ipv6_vlsm_subnets, AddressFamily, DikaiosError, Ipv6Requirement, Ipv6Size};
+
+#[test]
+fn subnets_are_sized_by_prefix_length_and_64_count() {
+    let requirements = [
+        Ipv6Requirement::new("Campus", Ipv6Size::PrefixLength(52)),
+        Ipv6Requirement::new("Lab", Ipv6Size::Subnets64(3)),
+        Ipv6Requirement::new("Servers", Ipv6Size::Subnets64(1)),
+        Ipv6Requirement::new("DMZ", Ipv6Size::PrefixLength(56)),
+    ];
+    let plan = calculate_ipv6_vlsm_subnets("2001:db8::", 48, &requirements).unwrap();
+
+    let placed: Vec<(&str, usize, &str, u32)> = plan
+        .subnets
+        .iter()
+        .map(|subnet| (subnet.name.as_str(), subnet.index, subnet.network.as_str(), subnet.cidr))
+        .collect();
+    assert_eq!(placed, [
+        ("Campus", 0, "2001:db8::", 52),
+        ("DMZ", 3, "2001:db8:0:1000::", 56),
+        ("Lab", 1, "2001:db8:0:1100::", 62),
+        ("Servers", 2, "2001:db8:0:1104::", 64),
+    ]);
+
+    let lab = &plan.subnets[2];
+    assert_eq!(lab.family, AddressFamily::Ipv6);
+    assert_eq!(lab.last_host, "2001:db8:0:1103:ffff:ffff:ffff:ffff");
+    assert_eq!(lab.address_count, "73786976294838206464");
+    assert_eq!((lab.broadcast.as_deref(), lab.usable_hosts, lab.required_hosts), (None, None, None));
+
+    assert_eq!(plan.unused.blocks.first().map(String::as_str), Some("2001:db8:0:1105::/64"));
+    assert_eq!(plan.unused.blocks.last().map(String::as_str), Some("2001:db8:0:8000::/49"));
+    assert_eq!(plan.unused.blocks.len(), 13);
+}
+
+#[test]
+fn requirements_read_either_size_from_json() {
+    let requirements: Vec<Ipv6Requirement> =
+        serde_json::from_str(r#"[{"name": "Lab", "subnets_64": 3}, {"prefix_length": 56}]"#).unwrap();
+    assert_eq!(requirements, [
+        Ipv6Requirement::new("Lab", Ipv6Size::Subnets64(3)),
+        Ipv6Requirement::new("", Ipv6Size::PrefixLength(56)),
+    ]);
+}
+
+#[test]
+fn impossible_requirements_are_rejected() {
+    let plan = |size| calculate_ipv6_vlsm_subnets("2001:db8::", 48, &[Ipv6Requirement::new("LAN", size)]);
+    assert!(matches!(plan(Ipv6Size::Subnets64(0)), Err(DikaiosError::InvalidInput { .. })));
+    assert!(matches!(plan(Ipv6Size::PrefixLength(129)), Err(DikaiosError::OutOfRange { .. })));
+    assert!(matches!(plan(Ipv6Size::PrefixLength(40)), Err(DikaiosError::AllocationFailed { .. })));
+    assert_eq!(plan(Ipv6Size::Subnets64(0)).unwrap_err().field(), "ipv6_requirements");
+
+    let requirements =
+        [Ipv6Requirement::new("A", Ipv6Size::Subnets64(4)), Ipv6Requirement::new("B", Ipv6Size::Subnets64(1))];
+    assert_eq!(
+        calculate_ipv6_vlsm_subnets("2001:db8::", 62, &requirements).unwrap_err(),
+        DikaiosError::InsufficientSpace { field: "ipv6_requirements", required: 5 << 64, available: 4 << 64 }
+    );
+}
@@ -0,0 +1,95 @@
+//! Linux subinterface files for a VLAN plan, with the default route only on
+//! the uplink VLAN.
+
+mod common;
+
+use common::office_vlans;
+use dikaios_lib::calc::{calculate_vlans, export_linux_config, ConfigFile, DikaiosError, LinuxFormat, VlanAllocation};
+
+fn allocations() -> Vec<VlanAllocation> {
+    calculate_vlans("192.168.10.0", 24, &office_vlans(), &[]).unwrap().allocations
+}
+
+fn export(uplink_vlan: Option<u32>, format: LinuxFormat) -> Vec<ConfigFile> {
+    export_linux_config(&allocations(), "eth0", uplink_vlan, format).unwrap()
+}
+
+fn file<'a>(files: &'a [ConfigFile], path: &str) -> &'a str {
+    &files.iter().find(|file| file.path == path).unwrap_or_else(|| panic!("no {}", path)).contents
+}
+
+#[test]
+fn netplan_routes_only_through_the_uplink() {
+    let files = export(Some(99), LinuxFormat::Netplan);
+    let contents = file(&files, "/etc/netplan/60-dikaios-vlans.yaml");
+
+    assert!(contents.contains(
+        "    eth0.10:\n      id: 10\n      link: eth0\n      addresses:\n        - 192.168.10.2/26\n"
+    ));
+    assert!(contents.contains(
+        "        - 192.168.10.98/29\n      routes:\n        - to: default\n          via: 192.168.10.97\n"
+    ));
+    assert_eq!(contents.matches("to: default").count(), 1);
+    assert!(!contents.contains("metric"));
+}
+
+#[test]
+fn systemd_networkd_routes_only_through_the_uplink() {
+    let files = export(Some(10), LinuxFormat::SystemdNetworkd);
+
+    assert_eq!(
+        file(&files, "/etc/systemd/network/10-eth0.network"),
+        "[Match]\nName=eth0\n\n[Network]\nVLAN=eth0.10\nVLAN=eth0.20\nVLAN=eth0.99\n"
+    );
+    let netdev = file(&files, "/etc/systemd/network/20-eth0.20.netdev");
+    assert!(netdev.contains("Kind=vlan\nDescription=Guest WiFi\n\n[VLAN]\nId=20\n"));
+    assert_eq!(
+        file(&files, "/etc/systemd/network/20-eth0.10.network"),
+        "[Match]\nName=eth0.10\n\n[Network]\nAddress=192.168.10.2/26\n\n[Route]\nGateway=192.168.10.1\n"
+    );
+    assert_eq!(
+        file(&files, "/etc/systemd/network/20-eth0.20.network"),
+        "[Match]\nName=eth0.20\n\n[Network]\nAddress=192.168.10.66/27\n"
+    );
+}
+
+#[test]
+fn network_manager_routes_only_through_the_uplink() {
+    let files = export(Some(20), LinuxFormat::NetworkManager);
+    assert_eq!(files.len(), 3);
+
+    let guest = file(&files, "/etc/NetworkManager/system-connections/eth0.20.nmconnection");
+    assert!(guest.contains("[vlan]\nid=20\nparent=eth0\n"));
+    assert!(guest.contains("[ipv4]\nmethod=manual\naddress1=192.168.10.66/27,192.168.10.65\n\n"));
+    let sales = file(&files, "/etc/NetworkManager/system-connections/eth0.10.nmconnection");
+    assert!(sales.contains("[ipv4]\nmethod=manual\naddress1=192.168.10.2/26\nnever-default=true\n\n"));
+    assert!(files.iter().all(|file| !file.contents.contains("route-metric")));
+}
+
+#[test]
+fn ifupdown_routes_only_through_the_uplink() {
+    let files = export(Some(10), LinuxFormat::Ifupdown);
+    let contents = file(&files, "/etc/network/interfaces.d/dikaios-vlans");
+
+    assert!(contents.contains("auto eth0.10\niface eth0.10 inet static\n    vlan-raw-device eth0\n"));
+    assert!(contents.contains("    address 192.168.10.2/26\n    gateway 192.168.10.1\n\n"));
+    assert!(contents.contains("iface eth0.99 inet static\n    vlan-raw-device eth0\n    address 192.168.10.98/29\n\n"));
+    assert_eq!(contents.matches("gateway").count(), 1);
+}
+
+#[test]
+fn no_uplink_writes_no_default_route() {
+    for format in LinuxFormat::ALL {
+        let files = export(None, format);
+        for file in &files {
+            for route in ["to: default", "Gateway=", ",192.168.10.", "gateway "] {
+                assert!(!file.contents.contains(route), "{} in {}", route, file.path);
+            }
+        }
+    }
+
+    assert!(matches!(
+        export_linux_config(&allocations(), "eth0", Some(30), LinuxFormat::Netplan),
+        Err(DikaiosError::InvalidInput { field: "uplink_vlan", .. })
+    ));
+}
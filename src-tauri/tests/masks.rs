@@ -0,0 +1,51 @@
+//! Prefix lengths, dotted netmasks and wildcard masks read by the shared
+//! mask parser.
+
+use dikaios_lib::calc::{calculate_ip_info, parse_ipv4_mask, parse_ipv6_mask, DikaiosError, MaskInput};
+
+#[test]
+fn every_ipv4_notation_gives_the_same_length() {
+    for mask in ["26", "/26", " 26 ", "255.255.255.192", "0.0.0.63"] {
+        assert_eq!(parse_ipv4_mask(mask, "subnet_mask").unwrap(), 26, "{:?}", mask);
+    }
+    for (mask, len) in [("0.0.0.0", 0), ("255.255.255.255", 32), ("255.0.0.0", 8), ("0.255.255.255", 8), ("/0", 0)] {
+        assert_eq!(parse_ipv4_mask(mask, "subnet_mask").unwrap(), len, "{:?}", mask);
+    }
+
+    assert_eq!(MaskInput::from(24).ipv4_prefix_len("base_cidr").unwrap(), 24);
+    assert_eq!(MaskInput::from("0.0.3.255".to_string()).ipv4_prefix_len("base_cidr").unwrap(), 22);
+    let from_json: MaskInput = serde_json::from_str("\"255.255.252.0\"").unwrap();
+    assert_eq!(from_json.ipv4_prefix_len("base_cidr").unwrap(), 22);
+    assert_eq!(calculate_ip_info("10.1.2.3", "0.0.0.255").unwrap().network, "10.1.2.0");
+}
+
+#[test]
+fn non_contiguous_masks_are_rejected() {
+    for mask in ["255.0.255.0", "255.255.255.1", "0.255.0.255", "128.0.0.1"] {
+        assert_eq!(
+            parse_ipv4_mask(mask, "subnet_mask"),
+            Err(DikaiosError::NonContiguousMask { field: "subnet_mask" }),
+            "{:?}",
+            mask
+        );
+    }
+}
+
+#[test]
+fn out_of_range_and_malformed_masks_are_rejected() {
+    let too_long = Err(DikaiosError::OutOfRange { field: "base_cidr", min: 0, max: 32 });
+    assert_eq!(parse_ipv4_mask("33", "base_cidr"), too_long);
+    assert_eq!(MaskInput::from(33).ipv4_prefix_len("base_cidr"), too_long);
+    assert_eq!(parse_ipv4_mask("twenty", "base_cidr"), Err(DikaiosError::InvalidNumber { field: "base_cidr" }));
+    assert_eq!(parse_ipv4_mask("255.255.256.0", "base_cidr").unwrap_err().field(), "base_cidr");
+
+    assert_eq!(parse_ipv6_mask("/64", "network_bits").unwrap(), 64);
+    assert_eq!(
+        parse_ipv6_mask("129", "network_bits"),
+        Err(DikaiosError::OutOfRange { field: "network_bits", min: 0, max: 128 })
+    );
+    assert_eq!(
+        parse_ipv6_mask("ffff:ffff::", "network_bits"),
+        Err(DikaiosError::InvalidNumber { field: "network_bits" })
+    );
+}
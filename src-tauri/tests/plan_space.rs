@@ -0,0 +1,61 @@
+//! Plans that overflow their parent block, and the space plans leave free.
+
+mod common;
+
+use common::{office_vlans, vlan};
+use dikaios_lib::calc::{
+    calculate_ipv6_vlsm_subnets, calculate_vlans, calculate_vlsm_subnets, DikaiosError, HostRequirement,
+    Ipv6Requirement, Ipv6Size, VlsmOptions,
+};
+
+#[test]
+fn overflowing_plans_report_the_missing_addresses() {
+    let requirements = [HostRequirement::new("Users", 100), HostRequirement::new("Printers", 10)];
+    let error = calculate_vlsm_subnets("192.168.1.0", 25, &requirements, &VlsmOptions::default()).unwrap_err();
+    assert_eq!(error, DikaiosError::InsufficientSpace { field: "host_requirements", required: 144, available: 128 });
+    assert_eq!(
+        error.to_string(),
+        "The requested subnets need 144 addresses but the parent block only holds 128; 16 more are needed"
+    );
+
+    let json = serde_json::to_value(&error).unwrap();
+    assert_eq!((json["code"].as_str(), json["missing"].as_str()), (Some("insufficient_space"), Some("16")));
+
+    let vlans = [vlan(10, "Sales", 50, &[]), vlan(99, "Management", 5, &[])];
+    assert_eq!(
+        calculate_vlans("10.0.0.0", 26, &vlans, &[]).unwrap_err(),
+        DikaiosError::InsufficientSpace { field: "vlan_configs", required: 72, available: 64 }
+    );
+}
+
+#[test]
+fn missing_addresses_never_underflow() {
+    let error = DikaiosError::InsufficientSpace { field: "host_requirements", required: 1, available: u128::MAX };
+    assert!(error.to_string().ends_with("; 0 more are needed"));
+    assert_eq!(serde_json::to_value(&error).unwrap()["missing"], "0");
+}
+
+#[test]
+fn unused_space_is_summarized_after_the_last_subnet() {
+    let plan = calculate_vlans("192.168.10.0", 24, &office_vlans(), &[]).unwrap();
+    assert_eq!(plan.unused.blocks, ["192.168.10.104/29", "192.168.10.112/28", "192.168.10.128/25"]);
+    assert_eq!(plan.unused.address_count, "152");
+}
+
+#[test]
+fn full_plans_leave_no_unused_space() {
+    // The last subnet ends on 255.255.255.255
+    let edge = [HostRequirement::new("Edge", 2)];
+    let plan = calculate_vlsm_subnets("255.255.255.252", 30, &edge, &VlsmOptions::default()).unwrap();
+    assert!(plan.unused.blocks.is_empty());
+    assert_eq!(plan.unused.address_count, "0");
+
+    let plan = calculate_ipv6_vlsm_subnets("::", 0, &[Ipv6Requirement::new("All", Ipv6Size::PrefixLength(0))]).unwrap();
+    assert!(plan.unused.blocks.is_empty());
+    assert_eq!(plan.unused.address_count, "0");
+
+    let plan =
+        calculate_ipv6_vlsm_subnets("2001:db8::", 63, &[Ipv6Requirement::new("LAN", Ipv6Size::Subnets64(1))]).unwrap();
+    assert_eq!(plan.unused.blocks, ["2001:db8:0:1::/64"]);
+    assert_eq!(plan.unused.address_count, "18446744073709551616");
+}
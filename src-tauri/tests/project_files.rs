@@ -0,0 +1,148 @@
+//! Saving address plans to project files and opening them again.
+
+mod common;
+
+use common::{vlan, vlan_input, wireless_input};
+use dikaios_lib::calc::{
+    calculate_vlan_plan, calculate_vlsm_plan, generate_wireless_config, project_from_str, project_to_string,
+    load_project, save_project, DikaiosError, Growth, HostRequirement, MaskInput, Project, ProjectFormat,
+    SavedVlanPlan, SavedVlsm, SavedWirelessConfig, VlsmInput, VlsmOptions, WirelessNetworkConfig,
+    WirelessNetworkInput, PROJECT_VERSION,
+};
+
+fn project() -> Project {
+    let vlsm_input = VlsmInput {
+        base_network: "192.168.1.0".to_string(),
+        base_cidr: MaskInput::from("24".to_string()),
+        host_requirements: vec![
+            HostRequirement::new("Sales LAN", 50).with_growth(Growth::percent(20)),
+            HostRequirement::new("WAN link", 2),
+        ],
+        ipv6_requirements: Vec::new(),
+        options: VlsmOptions::default(),
+    };
+    let vlsm = calculate_vlsm_plan(&vlsm_input).unwrap();
+
+    let vlan_input = vlan_input("10.0.0.0", "255.255.0.0", vec![vlan(10, "Sales", 20, &["Gi1/0/1"])]);
+    let vlans = calculate_vlan_plan(&vlan_input).unwrap();
+
+    let mut project = Project::new("Branch office");
+    project.vlsm.push(SavedVlsm { name: "LANs".to_string(), input: vlsm_input, subnets: vlsm.subnets, unused: vlsm.unused });
+    project.vlans.push(SavedVlanPlan {
+        name: "Access".to_string(),
+        commands: vlans.render(vlan_input.platform),
+        input: vlan_input,
+        allocations: vlans.allocations,
+        trunks: vlans.trunks,
+        unused: vlans.unused,
+    });
+    let guest = SavedWirelessConfig { name: "Guest".to_string(), config: wireless(true), keep_secrets: false };
+    project.wireless.push(guest);
+    project
+}
+
+fn wireless(mask_passphrase: bool) -> WirelessNetworkConfig {
+    generate_wireless_config(WirelessNetworkInput { mask_passphrase, ..wireless_input() }).unwrap()
+}
+
+#[test]
+fn json_and_toml_round_trip() {
+    let project = project();
+    for format in ProjectFormat::ALL {
+        let text = project_to_string(&project, format).unwrap();
+        let opened = project_from_str(&text, format).unwrap();
+
+        assert_eq!(opened.version, PROJECT_VERSION);
+        assert_eq!(opened.name, "Branch office");
+        assert_eq!(opened.vlsm[0].input.host_requirements, project.vlsm[0].input.host_requirements);
+        assert_eq!(opened.vlsm[0].input.base_cidr, MaskInput::from("24".to_string()));
+        assert_eq!(opened.vlsm[0].subnets[0].network, "192.168.1.0");
+        assert_eq!(opened.vlsm[0].unused, project.vlsm[0].unused);
+        assert_eq!(opened.vlans[0].allocations[0].vlan_id, 10);
+        assert_eq!(opened.vlans[0].commands, project.vlans[0].commands);
+        assert_eq!(opened.wireless[0].config.router_commands, project.wireless[0].config.router_commands);
+
+        // Saving the opened project again gives the same file
+        assert_eq!(project_to_string(&opened, format).unwrap(), text, "{}", format);
+    }
+}
+
+#[test]
+fn the_extension_picks_the_format() {
+    let dir = std::env::temp_dir().join(format!("dikaios-project-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let project = project();
+
+    for name in ["plan.toml", "plan.json", "plan.dikaios"] {
+        let path = dir.join(name);
+        save_project(&project, &path).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(text.starts_with('{'), !name.ends_with(".toml"), "{}", name);
+        assert_eq!(load_project(&path).unwrap().vlsm.len(), 1);
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    match load_project(dir.join("missing.json")) {
+        Err(DikaiosError::Io { field, .. }) => assert_eq!(field, "path"),
+        other => panic!("expected an IO error, got {:?}", other),
+    }
+}
+
+#[test]
+fn only_known_versions_open() {
+    let project = project_from_str("{\"version\": 1, \"name\": \"Empty\"}", ProjectFormat::Json).unwrap();
+    assert_eq!((project.version, project.name.as_str()), (PROJECT_VERSION, "Empty"));
+    assert!(project.vlsm.is_empty() && project.vlans.is_empty() && project.wireless.is_empty());
+
+    let newer = format!("version = {}\nname = \"Future\"\n", PROJECT_VERSION + 1);
+    for (text, format) in [
+        (newer.as_str(), ProjectFormat::Toml),
+        ("{\"name\": \"Unversioned\"}", ProjectFormat::Json),
+        ("{\"version\": 0, \"name\": \"Zero\"}", ProjectFormat::Json),
+        ("{\"version\": \"1\", \"name\": \"Text\"}", ProjectFormat::Json),
+        ("[]", ProjectFormat::Json),
+    ] {
+        match project_from_str(text, format) {
+            Err(DikaiosError::InvalidInput { field, .. }) => assert_eq!(field, "project"),
+            other => panic!("expected {:?} to be rejected, got {:?}", text, other),
+        }
+    }
+}
+
+#[test]
+fn passphrases_are_only_saved_with_consent() {
+    let mut project = Project::new("Lab");
+    let lab = SavedWirelessConfig { name: "Lab".to_string(), config: wireless(false), keep_secrets: false };
+    project.wireless.push(lab);
+
+    for format in ProjectFormat::ALL {
+        match project_to_string(&project, format) {
+            Err(DikaiosError::InvalidInput { field: "wireless", message }) => assert!(message.contains("Office")),
+            other => panic!("expected the passphrase to need consent, got {:?}", other),
+        }
+    }
+
+    project.wireless[0].keep_secrets = true;
+    let text = project_to_string(&project, ProjectFormat::Toml).unwrap();
+    assert!(text.contains("wpa_passphrase=correct horse"));
+    assert!(project_from_str(&text, ProjectFormat::Toml).unwrap().wireless[0].keep_secrets);
+}
+
+#[test]
+fn version_1_files_keep_the_secrets_they_hold() {
+    let mut project = project();
+    project.wireless.push(SavedWirelessConfig { name: "Lab".to_string(), config: wireless(false), keep_secrets: true });
+    let mut value: serde_json::Value =
+        serde_json::from_str(&project_to_string(&project, ProjectFormat::Json).unwrap()).unwrap();
+    value["version"] = 1.into();
+    for saved in value["wireless"].as_array_mut().unwrap() {
+        saved.as_object_mut().unwrap().remove("keep_secrets");
+    }
+    value["wireless"][1]["config"].as_object_mut().unwrap().remove("masked");
+
+    let opened = project_from_str(&value.to_string(), ProjectFormat::Json).unwrap();
+    assert_eq!(opened.version, PROJECT_VERSION);
+    let kept: Vec<bool> = opened.wireless.iter().map(|saved| saved.keep_secrets).collect();
+    assert_eq!(kept, [false, true]);
+    assert!(project_to_string(&opened, ProjectFormat::Json).is_ok());
+}
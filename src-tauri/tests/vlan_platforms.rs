@@ -0,0 +1,187 @@
+//! Golden-file tests for the VLAN configuration of every platform.
+//!
+//! Each platform's output for the same plan is compared with
+//! `tests/golden/vlan/<platform>.txt`. Run with `UPDATE_GOLDEN=1` to rewrite
+//! the files after an intended change and review the diff.
+
+mod common;
+
+use common::{office_vlans, vlan, vlan_input};
+use dikaios_lib::calc::{
+    calculate_vlan_plan, calculate_vlans, configure_dhcp, configure_redundancy, dhcp_server_config, DhcpMode,
+    DhcpOptions, DhcpServer, DikaiosError, FhrpProtocol, Platform, RedundancyOptions, TrunkPort, VlanInput, VlanPlan,
+};
+use std::fs;
+use std::path::PathBuf;
+
+fn plan() -> VlanPlan {
+    let trunks = [
+        TrunkPort { interface: "Gi1/0/48".to_string(), allowed_vlans: Vec::new(), native_vlan: Some(99) },
+        TrunkPort { interface: "Gi1/0/47".to_string(), allowed_vlans: vec![20, 10], native_vlan: None },
+    ];
+    calculate_vlans("192.168.10.0", 24, &office_vlans(), &trunks).unwrap()
+}
+
+fn golden_path(platform: Platform) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden/vlan")
+        .join(format!("{}.txt", platform.id()))
+}
+
+#[test]
+fn every_platform_matches_its_golden_file() {
+    let plan = plan();
+
+    for platform in Platform::ALL {
+        let rendered = plan.render(platform);
+        let path = golden_path(platform);
+
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            fs::write(&path, &rendered).unwrap();
+            continue;
+        }
+
+        let expected = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("cannot read {}: {}", path.display(), e));
+        assert_eq!(rendered, expected, "{} output differs from {}", platform, path.display());
+    }
+}
+
+#[test]
+fn platforms_parse_from_their_ids() {
+    for platform in Platform::ALL {
+        assert_eq!(platform.id().parse::<Platform>().unwrap(), platform);
+    }
+    assert_eq!("arista-eos".parse::<Platform>().unwrap(), Platform::AristaEos);
+    assert!("ios-xr".parse::<Platform>().is_err());
+}
+
+#[test]
+fn trunks_resolve_their_vlans_and_warn_about_gaps() {
+    let plan = plan();
+    assert_eq!(plan.trunks[0].allowed_vlans, [10, 20, 99]);
+    assert_eq!(plan.trunks[1].allowed_vlans, [10, 20]);
+    assert!(plan.warnings.is_empty());
+
+    let vlans = [vlan(10, "Sales", 50, &["Gi1/0/1-12"]), vlan(20, "Voice", 20, &["Gi1/0/13"])];
+    let trunks = [TrunkPort { interface: "Gi1/0/48".to_string(), allowed_vlans: vec![10, 30], native_vlan: None }];
+    let plan = calculate_vlans("192.168.10.0", 24, &vlans, &trunks).unwrap();
+    let messages: Vec<&str> = plan.warnings.iter().map(|warning| warning.message.as_str()).collect();
+    assert_eq!(messages, [
+        "Trunk Gi1/0/48 carries VLAN 30, which is not part of the plan",
+        "VLAN 20 (Voice) has access ports but no trunk carries it",
+    ]);
+}
+
+#[test]
+fn dhcp_pools_helpers_and_server_config() {
+    let mut plan = plan();
+    let options = DhcpOptions {
+        vlans: vec![10, 20],
+        dns_servers: vec!["192.168.10.98".to_string()],
+        domain_name: Some("example.com".to_string()),
+        lease_time: 2 * 86_400 + 3600,
+        excluded_addresses: 9,
+        ..DhcpOptions::default()
+    };
+    configure_dhcp(&mut plan, &options).unwrap();
+
+    let sales = plan.allocation(10).unwrap().dhcp.as_ref().unwrap();
+    assert_eq!((sales.pool_start.as_str(), sales.pool_end.as_str()), ("192.168.10.11", "192.168.10.62"));
+    assert!(plan.allocation(99).unwrap().dhcp.is_none());
+
+    let commands = plan.render(Platform::CiscoIos);
+    assert!(commands.contains("ip dhcp excluded-address 192.168.10.1 192.168.10.10\n"));
+    assert!(commands.contains(
+        "ip dhcp pool VLAN20\nnetwork 192.168.10.64 255.255.255.224\ndefault-router 192.168.10.65\n\
+         dns-server 192.168.10.98\ndomain-name example.com\nlease 2 1 0\nexit\n"
+    ));
+    assert!(!commands.contains("ip helper-address"));
+
+    let dhcpd = dhcp_server_config(&plan.allocations, DhcpServer::IscDhcpd);
+    assert!(dhcpd.contains("subnet 192.168.10.0 netmask 255.255.255.192 {\n  range 192.168.10.11 192.168.10.62;\n"));
+    let kea: serde_json::Value = serde_json::from_str(&dhcp_server_config(&plan.allocations, DhcpServer::Kea)).unwrap();
+    assert_eq!(kea["Dhcp4"]["subnet4"][1]["pools"][0]["pool"], "192.168.10.75 - 192.168.10.94");
+
+    plan.dhcp.as_mut().unwrap().mode = DhcpMode::Relay;
+    plan.dhcp.as_mut().unwrap().server = Some("10.0.0.53".to_string());
+    for platform in [Platform::CiscoIos, Platform::AristaEos] {
+        let commands = plan.render(platform);
+        assert_eq!(commands.matches("ip helper-address 10.0.0.53").count(), 2, "{}", platform);
+        assert!(!commands.contains("ip dhcp pool"));
+    }
+}
+
+#[test]
+fn redundant_pair_shares_a_virtual_gateway() {
+    let input = VlanInput {
+        dhcp: Some(DhcpOptions::default()),
+        redundancy: Some(RedundancyOptions::new(FhrpProtocol::Hsrp)),
+        ..vlan_input("192.168.10.0", "24", vec![vlan(10, "Sales", 61, &[]), vlan(20, "Voice", 1, &[])])
+    };
+    let plan = calculate_vlan_plan(&input).unwrap();
+
+    // 61 hosts, the virtual gateway among them, and two router addresses no
+    // longer fit in a /26
+    let sales = plan.allocation(10).unwrap();
+    assert_eq!((sales.cidr, sales.gateway.as_str(), sales.first_host.as_str()), (25, "192.168.10.1", "192.168.10.4"));
+    let gateway = sales.redundancy.as_ref().unwrap();
+    assert_eq!(gateway.router_addresses, ["192.168.10.2", "192.168.10.3"]);
+    assert_eq!((gateway.priorities, gateway.active_router), ([110, 100], 0));
+    assert_eq!(sales.dhcp.as_ref().unwrap().pool_start, "192.168.10.4");
+
+    let voice = plan.allocation(20).unwrap();
+    assert_eq!((voice.cidr, voice.redundancy.as_ref().unwrap().priorities), (29, [100, 110]));
+
+    let commands = plan.render(Platform::CiscoIos);
+    let (first, second) = commands.split_once("! Router SW2\n").unwrap();
+    assert!(first.starts_with("! Router SW1\n"));
+    assert!(first.contains(
+        "ip address 192.168.10.2 255.255.255.128\nstandby version 2\nstandby 10 ip 192.168.10.1\n\
+         standby 10 priority 110\nstandby 10 preempt\n"
+    ));
+    assert!(second.contains("ip address 192.168.10.131 255.255.255.248\nstandby version 2\nstandby 20 ip 192.168.10.129\n\
+         standby 20 priority 110\n"));
+    assert!(first.contains("ip dhcp pool VLAN10") && !second.contains("ip dhcp pool"));
+
+    let vyos = plan.render(Platform::Vyos);
+    assert!(vyos.contains("set high-availability vrrp group VLAN10 address '192.168.10.1/25'\n"));
+}
+
+#[test]
+fn redundant_vlans_fit_the_gateway_and_both_routers() {
+    let input = VlanInput {
+        redundancy: Some(RedundancyOptions::new(FhrpProtocol::Vrrp)),
+        ..vlan_input("10.0.0.0", "24", vec![vlan(10, "Transit", 0, &[]), vlan(20, "Printers", 4, &[])])
+    };
+    let plan = calculate_vlan_plan(&input).unwrap();
+
+    // The gateway, three more hosts and the routers fill a /29 exactly
+    let printers = plan.allocation(20).unwrap();
+    assert_eq!((printers.cidr, printers.usable_hosts), (29, 4));
+    assert_eq!((printers.first_host.as_str(), printers.last_host.as_str()), ("10.0.0.4", "10.0.0.6"));
+    let transit = plan.allocation(10).unwrap();
+    assert_eq!((transit.cidr, transit.gateway.as_str()), (29, "10.0.0.9"));
+    assert_eq!(transit.redundancy.as_ref().unwrap().router_addresses, ["10.0.0.10", "10.0.0.11"]);
+
+    // A /30 has no room for the second router
+    let mut unsized_plan = calculate_vlans("10.0.0.0", 24, &[vlan(10, "Transit", 2, &[])], &[]).unwrap();
+    assert_eq!(unsized_plan.allocations[0].cidr, 30);
+    assert!(matches!(
+        configure_redundancy(&mut unsized_plan, &RedundancyOptions::new(FhrpProtocol::Vrrp)),
+        Err(DikaiosError::AllocationFailed { field: "redundancy", .. })
+    ));
+}
+
+#[test]
+fn usable_hosts_leave_out_the_router_addresses() {
+    let mut input = vlan_input("192.168.10.0", "24", vec![vlan(10, "Sales", 50, &[]), vlan(20, "Voice", 61, &[])]);
+    let usable = |plan: &VlanPlan| [10, 20].map(|vlan_id| {
+        let allocation = plan.allocation(vlan_id).unwrap();
+        (allocation.cidr, allocation.usable_hosts)
+    });
+
+    assert_eq!(usable(&calculate_vlan_plan(&input).unwrap()), [(26, 62), (26, 62)]);
+    input.redundancy = Some(RedundancyOptions::new(FhrpProtocol::Glbp));
+    assert_eq!(usable(&calculate_vlan_plan(&input).unwrap()), [(26, 60), (25, 124)]);
+}
@@ -0,0 +1,85 @@
+//! The validation pass that runs before a VLAN plan is allocated.
+
+mod common;
+
+use common::{vlan, vlan_input};
+use dikaios_lib::calc::{
+    calculate_vlan_plan, validate_vlans, DikaiosError, FhrpProtocol, Platform, RedundancyOptions, Severity, TrunkPort,
+    VlanInput,
+};
+
+fn input(vlans: &[(u32, &str)], platform: Platform) -> VlanInput {
+    let vlan_configs = vlans.iter().map(|&(vlan_id, vlan_name)| vlan(vlan_id, vlan_name, 10, &[])).collect();
+    VlanInput { platform, ..vlan_input("10.0.0.0", "16", vlan_configs) }
+}
+
+#[test]
+fn reports_every_problem_at_once() {
+    let input = input(
+        &[(0, "Zero"), (10, "Sales"), (10, "Sales Floor"), (1003, "Legacy"), (5000, "sales")],
+        Platform::CiscoIos,
+    );
+    let diagnostics = validate_vlans(&input);
+
+    let found: Vec<(Severity, Option<u32>, &str)> = diagnostics.iter()
+        .map(|diagnostic| (diagnostic.severity, diagnostic.vlan_id, diagnostic.message.as_str()))
+        .collect();
+    assert_eq!(found, [
+        (Severity::Error, Some(0), "VLAN 0 (Zero) is outside the VLAN ID range 1-4094"),
+        (Severity::Error, Some(10), "VLAN ID 10 is used by both Sales and Sales Floor"),
+        (Severity::Error, Some(10), "VLAN 10 name \"Sales Floor\" contains spaces, which break the generated name commands"),
+        (Severity::Error, Some(1003), "VLAN 1003 (Legacy) is reserved for FDDI and Token Ring on Cisco switches"),
+        (Severity::Error, Some(5000), "VLAN 5000 (sales) is outside the VLAN ID range 1-4094"),
+        (Severity::Error, Some(5000), "VLAN 5000 has the same name as VLAN 10: sales"),
+    ]);
+
+    // Every suggested ID is free and distinct
+    assert_eq!(diagnostics[0].suggestion.as_deref(), Some("Use a free ID such as 2"));
+    assert_eq!(diagnostics[1].suggestion.as_deref(), Some("Use a free ID such as 3"));
+    assert_eq!(diagnostics[2].suggestion.as_deref(), Some("Rename it to Sales-Floor"));
+
+    match calculate_vlan_plan(&input) {
+        Err(DikaiosError::ValidationFailed { field, diagnostics: reported }) => {
+            assert_eq!(field, "vlan_configs");
+            assert_eq!(reported, diagnostics);
+        }
+        other => panic!("expected a validation failure, got {:?}", other),
+    }
+}
+
+#[test]
+fn warnings_do_not_stop_the_plan() {
+    let mut input = input(&[(1, "Default"), (1002, "Legacy")], Platform::JuniperJunos);
+    input.trunks.push(TrunkPort { interface: "ge-0/0/47".to_string(), allowed_vlans: vec![1], native_vlan: None });
+
+    let plan = calculate_vlan_plan(&input).unwrap();
+    assert_eq!(plan.allocations.len(), 2);
+    assert!(plan.warnings.iter().all(|warning| warning.severity == Severity::Warning));
+    assert_eq!(plan.warnings.iter().map(|warning| warning.vlan_id).collect::<Vec<_>>(), [Some(1), Some(1002)]);
+}
+
+#[test]
+fn trunks_are_checked_too() {
+    let mut input = input(&[(10, "Sales")], Platform::CiscoIos);
+    input.vlan_configs[0].access_ports.push("Gi1/0/48".to_string());
+    input.trunks.push(TrunkPort { interface: "Gi1/0/48".to_string(), allowed_vlans: vec![10, 4095], native_vlan: None });
+
+    let messages: Vec<String> = validate_vlans(&input).into_iter().map(|diagnostic| diagnostic.message).collect();
+    assert_eq!(messages, [
+        "Gi1/0/48 is both a trunk and an access port of VLAN 10",
+        "Trunk Gi1/0/48 refers to VLAN 4095, outside the VLAN ID range 1-4094",
+    ]);
+}
+
+#[test]
+fn redundancy_protocols_must_suit_the_platform() {
+    let mut input = input(&[(10, "Sales")], Platform::JuniperJunos);
+    input.redundancy = Some(RedundancyOptions::new(FhrpProtocol::Hsrp));
+
+    let diagnostics = validate_vlans(&input);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, "Juniper Junos does not support HSRP");
+
+    input.redundancy = Some(RedundancyOptions::new(FhrpProtocol::Vrrp));
+    assert!(validate_vlans(&input).is_empty());
+}
@@ -0,0 +1,95 @@
+//! How the IPv4 VLSM allocator sizes subnets: growth headroom, reserved
+//! addresses, point-to-point links and plans that do not fit.
+
+use dikaios_lib::calc::{calculate_vlsm_subnets, DikaiosError, Growth, HostRequirement, VlsmOptions};
+
+#[test]
+fn growth_is_parsed_from_text_and_numbers() {
+    for (text, percent) in [("", 0), ("none", 0), ("double", 100), ("Triple", 200), ("30%", 30), ("+30%", 30), ("45", 45)] {
+        assert_eq!(text.parse::<Growth>().unwrap(), Growth::percent(percent), "{:?}", text);
+    }
+    assert!(matches!("a lot".parse::<Growth>(), Err(DikaiosError::InvalidInput { field: "growth", .. })));
+
+    assert_eq!(serde_json::from_str::<Growth>("\"+50%\"").unwrap(), Growth::percent(50));
+    assert_eq!(serde_json::from_str::<Growth>("25").unwrap(), Growth::percent(25));
+
+    let requirement: HostRequirement = "Sales LAN: 50+double".parse().unwrap();
+    assert_eq!(requirement, HostRequirement::new("Sales LAN", 50).with_growth(Growth::DOUBLE));
+    assert_eq!(Growth::percent(30).apply(10), 13);
+}
+
+#[test]
+fn growth_and_reserved_addresses_size_the_subnet() {
+    let options = VlsmOptions { growth: Growth::percent(50), reserved: 3, point_to_point: false };
+    let requirements = [HostRequirement::new("Users", 39), HostRequirement::new("Voice", 8).with_reserved(0)];
+    let plan = calculate_vlsm_subnets("10.0.0.0", 24, &requirements, &options).unwrap();
+
+    let users = &plan.subnets[0];
+    assert_eq!((users.cidr, users.planned_hosts, users.reserved_hosts), (26, Some(62), Some(3)));
+    let voice = &plan.subnets[1];
+    assert_eq!((voice.network.as_str(), voice.cidr, voice.planned_hosts), ("10.0.0.64", 28, Some(12)));
+}
+
+#[test]
+fn reserved_addresses_are_not_handed_out_as_hosts() {
+    let options = VlsmOptions { reserved: 3, ..VlsmOptions::default() };
+    let plan = calculate_vlsm_subnets("192.168.10.0", 24, &[HostRequirement::new("Servers", 20)], &options).unwrap();
+
+    let servers = &plan.subnets[0];
+    assert_eq!((servers.network.as_str(), servers.cidr), ("192.168.10.0", 27));
+    assert_eq!((servers.first_host.as_str(), servers.last_host.as_str()), ("192.168.10.4", "192.168.10.30"));
+    assert_eq!(servers.usable_hosts, Some(27));
+}
+
+#[test]
+fn point_to_point_links_get_a_31_and_loopbacks_a_32() {
+    let options = VlsmOptions { point_to_point: true, ..VlsmOptions::default() };
+    let requirements = [HostRequirement::new("Loopback", 1), HostRequirement::new("WAN", 2), HostRequirement::new("LAN", 3)];
+    let plan = calculate_vlsm_subnets("10.255.0.0", 28, &requirements, &options).unwrap();
+
+    let sizes: Vec<(&str, &str, u32)> =
+        plan.subnets.iter().map(|subnet| (subnet.name.as_str(), subnet.network.as_str(), subnet.cidr)).collect();
+    assert_eq!(sizes, [("LAN", "10.255.0.0", 29), ("WAN", "10.255.0.8", 31), ("Loopback", "10.255.0.10", 32)]);
+
+    let wan = &plan.subnets[1];
+    assert_eq!((wan.first_host.as_str(), wan.last_host.as_str()), ("10.255.0.8", "10.255.0.9"));
+    assert_eq!((wan.broadcast.as_deref(), wan.usable_hosts), (None, Some(2)));
+    let loopback = &plan.subnets[2];
+    assert_eq!((loopback.first_host.as_str(), loopback.usable_hosts), ("10.255.0.10", Some(1)));
+    assert_eq!(plan.unused.blocks, ["10.255.0.11/32", "10.255.0.12/30"]);
+
+    let rounded = calculate_vlsm_subnets("10.255.0.0", 29, &requirements[..2], &VlsmOptions::default()).unwrap();
+    assert!(rounded.subnets.iter().all(|subnet| subnet.cidr == 30));
+}
+
+#[test]
+fn reserved_addresses_must_leave_a_host() {
+    let options = VlsmOptions { point_to_point: true, ..VlsmOptions::default() };
+    let link = [HostRequirement::new("Spare link", 0).with_reserved(2)];
+    match calculate_vlsm_subnets("10.255.0.0", 30, &link, &options) {
+        Err(DikaiosError::AllocationFailed { field: "host_requirements", message }) => {
+            assert_eq!(message, "Spare link reserves every usable address of its /31 subnet, leaving none for hosts")
+        }
+        other => panic!("expected the /31 to be rejected, got {:?}", other),
+    }
+
+    let top = [HostRequirement::new("", 0).with_reserved(1)];
+    match calculate_vlsm_subnets("255.255.255.255", 32, &top, &options) {
+        Err(DikaiosError::AllocationFailed { field: "host_requirements", message }) => {
+            assert_eq!(message, "Subnet 1 reserves every usable address of its /32 subnet, leaving none for hosts")
+        }
+        other => panic!("expected the /32 to be rejected, got {:?}", other),
+    }
+}
+
+#[test]
+fn oversized_host_counts_fail_instead_of_overflowing() {
+    let greedy = Growth::percent(u32::MAX);
+    assert!(greedy.apply(u32::MAX) > u32::MAX as u64);
+
+    let requirements = [HostRequirement::new("Everyone", u32::MAX).with_growth(greedy).with_reserved(u32::MAX)];
+    assert!(matches!(
+        calculate_vlsm_subnets("0.0.0.0", 0, &requirements, &VlsmOptions::default()),
+        Err(DikaiosError::AllocationFailed { field: "host_requirements", .. })
+    ));
+}
@@ -0,0 +1,195 @@
+//! The wireless setup: the Packet Tracer commands, the Linux access point
+//! files written next to them, and how passphrases and secrets appear in both.
+
+mod common;
+
+use common::wireless_input;
+use dikaios_lib::calc::{
+    cisco_salt, cisco_secret_hash, generate_wireless_config, is_valid_wpa_key, validate_wireless, wpa_psk,
+    CiscoSecretType, DikaiosError, MaskInput, WirelessNetworkInput, WirelessSecurity,
+};
+
+/// An access point on its own 5 GHz network.
+fn access_point() -> WirelessNetworkInput {
+    WirelessNetworkInput {
+        base_network: Some("10.20.30.0".to_string()),
+        base_cidr: Some(MaskInput::from("24".to_string())),
+        auto_generate: false,
+        country_code: "de".to_string(),
+        channel: 36,
+        interface: "wlan1".to_string(),
+        ..wireless_input()
+    }
+}
+
+/// The IEEE 802.11i test network, masked and with a type 9 enable secret.
+fn ieee() -> WirelessNetworkInput {
+    WirelessNetworkInput {
+        ssid: "IEEE".to_string(),
+        wpa_password: "password".to_string(),
+        mask_passphrase: true,
+        enable_secret: Some("cisco".to_string()),
+        secret_type: CiscoSecretType::Type9,
+        ..wireless_input()
+    }
+}
+
+/// The IEEE 802.11i test vector for the passphrase "password" on "IEEE".
+const IEEE_PSK: &str = "f42c6fc52df0ebef9ebb4b90b38a5f902e83fe1b135a70e23aed762e9710a12e";
+
+#[test]
+fn hostapd_and_dhcp_serve_the_cisco_pool() {
+    let config = generate_wireless_config(access_point()).unwrap();
+
+    assert!(config.hostapd_conf.contains("interface=wlan1\n"));
+    assert!(config.hostapd_conf.contains("ssid=Office\n"));
+    assert!(config.hostapd_conf.contains("country_code=DE\n"));
+    assert!(config.hostapd_conf.contains("hw_mode=a\nchannel=36\n"));
+    assert!(config.hostapd_conf.contains("wpa_key_mgmt=WPA-PSK\nwpa_passphrase=correct horse\n"));
+
+    assert!(config.dnsmasq_conf.contains("dhcp-range=10.20.30.10,10.20.30.254,255.255.255.0,86400\n"));
+    assert!(config.dnsmasq_conf.contains("dhcp-option=option:router,10.20.30.1\n"));
+
+    let kea: serde_json::Value = serde_json::from_str(&config.kea_config).unwrap();
+    let subnet = &kea["Dhcp4"]["subnet4"][0];
+    assert_eq!(subnet["subnet"], "10.20.30.0/24");
+    assert_eq!(subnet["pools"][0]["pool"], "10.20.30.10 - 10.20.30.254");
+
+    // The router's own pool leaves out the same addresses
+    assert!(config.router_commands.contains("ip dhcp excluded-address 10.20.30.1 10.20.30.9"));
+    assert!(config.warnings.is_empty());
+}
+
+#[test]
+fn wpa3_uses_sae_with_protected_management_frames() {
+    let mut input = access_point();
+    input.security = WirelessSecurity::Wpa2Wpa3;
+    let config = generate_wireless_config(input).unwrap();
+
+    assert!(config.hostapd_conf.contains("wpa_key_mgmt=WPA-PSK SAE\nieee80211w=1\n"));
+    assert!(config.hostapd_conf.contains("sae_password=correct horse\n"));
+    assert!(config.router_commands.contains("! Security: WPA2-PSK/WPA3-SAE"));
+}
+
+#[test]
+fn every_output_is_checked_against_the_same_rules() {
+    let mut input = access_point();
+    input.ssid = "A".repeat(33);
+    input.wpa_password = "short".to_string();
+    input.country_code = "Germany".to_string();
+    input.channel = 37;
+    input.ap_hostname = "Access Point".to_string();
+
+    let diagnostics = validate_wireless(&input);
+    let fields: Vec<&str> = diagnostics.iter().map(|diagnostic| diagnostic.field).collect();
+    assert_eq!(fields, ["ssid", "wpa_password", "ap_hostname", "country_code", "channel"]);
+    assert_eq!(diagnostics[2].suggestion.as_deref(), Some("Rename it to Access-Point"));
+
+    match generate_wireless_config(input) {
+        Err(DikaiosError::ValidationFailed { field, diagnostics: reported }) => {
+            assert_eq!(field, "wireless");
+            assert_eq!(reported, diagnostics);
+        }
+        other => panic!("expected a validation failure, got {:?}", other),
+    }
+}
+
+#[test]
+fn channels_outside_the_regulatory_domain_are_flagged() {
+    let mut input = access_point();
+    input.country_code = "US".to_string();
+    input.channel = 13;
+    let config = generate_wireless_config(input.clone()).unwrap();
+    assert_eq!(config.warnings.len(), 1);
+    assert!(config.hostapd_conf.contains("hw_mode=g\nchannel=13\n"));
+
+    input.channel = 14;
+    assert!(validate_wireless(&input)[0].is_error());
+}
+
+#[test]
+fn psk_is_derived_from_passphrase_and_ssid() {
+    assert_eq!(wpa_psk("password", "IEEE"), IEEE_PSK);
+    assert_eq!(wpa_psk(&IEEE_PSK.to_ascii_uppercase(), "Other"), IEEE_PSK);
+}
+
+#[test]
+fn passphrases_follow_the_wpa_rules() {
+    assert!(is_valid_wpa_key("eight ch"));
+    assert!(is_valid_wpa_key(&"~".repeat(63)));
+    assert!(is_valid_wpa_key(IEEE_PSK));
+    assert!(!is_valid_wpa_key("seven c"));
+    assert!(!is_valid_wpa_key(&"g".repeat(64)));
+    assert!(!is_valid_wpa_key("pässword"));
+
+    let mut input = ieee();
+    input.wpa_password = IEEE_PSK.to_string();
+    input.security = WirelessSecurity::Wpa3;
+    input.mask_passphrase = false;
+    let diagnostics = validate_wireless(&input);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, "WPA3-SAE needs a passphrase, not a hex pre-shared key");
+}
+
+#[test]
+fn cisco_hashes_match_ios() {
+    assert_eq!(
+        cisco_secret_hash("cisco", "dsYGNam3K1SIJO", CiscoSecretType::Type8),
+        "$8$dsYGNam3K1SIJO$7nv/35M/qr6t.dVc7UY9zrJDWRVqncHub1PE9UlMQFs"
+    );
+    assert_eq!(
+        cisco_secret_hash("cisco", "2MJBozw/9R3UsU", CiscoSecretType::Type9),
+        "$9$2MJBozw/9R3UsU$TZqOZVNvD0xD9a40AGfinjoyQ5ousDBbW2ycEHIxuaY"
+    );
+
+    let salt = cisco_salt().unwrap();
+    assert_eq!(salt.len(), 14);
+    assert_ne!(salt, cisco_salt().unwrap());
+}
+
+#[test]
+fn masked_output_never_contains_the_passphrase() {
+    let mut input = ieee();
+    input.wpa_password = "correct horse battery".to_string();
+    input.enable_secret = Some("staple".to_string());
+    let config = generate_wireless_config(input).unwrap();
+
+    let outputs = [&config.router_commands, &config.ap_commands, &config.hostapd_conf, &config.setup_steps.join("\n")];
+    for output in outputs {
+        assert!(!output.contains("correct horse") && !output.contains("staple"), "{}", output);
+    }
+
+    assert!(config.router_commands.contains(&format!("! Password: ********\n! WPA PSK: {}\n", config.psk)));
+    assert!(config.router_commands.contains("hostname R1\nenable secret 9 $9$"));
+    assert!(config.ap_commands.contains("hostname AP1\nenable secret 9 $9$"));
+    assert!(config.hostapd_conf.contains(&format!("wpa_psk={}\n", config.psk)));
+    assert!(!config.hostapd_conf.contains("wpa_passphrase"));
+}
+
+#[test]
+fn masked_output_carries_the_derived_psk() {
+    let config = generate_wireless_config(ieee()).unwrap();
+    assert!(config.masked);
+    assert_eq!(config.psk, IEEE_PSK);
+    assert!(config.ap_commands.contains(&format!("! Password: ********\n! WPA PSK: {}\n", IEEE_PSK)));
+    assert!(config.hostapd_conf.contains(&format!("wpa_psk={}\n", IEEE_PSK)));
+}
+
+#[test]
+fn masked_sae_leaves_the_password_to_fill_in() {
+    let mut mixed = ieee();
+    mixed.security = WirelessSecurity::Wpa2Wpa3;
+    let config = generate_wireless_config(mixed).unwrap();
+
+    assert!(config.hostapd_conf.contains(&format!("wpa_psk={}\n", IEEE_PSK)));
+    assert!(config.hostapd_conf.contains("#sae_password=\n"));
+    assert_eq!(config.warnings.len(), 1);
+    assert_eq!(config.warnings[0].field, "security");
+
+    let mut wpa3 = ieee();
+    wpa3.security = WirelessSecurity::Wpa3;
+    let config = generate_wireless_config(wpa3).unwrap();
+    let note = "# SAE cannot use the pre-shared key, fill in the passphrase\n#sae_password=\n";
+    assert!(config.hostapd_conf.contains(note));
+    assert!(!config.hostapd_conf.contains("wpa_psk") && !config.hostapd_conf.contains("wpa_passphrase"));
+}
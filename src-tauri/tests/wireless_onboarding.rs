@@ -0,0 +1,88 @@
+//! The join QR code and onboarding card of a wireless network.
+
+mod common;
+
+use common::wireless_input;
+use dikaios_lib::calc::{
+    generate_wifi_onboarding, generate_wireless_config, qr_png, wifi_qr_payload, DikaiosError, MaskInput,
+    WirelessNetworkInput, WirelessSecurity,
+};
+
+fn input() -> WirelessNetworkInput {
+    WirelessNetworkInput {
+        ssid: "Guest <Café>".to_string(),
+        wpa_password: "semi;colon:pass".to_string(),
+        ..wireless_input()
+    }
+}
+
+#[test]
+fn payload_escapes_special_characters() {
+    assert_eq!(
+        wifi_qr_payload(r#"My "Net", 2\3"#, WirelessSecurity::Wpa2Wpa3, "a;b:c"),
+        r#"WIFI:T:WPA;S:My \"Net\"\, 2\\3;P:a\;b\:c;;"#
+    );
+    assert_eq!(wifi_qr_payload("Lab", WirelessSecurity::Wpa3, "password"), "WIFI:T:SAE;S:Lab;P:password;;");
+}
+
+#[test]
+fn card_shows_the_qr_code_and_network_details() {
+    let input = input();
+    let onboarding = generate_wifi_onboarding(&input).unwrap();
+
+    assert_eq!(onboarding.payload, r"WIFI:T:WPA;S:Guest <Café>;P:semi\;colon\:pass;;");
+    assert!(onboarding.qr_svg.starts_with("<?xml"));
+    assert!(onboarding.qr_svg.contains("<svg"));
+
+    let card = &onboarding.card_html;
+    assert!(card.contains("<h1>Guest &lt;Café&gt;</h1>"));
+    assert!(card.contains("<dt>Password</dt><dd>semi;colon:pass</dd>"));
+    assert!(card.contains("<dt>Gateway</dt><dd>192.168.1.1</dd>"));
+    assert!(card.contains("<dt>Addresses</dt><dd>192.168.1.10 - 192.168.1.254 (DHCP)</dd>"));
+    assert!(card.contains("<svg") && !card.contains("<?xml"));
+}
+
+#[test]
+fn masked_card_leaves_the_passphrase_to_the_qr_code() {
+    let mut input = input();
+    input.mask_passphrase = true;
+    let onboarding = generate_wifi_onboarding(&input).unwrap();
+
+    assert!(!onboarding.card_html.contains("semi;colon"));
+    assert!(onboarding.payload.contains(r"P:semi\;colon\:pass;"));
+}
+
+#[test]
+fn card_follows_the_planned_subnet() {
+    let mut input = input();
+    input.auto_generate = false;
+    input.base_network = Some("10.20.30.0".to_string());
+    input.base_cidr = Some(MaskInput::from("255.255.255.0".to_string()));
+    let card = generate_wifi_onboarding(&input).unwrap().card_html;
+    assert!(card.contains("<dt>Network</dt><dd>10.20.30.0/24</dd>"));
+    assert!(card.contains("<dt>Gateway</dt><dd>10.20.30.1</dd>"));
+
+    // Host bits in the given network are dropped
+    input.base_network = Some("10.20.30.77".to_string());
+    let card = generate_wifi_onboarding(&input).unwrap().card_html;
+    assert!(card.contains("<dt>Network</dt><dd>10.20.30.0/24</dd>"));
+    let config = generate_wireless_config(input.clone()).unwrap();
+    assert_eq!((config.network.as_str(), config.cidr), ("10.20.30.0", 24));
+    assert!(config.router_commands.contains("network 10.20.30.0 255.255.255.0\n"));
+
+    input.channel = 15;
+    assert!(matches!(generate_wifi_onboarding(&input), Err(DikaiosError::ValidationFailed { .. })));
+}
+
+#[test]
+fn png_has_a_quiet_zone() {
+    let png = qr_png("WIFI:T:WPA;S:Lab;P:password;;", 2).unwrap();
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+
+    // The IHDR chunk holds the width and height right after the signature
+    let width = u32::from_be_bytes([png[16], png[17], png[18], png[19]]);
+    let height = u32::from_be_bytes([png[20], png[21], png[22], png[23]]);
+    assert_eq!(width, height);
+    // The payload needs a version 3 code, 29 modules wide, plus four on every side
+    assert_eq!(width, (29 + 8) * 2);
+}
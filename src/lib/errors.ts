@@ -0,0 +1,33 @@
+/** A problem found by a validation pass, with a suggested fix. */
+export type Diagnostic = {
+    severity: "error" | "warning";
+    field: string;
+    vlan_id: number | null;
+    message: string;
+    suggestion: string | null;
+};
+
+/** The structured error returned by a rejected Tauri command. */
+export type DikaiosError = {
+    code: string;
+    message: string;
+    field: string;
+    octet: number | null;
+    range: { min: number; max: number } | null;
+    /** How many more addresses a plan needs, as a decimal string. */
+    missing: string | null;
+    /** Every problem a failed validation pass found. */
+    diagnostics: Diagnostic[] | null;
+};
+
+export function isDikaiosError(error: unknown): error is DikaiosError {
+    return typeof error === "object" && error !== null && "code" in error && "message" in error;
+}
+
+/** A message suitable for showing to the user for any rejected `invoke`. */
+export function errorMessage(error: unknown): string {
+    if (isDikaiosError(error) || error instanceof Error) {
+        return error.message;
+    }
+    return String(error);
+}
@@ -0,0 +1,68 @@
+/** A parent block that subnets are allocated from. */
+export type IpamBlock = {
+    id: number;
+    prefix: string;
+    description: string;
+};
+
+/** A subnet allocated from a block; `block_id` is set by the database. */
+export type IpamSubnet = {
+    id: number;
+    block_id: number;
+    prefix: string;
+    name: string;
+    vlan_id: number | null;
+    gateway: string | null;
+    description: string;
+};
+
+/** A range of a block that automatic allocation never hands out. */
+export type IpamReservation = {
+    id: number;
+    block_id: number;
+    first: string;
+    last: string;
+    reason: string;
+};
+
+export type IpamVlan = {
+    vlan_id: number;
+    name: string;
+    description: string;
+};
+
+export type AddressStatus = "active" | "reserved" | "dhcp" | "deprecated";
+
+export const ADDRESS_STATUSES: { value: AddressStatus; label: string }[] = [
+    { value: "active", label: "Active" },
+    { value: "reserved", label: "Reserved" },
+    { value: "dhcp", label: "DHCP" },
+    { value: "deprecated", label: "Deprecated" },
+];
+
+/** An address assigned to a host; `subnet_id` is set by the database. */
+export type IpamAssignment = {
+    id: number;
+    subnet_id: number;
+    address: string;
+    hostname: string;
+    mac: string | null;
+    owner: string;
+    status: AddressStatus;
+    notes: string;
+};
+
+export type IpamSearchResults = {
+    blocks: IpamBlock[];
+    subnets: IpamSubnet[];
+    vlans: IpamVlan[];
+    assignments: IpamAssignment[];
+};
+
+/** What `ipam_commit_vlsm` and `ipam_commit_vlan_plan` stored. */
+export type CommittedPlan = {
+    block: IpamBlock;
+    subnets: IpamSubnet[];
+    vlans: IpamVlan[];
+    assignments: IpamAssignment[];
+};
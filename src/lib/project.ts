@@ -0,0 +1,46 @@
+import type { UnusedSpace } from "@/lib/unused";
+
+/**
+ * A saved address plan, as written to a project file by `save_project`.
+ *
+ * Inputs and results are kept exactly as the calculator commands take and
+ * return them, so the pages only need to hand over what they already have.
+ */
+export type Project = {
+    version: number;
+    name: string;
+    vlsm: { name: string; input: unknown; subnets: unknown[]; unused: UnusedSpace }[];
+    vlans: {
+        name: string;
+        input: unknown;
+        allocations: unknown[];
+        trunks: unknown[];
+        commands: string;
+        unused: UnusedSpace;
+    }[];
+    /** `keep_secrets` allows saving a configuration generated without masking its passphrase. */
+    wireless: { name: string; config: unknown; keep_secrets: boolean }[];
+};
+
+/** Where the open project lives between page loads. */
+const STORAGE_KEY = "dikaios.project";
+
+export function emptyProject(): Project {
+    return { version: 2, name: "Untitled", vlsm: [], vlans: [], wireless: [] };
+}
+
+/** The open project, or an empty one if nothing has been added yet. */
+export function currentProject(): Project {
+    const stored = localStorage.getItem(STORAGE_KEY);
+    return stored ? { ...emptyProject(), ...JSON.parse(stored) } : emptyProject();
+}
+
+export function storeProject(project: Project) {
+    localStorage.setItem(STORAGE_KEY, JSON.stringify(project));
+}
+
+/** Appends `entry` to the `section` list of the open project. */
+export function addToProject<K extends "vlsm" | "vlans" | "wireless">(section: K, entry: Project[K][number]) {
+    const project = currentProject();
+    storeProject({ ...project, [section]: [...project[section], entry] });
+}
@@ -10,6 +10,9 @@ const IpRangeCalculator = lazy(() => import("@/pages/IpRangeCalculator"));
 const VlsmCalculator = lazy(() => import("@/pages/VlsmCalculator"));
 const VlanAllocationCalculator = lazy(() => import("@/pages/VlanAllocationCalculator"));
 const WirelessNetworkSetup = lazy(() => import("@/pages/WirelessNetworkSetup"));
+const IpamDatabase = lazy(() => import("@/pages/IpamDatabase"));
+const FreeSpaceReport = lazy(() => import("@/pages/FreeSpaceReport"));
+const ConflictChecker = lazy(() => import("@/pages/ConflictChecker"));
 
 const routes: RouteObject[] = [
     {
@@ -72,6 +75,30 @@ const routes: RouteObject[] = [
                     </Suspense>
                 ),
             },
+            {
+                path: "/ipam-database",
+                element: (
+                    <Suspense fallback={<SuspenseLayout />}>
+                        <IpamDatabase />
+                    </Suspense>
+                ),
+            },
+            {
+                path: "/free-space-report",
+                element: (
+                    <Suspense fallback={<SuspenseLayout />}>
+                        <FreeSpaceReport />
+                    </Suspense>
+                ),
+            },
+            {
+                path: "/conflict-checker",
+                element: (
+                    <Suspense fallback={<SuspenseLayout />}>
+                        <ConflictChecker />
+                    </Suspense>
+                ),
+            },
         ],
     },
 ];
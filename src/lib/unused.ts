@@ -0,0 +1,13 @@
+/** The part of a parent block left free after a VLSM or VLAN plan. */
+export type UnusedSpace = {
+    blocks: string[];
+    address_count: string;
+};
+
+/** A one-line summary such as "160 addresses in 10.0.0.96/27, 10.0.0.128/25". */
+export function describeUnused(unused: UnusedSpace): string {
+    if (unused.blocks.length === 0) {
+        return "None, the parent block is fully allocated";
+    }
+    return `${BigInt(unused.address_count).toLocaleString()} addresses in ${unused.blocks.join(", ")}`;
+}
@@ -0,0 +1,135 @@
+import { Button, buttonVariants } from "@/components/ui/button";
+import { Label } from "@/components/ui/label";
+import { errorMessage } from "@/lib/errors";
+import { currentProject } from "@/lib/project";
+import { cn } from "@/lib/utils";
+import { invoke } from "@tauri-apps/api/core";
+import { ArrowLeft, FolderOpen } from "lucide-react";
+import { useState } from "react";
+
+type ConflictEntry = {
+    index: number;
+    label: string;
+    prefix: string;
+};
+
+type Conflict = {
+    kind: "duplicate" | "contains";
+    outer: ConflictEntry;
+    inner: ConflictEntry;
+};
+
+type ConflictReport = {
+    conflicts: Conflict[];
+    duplicates: number;
+    containments: number;
+};
+
+type PlannedSubnet = { name?: string; vlan_id?: number; vlan_name?: string; network: string; cidr: number };
+
+/** Splits "Label words 10.0.0.0/24" lines into a label and the trailing prefix. */
+function parseLines(text: string) {
+    return text
+        .split("\n")
+        .map((line) => line.trim())
+        .filter(Boolean)
+        .map((line) => {
+            const split = line.lastIndexOf(" ");
+            return split < 0
+                ? { label: "", prefix: line }
+                : { label: line.slice(0, split).trim(), prefix: line.slice(split + 1) };
+        });
+}
+
+const ConflictChecker = () => {
+    const [lines, setLines] = useState("");
+    const [report, setReport] = useState<ConflictReport>();
+
+    /** Lists every subnet of the VLSM and VLAN plans in the open project. */
+    const loadProject = () => {
+        const project = currentProject();
+        const vlsm = project.vlsm.flatMap((plan) =>
+            (plan.subnets as PlannedSubnet[]).map(
+                (subnet) => `${plan.name} / ${subnet.name} ${subnet.network}/${subnet.cidr}`,
+            ),
+        );
+        const vlans = project.vlans.flatMap((plan) =>
+            (plan.allocations as PlannedSubnet[]).map(
+                (vlan) => `${plan.name} / VLAN ${vlan.vlan_id} ${vlan.vlan_name} ${vlan.network}/${vlan.cidr}`,
+            ),
+        );
+        setLines([...vlsm, ...vlans].join("\n"));
+    };
+
+    const check = async () => {
+        try {
+            setReport(await invoke<ConflictReport>("check_conflicts", { input: { prefixes: parseLines(lines) } }));
+        } catch (error) {
+            alert(errorMessage(error));
+        }
+    };
+
+    const describe = (entry: ConflictEntry) => `${entry.label || `#${entry.index + 1}`} (${entry.prefix})`;
+
+    return (
+        <main>
+            <div className="p-4 flex flex-col gap-4">
+                <a
+                    className={cn(
+                        buttonVariants({
+                            variant: "outline",
+                            size: "sm",
+                        }),
+                        "px-4 max-w-24",
+                    )}
+                    href="/"
+                >
+                    <ArrowLeft className="w-4 h-4" />
+                    Back
+                </a>
+                <div className="flex flex-row gap-12">
+                    <div className="flex flex-col gap-4 w-1/3">
+                        <div>
+                            <Label htmlFor="prefixes">Prefixes, one per line with an optional label</Label>
+                            <textarea
+                                id="prefixes"
+                                rows={16}
+                                value={lines}
+                                onChange={(e) => setLines(e.target.value)}
+                                placeholder={"Sales 10.0.1.0/24\nVoice 10.0.1.128/25\nSite 2001:db8::/48"}
+                                className="flex w-full border border-input bg-transparent px-3 py-1 text-sm shadow-sm"
+                            />
+                        </div>
+                        <Button variant="outline" type="button" onClick={loadProject}>
+                            <FolderOpen className="w-4 h-4" />
+                            Load Open Project
+                        </Button>
+                        <Button variant="default" type="button" onClick={check}>
+                            Check
+                        </Button>
+                    </div>
+                    <div className="w-1/2">
+                        {report && (
+                            <div className="border p-4 space-y-2 text-sm">
+                                <div className="font-medium">
+                                    {report.conflicts.length === 0
+                                        ? "No prefixes overlap"
+                                        : `${report.duplicates} duplicates, ${report.containments} containments`}
+                                </div>
+                                {report.conflicts.map((conflict) => (
+                                    <div key={`${conflict.outer.index}-${conflict.inner.index}`}>
+                                        {conflict.kind === "duplicate"
+                                            ? `${describe(conflict.inner)} duplicates ${describe(conflict.outer)}`
+                                            : `${describe(conflict.outer)} contains ${describe(conflict.inner)}`}
+                                    </div>
+                                ))}
+                            </div>
+                        )}
+                    </div>
+                </div>
+            </div>
+        </main>
+    );
+};
+
+export default ConflictChecker;
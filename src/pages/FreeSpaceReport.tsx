@@ -0,0 +1,187 @@
+import { Button, buttonVariants } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { errorMessage } from "@/lib/errors";
+import { cn } from "@/lib/utils";
+import { invoke } from "@tauri-apps/api/core";
+import { ArrowLeft } from "lucide-react";
+import { useState } from "react";
+
+/** A stretch of the parent block that is entirely used or entirely free. */
+type SpaceRun = {
+    first: string;
+    last: string;
+    used: boolean;
+    offset: number;
+    width: number;
+};
+
+type HeatmapCell = {
+    prefix: string;
+    used: number;
+};
+
+type FreeSpaceReport = {
+    parent: string;
+    free_blocks: string[];
+    free_count: string;
+    used_count: string;
+    largest_free: string | null;
+    fragmentation: number;
+    runs: SpaceRun[];
+    heatmap: HeatmapCell[];
+};
+
+const FreeSpaceReportPage = () => {
+    const [parent, setParent] = useState("");
+    const [used, setUsed] = useState("");
+    const [cellPrefixLength, setCellPrefixLength] = useState("");
+    const [report, setReport] = useState<FreeSpaceReport>();
+
+    const calculate = async () => {
+        if (!parent) {
+            alert("Please enter the parent block.");
+            return;
+        }
+
+        try {
+            const response = await invoke<FreeSpaceReport>("calculate_free_space", {
+                input: {
+                    parent,
+                    used: used.split(/[\s,]+/).filter(Boolean),
+                    cell_prefix_length: cellPrefixLength ? Number(cellPrefixLength) : null,
+                },
+            });
+            setReport(response);
+        } catch (error) {
+            alert(errorMessage(error));
+        }
+    };
+
+    const columns = report ? Math.ceil(Math.sqrt(report.heatmap.length)) : 1;
+
+    return (
+        <main>
+            <div className="p-4 flex flex-col gap-4">
+                <a
+                    className={cn(
+                        buttonVariants({
+                            variant: "outline",
+                            size: "sm",
+                        }),
+                        "px-4 max-w-24",
+                    )}
+                    href="/"
+                >
+                    <ArrowLeft className="w-4 h-4" />
+                    Back
+                </a>
+                <div className="flex flex-row gap-12">
+                    <div className="flex flex-col gap-4 w-1/3">
+                        <div>
+                            <Label htmlFor="parent">Parent Block</Label>
+                            <Input
+                                id="parent"
+                                value={parent}
+                                onChange={(e) => setParent(e.target.value)}
+                                placeholder="10.0.0.0/16 or 2001:db8::/48"
+                            />
+                        </div>
+                        <div>
+                            <Label htmlFor="used">Used Subnets</Label>
+                            <textarea
+                                id="used"
+                                rows={8}
+                                value={used}
+                                onChange={(e) => setUsed(e.target.value)}
+                                placeholder={"10.0.0.0/24\n10.0.4.0/22"}
+                                className="flex w-full border border-input bg-transparent px-3 py-1 text-sm shadow-sm"
+                            />
+                        </div>
+                        <div>
+                            <Label htmlFor="cellPrefixLength">Heatmap Cell Prefix Length</Label>
+                            <Input
+                                id="cellPrefixLength"
+                                type="number"
+                                value={cellPrefixLength}
+                                onChange={(e) => setCellPrefixLength(e.target.value)}
+                                placeholder="6 bits longer than the parent"
+                            />
+                        </div>
+                        <Button variant="default" type="button" onClick={calculate}>
+                            Calculate
+                        </Button>
+                    </div>
+                    <div className="w-1/2 flex flex-col gap-4">
+                        {report && (
+                            <>
+                                <div className="border p-4 grid grid-cols-2 gap-2 text-sm">
+                                    <span className="font-medium">Parent:</span>
+                                    <span>{report.parent}</span>
+                                    <span className="font-medium">Used Addresses:</span>
+                                    <span className="break-all">{BigInt(report.used_count).toLocaleString()}</span>
+                                    <span className="font-medium">Free Addresses:</span>
+                                    <span className="break-all">{BigInt(report.free_count).toLocaleString()}</span>
+                                    <span className="font-medium">Largest Free Prefix:</span>
+                                    <span>{report.largest_free ?? "None, the parent block is full"}</span>
+                                    <span className="font-medium">Fragmentation:</span>
+                                    <span>{(report.fragmentation * 100).toFixed(1)}%</span>
+                                </div>
+
+                                <div>
+                                    <h3 className="font-semibold">Address Space</h3>
+                                    <div className="relative h-8 w-full border bg-muted">
+                                        {report.runs
+                                            .filter((run) => run.used)
+                                            .map((run) => (
+                                                <div
+                                                    key={run.first}
+                                                    title={`${run.first} - ${run.last}`}
+                                                    className="absolute inset-y-0 bg-primary"
+                                                    style={{
+                                                        left: `${run.offset * 100}%`,
+                                                        width: `max(1px, ${run.width * 100}%)`,
+                                                    }}
+                                                />
+                                            ))}
+                                    </div>
+                                </div>
+
+                                <div>
+                                    <h3 className="font-semibold">Heatmap</h3>
+                                    <div
+                                        className="grid gap-px"
+                                        style={{ gridTemplateColumns: `repeat(${columns}, minmax(0, 1fr))` }}
+                                    >
+                                        {report.heatmap.map((cell) => (
+                                            <div
+                                                key={cell.prefix}
+                                                title={`${cell.prefix}: ${(cell.used * 100).toFixed(1)}% used`}
+                                                className="h-4 border bg-primary"
+                                                style={{ opacity: 0.1 + cell.used * 0.9 }}
+                                            />
+                                        ))}
+                                    </div>
+                                </div>
+
+                                <div>
+                                    <h3 className="font-semibold">Free Blocks</h3>
+                                    <ul className="border p-4 text-sm space-y-1">
+                                        {report.free_blocks.map((block) => (
+                                            <li key={block}>{block}</li>
+                                        ))}
+                                        {report.free_blocks.length === 0 && (
+                                            <li className="text-muted-foreground">None</li>
+                                        )}
+                                    </ul>
+                                </div>
+                            </>
+                        )}
+                    </div>
+                </div>
+            </div>
+        </main>
+    );
+};
+
+export default FreeSpaceReportPage;
@@ -1,8 +1,47 @@
 import { Button, buttonVariants } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { errorMessage } from "@/lib/errors";
+import { type Project, currentProject, emptyProject, storeProject } from "@/lib/project";
 import { cn } from "@/lib/utils";
 import { invoke } from "@tauri-apps/api/core";
+import { FolderOpen, Save } from "lucide-react";
+import { useState } from "react";
 
 const HomePage = () => {
+    const [project, setProject] = useState<Project>(currentProject);
+    const [projectPath, setProjectPath] = useState("");
+
+    const updateProject = (updated: Project) => {
+        storeProject(updated);
+        setProject(updated);
+    };
+
+    const saveProject = async () => {
+        if (!projectPath.trim()) {
+            alert("Please enter a file path ending in .json or .toml.");
+            return;
+        }
+        try {
+            await invoke("save_project", { project, path: projectPath.trim() });
+            alert(`Project saved to ${projectPath.trim()}`);
+        } catch (error) {
+            alert(errorMessage(error));
+        }
+    };
+
+    const openProject = async () => {
+        if (!projectPath.trim()) {
+            alert("Please enter the path of a project file.");
+            return;
+        }
+        try {
+            updateProject(await invoke<Project>("open_project", { path: projectPath.trim() }));
+        } catch (error) {
+            alert(errorMessage(error));
+        }
+    };
+
     return (
         <main className="flex flex-col min-h-screen">
             <div className="flex-1 flex items-center flex-row gap-8 justify-center">
@@ -93,6 +132,42 @@ const HomePage = () => {
                     >
                         Wireless Network Setup
                     </a>
+                    <a
+                        className={cn(
+                            buttonVariants({
+                                variant: "outline",
+                                size: "sm",
+                            }),
+                            "w-[200px] px-4",
+                        )}
+                        href="/ipam-database"
+                    >
+                        IPAM Database
+                    </a>
+                    <a
+                        className={cn(
+                            buttonVariants({
+                                variant: "outline",
+                                size: "sm",
+                            }),
+                            "w-[200px] px-4",
+                        )}
+                        href="/free-space-report"
+                    >
+                        Free Space Report
+                    </a>
+                    <a
+                        className={cn(
+                            buttonVariants({
+                                variant: "outline",
+                                size: "sm",
+                            }),
+                            "w-[200px] px-4",
+                        )}
+                        href="/conflict-checker"
+                    >
+                        Conflict Checker
+                    </a>
                     <Button
                         onClick={async () => {
                             try {
@@ -108,6 +183,48 @@ const HomePage = () => {
                         Exit
                     </Button>
                 </div>
+                <div className="flex flex-col gap-2 w-[260px] border p-4">
+                    <h2 className="font-semibold">Project</h2>
+                    <div className="space-y-1">
+                        <Label htmlFor="projectName">Name</Label>
+                        <Input
+                            id="projectName"
+                            value={project.name}
+                            onChange={(e) => updateProject({ ...project, name: e.target.value })}
+                        />
+                    </div>
+                    <ul className="text-sm text-muted-foreground">
+                        <li>{project.vlsm.length} VLSM plans</li>
+                        <li>{project.vlans.length} VLAN plans</li>
+                        <li>{project.wireless.length} wireless networks</li>
+                    </ul>
+                    <div className="space-y-1">
+                        <Label htmlFor="projectPath">File (.json or .toml)</Label>
+                        <Input
+                            id="projectPath"
+                            placeholder="/home/me/branch-office.toml"
+                            value={projectPath}
+                            onChange={(e) => setProjectPath(e.target.value)}
+                        />
+                    </div>
+                    <div className="flex gap-2">
+                        <Button variant="outline" size="sm" className="flex-1" onClick={saveProject}>
+                            <Save className="w-4 h-4" />
+                            Save
+                        </Button>
+                        <Button variant="outline" size="sm" className="flex-1" onClick={openProject}>
+                            <FolderOpen className="w-4 h-4" />
+                            Open
+                        </Button>
+                    </div>
+                    <Button
+                        variant="ghost"
+                        size="sm"
+                        onClick={() => confirm("Start a new, empty project?") && updateProject(emptyProject())}
+                    >
+                        New Project
+                    </Button>
+                </div>
             </div>
             <footer className="mb-8 text-center font-light text-sm text-muted-foreground">
                 In ancient Greek, δίκαιος (dikaios) is an adjective that means "just" or "righteous."
@@ -1,22 +1,29 @@
 import { Button, buttonVariants } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Label } from "@/components/ui/label";
+import { errorMessage } from "@/lib/errors";
 import { cn } from "@/lib/utils";
 import { invoke } from "@tauri-apps/api/core";
 import { ArrowLeft } from "lucide-react";
 import { useState } from "react";
 
 type NetworkInfo = {
+    family: "ipv4" | "ipv6";
+    prefix_length: number;
     address: string;
     netmask: string;
     wildcard: string;
     network: string;
-    broadcast: string;
+    broadcast: string | null;
     hostmin: string;
     hostmax: string;
-    hosts: number;
-    ip_class: string;
+    hosts: number | null;
+    address_count: string;
+    ip_class: string | null;
     is_private: boolean;
+    scope: string | null;
+    compressed: string | null;
+    expanded: string | null;
 };
 
 const IpCalculator = () => {
@@ -31,7 +38,7 @@ const IpCalculator = () => {
         }
 
         try {
-            const response = await invoke<{ info: NetworkInfo | null; error: string | null }>(
+            const response = await invoke<{ info: NetworkInfo }>(
                 "calculate_network_info",
                 {
                     input: {
@@ -41,18 +48,9 @@ const IpCalculator = () => {
                 },
             );
 
-            if (response.error) {
-                alert(response.error);
-                return;
-            }
-
-            if (response.info) {
-                setResult(response.info);
-            }
+            setResult(response.info);
         } catch (error) {
-            if (error instanceof Error) {
-                alert(error.message);
-            }
+            alert(errorMessage(error));
         }
     };
 
@@ -81,7 +79,7 @@ const IpCalculator = () => {
                                 type="text"
                                 value={ipAddress}
                                 onChange={(e) => setIpAddress(e.target.value)}
-                                placeholder="192.168.4.56"
+                                placeholder="192.168.4.56 or 2001:db8::1"
                             />
                         </div>
                         <div>
@@ -105,6 +103,13 @@ const IpCalculator = () => {
                                     <span className="font-medium">Address:</span>
                                     <span>{result.address}</span>
 
+                                    {result.expanded && (
+                                        <>
+                                            <span className="font-medium">Expanded:</span>
+                                            <span className="break-all">{result.expanded}</span>
+                                        </>
+                                    )}
+
                                     <span className="font-medium">Netmask:</span>
                                     <span>{result.netmask}</span>
 
@@ -112,22 +117,50 @@ const IpCalculator = () => {
                                     <span>{result.wildcard}</span>
 
                                     <span className="font-medium">Network:</span>
-                                    <span>{result.network}</span>
-
-                                    <span className="font-medium">Broadcast:</span>
-                                    <span>{result.broadcast}</span>
-
-                                    <span className="font-medium">HostMin:</span>
+                                    <span>
+                                        {result.network}/{result.prefix_length}
+                                    </span>
+
+                                    {result.broadcast && (
+                                        <>
+                                            <span className="font-medium">Broadcast:</span>
+                                            <span>{result.broadcast}</span>
+                                        </>
+                                    )}
+
+                                    <span className="font-medium">
+                                        {result.family === "ipv6" ? "First Address:" : "HostMin:"}
+                                    </span>
                                     <span>{result.hostmin}</span>
 
-                                    <span className="font-medium">HostMax:</span>
+                                    <span className="font-medium">
+                                        {result.family === "ipv6" ? "Last Address:" : "HostMax:"}
+                                    </span>
                                     <span>{result.hostmax}</span>
 
-                                    <span className="font-medium">Hosts/Net:</span>
-                                    <span>{result.hosts.toLocaleString()}</span>
-
-                                    <span className="font-medium">IP Class:</span>
-                                    <span>{result.ip_class}</span>
+                                    {result.hosts !== null && (
+                                        <>
+                                            <span className="font-medium">Hosts/Net:</span>
+                                            <span>{result.hosts.toLocaleString()}</span>
+                                        </>
+                                    )}
+
+                                    <span className="font-medium">Addresses:</span>
+                                    <span className="break-all">{BigInt(result.address_count).toLocaleString()}</span>
+
+                                    {result.ip_class && (
+                                        <>
+                                            <span className="font-medium">IP Class:</span>
+                                            <span>{result.ip_class}</span>
+                                        </>
+                                    )}
+
+                                    {result.scope && (
+                                        <>
+                                            <span className="font-medium">Scope:</span>
+                                            <span>{result.scope}</span>
+                                        </>
+                                    )}
 
                                     <span className="font-medium">Type:</span>
                                     <span>{result.is_private ? "Private" : "Public"}</span>
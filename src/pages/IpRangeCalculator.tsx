@@ -1,39 +1,51 @@
 import { Button, buttonVariants } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Label } from "@/components/ui/label";
+import { errorMessage } from "@/lib/errors";
 import { cn, randomId } from "@/lib/utils";
 import { invoke } from "@tauri-apps/api/core";
 import { ArrowLeft } from "lucide-react";
 import { useState } from "react";
 
+const PAGE_SIZE = 256;
+
+type HostPage = {
+    hosts: string[];
+    offset: number;
+    limit: number;
+    /** Unknown when a filtered search stopped before the end of the range. */
+    total: number | null;
+    more: boolean;
+    truncated: boolean;
+};
+
 const IpRangeCalculator = () => {
     const [endOfIP, setEndOfIP] = useState("");
     const [subnetMask, setSubnetMask] = useState("");
-    const [result, setResult] = useState<string[]>([]);
+    const [filter, setFilter] = useState("");
+    const [page, setPage] = useState<HostPage>();
+    const result = page?.hosts ?? [];
 
-    const calculateValidIPs = async () => {
+    const calculateValidIPs = async (offset = 0) => {
         if (!endOfIP || !subnetMask) {
             alert("Please enter both end of IP and subnet mask.");
             return;
         }
 
         try {
-            const response = await invoke<{ valid_ips: string[]; error: string | null }>("calculate_ip_range", {
+            const response = await invoke<HostPage>("calculate_ip_range", {
                 input: {
                     ip_address: endOfIP,
                     subnet_mask: subnetMask,
+                    offset,
+                    limit: PAGE_SIZE,
+                    filter: filter || null,
                 },
             });
 
-            if (response.error) {
-                alert(response.error);
-                return;
-            }
-
-            setResult(response.valid_ips);
-        } catch (_error) {
-            console.log(_error);
-            alert("Invalid input. Please provide correct values.");
+            setPage(response);
+        } catch (error) {
+            alert(errorMessage(error));
         }
     };
 
@@ -74,12 +86,49 @@ const IpRangeCalculator = () => {
                                 placeholder="28"
                             />
                         </div>
-                        <Button variant="default" type="button" onClick={calculateValidIPs}>
+                        <div>
+                            <Label htmlFor="filter">Filter (optional)</Label>
+                            <Input
+                                id="filter"
+                                type="text"
+                                value={filter}
+                                onChange={(e) => setFilter(e.target.value)}
+                                placeholder=".1"
+                            />
+                        </div>
+                        <Button variant="default" type="button" onClick={() => calculateValidIPs()}>
                             Calculate
                         </Button>
                     </div>
                     <div className="w-1/2">
                         <h3>Valid IP Addresses:</h3>
+                        {page && page.hosts.length > 0 && (
+                            <div className="flex flex-row items-center justify-between py-2">
+                                <span>
+                                    Showing {page.offset + 1}-{page.offset + page.hosts.length}
+                                    {page.total !== null ? ` of ${page.total}` : page.more ? " of more" : ""}
+                                    {page.truncated && " (search limited to the start of the range)"}
+                                </span>
+                                <div className="flex flex-row gap-2">
+                                    <Button
+                                        variant="outline"
+                                        size="sm"
+                                        disabled={page.offset === 0}
+                                        onClick={() => calculateValidIPs(Math.max(0, page.offset - page.limit))}
+                                    >
+                                        Previous
+                                    </Button>
+                                    <Button
+                                        variant="outline"
+                                        size="sm"
+                                        disabled={!page.more}
+                                        onClick={() => calculateValidIPs(page.offset + page.limit)}
+                                    >
+                                        Next
+                                    </Button>
+                                </div>
+                            </div>
+                        )}
                         {result.length > 0 ? (
                             <div className="flex flex-row gap-8 border max-h-[400px] overflow-y-auto p-4">
                                 {(() => {
@@ -0,0 +1,521 @@
+import { Button, buttonVariants } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { errorMessage } from "@/lib/errors";
+import {
+    ADDRESS_STATUSES,
+    type AddressStatus,
+    type IpamAssignment,
+    type IpamBlock,
+    type IpamReservation,
+    type IpamSearchResults,
+    type IpamSubnet,
+    type IpamVlan,
+} from "@/lib/ipam";
+import { cn } from "@/lib/utils";
+import { invoke } from "@tauri-apps/api/core";
+import { ArrowLeft, Database, Plus, Search, Sparkles, Trash2 } from "lucide-react";
+import { useEffect, useState } from "react";
+
+const selectClassName = "flex h-9 w-full border border-input bg-transparent px-3 py-1 text-sm shadow-sm";
+
+const IpamDatabase = () => {
+    const [blocks, setBlocks] = useState<IpamBlock[]>([]);
+    const [reservations, setReservations] = useState<IpamReservation[]>([]);
+    const [subnets, setSubnets] = useState<IpamSubnet[]>([]);
+    const [vlans, setVlans] = useState<IpamVlan[]>([]);
+    const [assignments, setAssignments] = useState<IpamAssignment[]>([]);
+    const [blockId, setBlockId] = useState<number | null>(null);
+    const [subnetId, setSubnetId] = useState<number | null>(null);
+
+    const [newBlock, setNewBlock] = useState({ prefix: "", description: "" });
+    const [newReservation, setNewReservation] = useState({ first: "", last: "", reason: "" });
+    const [nextPrefixLength, setNextPrefixLength] = useState("24");
+    const [newSubnet, setNewSubnet] = useState({ prefix: "", name: "", vlan_id: "", gateway: "" });
+    const [newVlan, setNewVlan] = useState({ vlan_id: "", name: "" });
+    const [newAssignment, setNewAssignment] = useState({
+        address: "",
+        hostname: "",
+        mac: "",
+        owner: "",
+        status: "active" as AddressStatus,
+        notes: "",
+    });
+
+    const [query, setQuery] = useState("");
+    const [searchResults, setSearchResults] = useState<IpamSearchResults | null>(null);
+
+    const selectedBlock = blocks.find((block) => block.id === blockId);
+    const selectedSubnet = subnets.find((subnet) => subnet.id === subnetId);
+
+    const reload = async () => {
+        try {
+            setBlocks(await invoke<IpamBlock[]>("ipam_list_blocks"));
+            setVlans(await invoke<IpamVlan[]>("ipam_list_vlans"));
+            setReservations(
+                blockId === null ? [] : await invoke<IpamReservation[]>("ipam_list_reservations", { blockId }),
+            );
+            setSubnets(blockId === null ? [] : await invoke<IpamSubnet[]>("ipam_list_subnets", { blockId }));
+            setAssignments(
+                subnetId === null ? [] : await invoke<IpamAssignment[]>("ipam_list_assignments", { subnetId }),
+            );
+        } catch (error) {
+            alert(errorMessage(error));
+        }
+    };
+
+    // biome-ignore lint/correctness/useExhaustiveDependencies: reload only when the selection changes
+    useEffect(() => {
+        reload();
+    }, [blockId, subnetId]);
+
+    /** Runs a write command, then refreshes every list. */
+    const write = async (command: string, args: Record<string, unknown>) => {
+        try {
+            await invoke(command, args);
+            await reload();
+            return true;
+        } catch (error) {
+            alert(errorMessage(error));
+            return false;
+        }
+    };
+
+    const addBlock = async () => {
+        if (await write("ipam_add_block", { block: newBlock })) {
+            setNewBlock({ prefix: "", description: "" });
+        }
+    };
+
+    const addReservation = async () => {
+        if (await write("ipam_add_reservation", { reservation: newReservation })) {
+            setNewReservation({ first: "", last: "", reason: "" });
+        }
+    };
+
+    const addSubnet = async () => {
+        const subnet = {
+            prefix: newSubnet.prefix,
+            name: newSubnet.name,
+            vlan_id: newSubnet.vlan_id ? Number(newSubnet.vlan_id) : null,
+            gateway: newSubnet.gateway || null,
+        };
+        if (await write("ipam_add_subnet", { subnet })) {
+            setNewSubnet({ prefix: "", name: "", vlan_id: "", gateway: "" });
+        }
+    };
+
+    const allocateSubnet = async () => {
+        if (!selectedBlock) {
+            return;
+        }
+        const args = { parent: selectedBlock.prefix, prefixLen: Number(nextPrefixLength), name: newSubnet.name };
+        if (await write("ipam_allocate_next_subnet", args)) {
+            setNewSubnet({ ...newSubnet, name: "" });
+        }
+    };
+
+    const addVlan = async () => {
+        if (await write("ipam_add_vlan", { vlan: { vlan_id: Number(newVlan.vlan_id), name: newVlan.name } })) {
+            setNewVlan({ vlan_id: "", name: "" });
+        }
+    };
+
+    const addAssignment = async () => {
+        const assignment = { ...newAssignment, mac: newAssignment.mac || null };
+        if (await write("ipam_add_assignment", { assignment })) {
+            setNewAssignment({ ...newAssignment, address: "", hostname: "", mac: "", notes: "" });
+        }
+    };
+
+    const assignNextFree = async () => {
+        if (!selectedSubnet) {
+            return;
+        }
+        if (
+            await write("ipam_allocate_next_address", {
+                subnet: selectedSubnet.prefix,
+                hostname: newAssignment.hostname,
+            })
+        ) {
+            setNewAssignment({ ...newAssignment, address: "", hostname: "" });
+        }
+    };
+
+    const search = async () => {
+        try {
+            setSearchResults(await invoke<IpamSearchResults>("ipam_search", { query }));
+        } catch (error) {
+            alert(errorMessage(error));
+        }
+    };
+
+    return (
+        <main>
+            <div className="p-4 flex flex-col gap-4">
+                <a
+                    className={cn(
+                        buttonVariants({
+                            variant: "outline",
+                            size: "sm",
+                        }),
+                        "px-4 max-w-24",
+                    )}
+                    href="/"
+                >
+                    <ArrowLeft className="w-4 h-4" />
+                    Back
+                </a>
+                <div className="flex items-center gap-2">
+                    <Database className="w-6 h-6" />
+                    <h2 className="text-2xl font-bold">IPAM Database</h2>
+                </div>
+                <p className="text-sm text-muted-foreground">
+                    Blocks, subnets, VLANs and address assignments stored on this computer. Plans from the VLSM and
+                    VLAN calculators can be committed here with their Commit to IPAM buttons. New subnets and addresses
+                    can be taken from the lowest free space, outside the ranges reserved in each block.
+                </p>
+
+                <div className="flex gap-2">
+                    <Input
+                        placeholder="Search by name, hostname, MAC, owner, address or prefix"
+                        value={query}
+                        onChange={(e) => setQuery(e.target.value)}
+                        onKeyDown={(e) => e.key === "Enter" && search()}
+                    />
+                    <Button variant="outline" onClick={search}>
+                        <Search className="w-4 h-4" />
+                        Search
+                    </Button>
+                </div>
+
+                {searchResults && (
+                    <div className="border p-4 text-sm space-y-1">
+                        {searchResults.blocks.map((block) => (
+                            <div key={`block-${block.id}`}>
+                                <span className="font-medium">Block</span> {block.prefix} {block.description}
+                            </div>
+                        ))}
+                        {searchResults.subnets.map((subnet) => (
+                            <div key={`subnet-${subnet.id}`}>
+                                <span className="font-medium">Subnet</span> {subnet.prefix} {subnet.name}
+                                {subnet.vlan_id !== null && ` (VLAN ${subnet.vlan_id})`}
+                            </div>
+                        ))}
+                        {searchResults.vlans.map((vlan) => (
+                            <div key={`vlan-${vlan.vlan_id}`}>
+                                <span className="font-medium">VLAN</span> {vlan.vlan_id} {vlan.name}
+                            </div>
+                        ))}
+                        {searchResults.assignments.map((assignment) => (
+                            <div key={`assignment-${assignment.id}`}>
+                                <span className="font-medium">Address</span> {assignment.address} {assignment.hostname}
+                                {assignment.mac && ` ${assignment.mac}`}
+                                {assignment.owner && ` (${assignment.owner})`}
+                            </div>
+                        ))}
+                        {Object.values(searchResults).every((results) => results.length === 0) && (
+                            <div className="text-muted-foreground">Nothing matches "{query}"</div>
+                        )}
+                    </div>
+                )}
+
+                <div className="grid grid-cols-4 gap-4">
+                    <div className="border p-4 space-y-2">
+                        <h3 className="font-semibold">Blocks</h3>
+                        {blocks.map((block) => (
+                            <div key={block.id} className="flex items-center justify-between text-sm">
+                                <button
+                                    type="button"
+                                    className={cn("text-left", block.id === blockId && "font-semibold text-primary")}
+                                    onClick={() => {
+                                        setBlockId(block.id);
+                                        setSubnetId(null);
+                                    }}
+                                >
+                                    {block.prefix}
+                                    {block.description && (
+                                        <span className="block text-xs text-muted-foreground">{block.description}</span>
+                                    )}
+                                </button>
+                                <Button
+                                    variant="ghost"
+                                    size="sm"
+                                    onClick={() =>
+                                        confirm(`Delete ${block.prefix} with all its subnets and addresses?`) &&
+                                        write("ipam_delete_block", { id: block.id }).then(() => setBlockId(null))
+                                    }
+                                >
+                                    <Trash2 className="w-4 h-4 text-destructive" />
+                                </Button>
+                            </div>
+                        ))}
+                        <Input
+                            placeholder="10.0.0.0/16"
+                            value={newBlock.prefix}
+                            onChange={(e) => setNewBlock({ ...newBlock, prefix: e.target.value })}
+                        />
+                        <Input
+                            placeholder="Description"
+                            value={newBlock.description}
+                            onChange={(e) => setNewBlock({ ...newBlock, description: e.target.value })}
+                        />
+                        <Button variant="outline" size="sm" onClick={addBlock}>
+                            <Plus className="w-4 h-4" />
+                            Add Block
+                        </Button>
+                        {selectedBlock && (
+                            <>
+                                <h4 className="pt-2 text-sm font-semibold">Reserved in {selectedBlock.prefix}</h4>
+                                {reservations.map((reservation) => (
+                                    <div key={reservation.id} className="flex items-center justify-between text-sm">
+                                        <span>
+                                            {reservation.first} - {reservation.last}
+                                            {reservation.reason && (
+                                                <span className="block text-xs text-muted-foreground">
+                                                    {reservation.reason}
+                                                </span>
+                                            )}
+                                        </span>
+                                        <Button
+                                            variant="ghost"
+                                            size="sm"
+                                            onClick={() => write("ipam_delete_reservation", { id: reservation.id })}
+                                        >
+                                            <Trash2 className="w-4 h-4 text-destructive" />
+                                        </Button>
+                                    </div>
+                                ))}
+                                <Input
+                                    placeholder="First address"
+                                    value={newReservation.first}
+                                    onChange={(e) => setNewReservation({ ...newReservation, first: e.target.value })}
+                                />
+                                <Input
+                                    placeholder="Last address"
+                                    value={newReservation.last}
+                                    onChange={(e) => setNewReservation({ ...newReservation, last: e.target.value })}
+                                />
+                                <Input
+                                    placeholder="Reason"
+                                    value={newReservation.reason}
+                                    onChange={(e) => setNewReservation({ ...newReservation, reason: e.target.value })}
+                                />
+                                <Button variant="outline" size="sm" onClick={addReservation}>
+                                    <Plus className="w-4 h-4" />
+                                    Reserve Range
+                                </Button>
+                            </>
+                        )}
+                    </div>
+
+                    <div className="border p-4 space-y-2">
+                        <h3 className="font-semibold">
+                            Subnets{selectedBlock ? ` of ${selectedBlock.prefix}` : ""}
+                        </h3>
+                        {selectedBlock ? (
+                            subnets.map((subnet) => (
+                                <div key={subnet.id} className="flex items-center justify-between text-sm">
+                                    <button
+                                        type="button"
+                                        className={cn(
+                                            "text-left",
+                                            subnet.id === subnetId && "font-semibold text-primary",
+                                        )}
+                                        onClick={() => setSubnetId(subnet.id)}
+                                    >
+                                        {subnet.prefix}
+                                        <span className="block text-xs text-muted-foreground">
+                                            {[subnet.name, subnet.vlan_id !== null && `VLAN ${subnet.vlan_id}`]
+                                                .filter(Boolean)
+                                                .join(", ")}
+                                        </span>
+                                    </button>
+                                    <Button
+                                        variant="ghost"
+                                        size="sm"
+                                        onClick={() =>
+                                            confirm(`Delete ${subnet.prefix} with all its addresses?`) &&
+                                            write("ipam_delete_subnet", { id: subnet.id }).then(() => setSubnetId(null))
+                                        }
+                                    >
+                                        <Trash2 className="w-4 h-4 text-destructive" />
+                                    </Button>
+                                </div>
+                            ))
+                        ) : (
+                            <p className="text-sm text-muted-foreground">Select a block to see its subnets</p>
+                        )}
+                        <Input
+                            placeholder="10.0.1.0/24"
+                            value={newSubnet.prefix}
+                            onChange={(e) => setNewSubnet({ ...newSubnet, prefix: e.target.value })}
+                        />
+                        <Input
+                            placeholder="Name"
+                            value={newSubnet.name}
+                            onChange={(e) => setNewSubnet({ ...newSubnet, name: e.target.value })}
+                        />
+                        <select
+                            value={newSubnet.vlan_id}
+                            onChange={(e) => setNewSubnet({ ...newSubnet, vlan_id: e.target.value })}
+                            className={selectClassName}
+                        >
+                            <option value="">No VLAN</option>
+                            {vlans.map((vlan) => (
+                                <option key={vlan.vlan_id} value={vlan.vlan_id}>
+                                    VLAN {vlan.vlan_id} ({vlan.name})
+                                </option>
+                            ))}
+                        </select>
+                        <Input
+                            placeholder="Gateway"
+                            value={newSubnet.gateway}
+                            onChange={(e) => setNewSubnet({ ...newSubnet, gateway: e.target.value })}
+                        />
+                        <Button variant="outline" size="sm" onClick={addSubnet}>
+                            <Plus className="w-4 h-4" />
+                            Add Subnet
+                        </Button>
+                        {selectedBlock && (
+                            <div className="flex items-center gap-2">
+                                <Label htmlFor="nextPrefixLength">Next free /</Label>
+                                <Input
+                                    id="nextPrefixLength"
+                                    type="number"
+                                    className="w-20"
+                                    value={nextPrefixLength}
+                                    onChange={(e) => setNextPrefixLength(e.target.value)}
+                                />
+                                <Button variant="outline" size="sm" onClick={allocateSubnet}>
+                                    <Sparkles className="w-4 h-4" />
+                                    Allocate
+                                </Button>
+                            </div>
+                        )}
+                    </div>
+
+                    <div className="border p-4 space-y-2">
+                        <h3 className="font-semibold">
+                            Addresses{selectedSubnet ? ` in ${selectedSubnet.prefix}` : ""}
+                        </h3>
+                        {selectedSubnet ? (
+                            assignments.map((assignment) => (
+                                <div key={assignment.id} className="flex items-center justify-between text-sm">
+                                    <span>
+                                        {assignment.address} {assignment.hostname}
+                                        <span className="block text-xs text-muted-foreground">
+                                            {[
+                                                ADDRESS_STATUSES.find((status) => status.value === assignment.status)
+                                                    ?.label,
+                                                assignment.mac,
+                                                assignment.owner,
+                                                assignment.notes,
+                                            ]
+                                                .filter(Boolean)
+                                                .join(", ")}
+                                        </span>
+                                    </span>
+                                    <Button
+                                        variant="ghost"
+                                        size="sm"
+                                        onClick={() => write("ipam_delete_assignment", { id: assignment.id })}
+                                    >
+                                        <Trash2 className="w-4 h-4 text-destructive" />
+                                    </Button>
+                                </div>
+                            ))
+                        ) : (
+                            <p className="text-sm text-muted-foreground">Select a subnet to see its addresses</p>
+                        )}
+                        <Input
+                            placeholder="Address"
+                            value={newAssignment.address}
+                            onChange={(e) => setNewAssignment({ ...newAssignment, address: e.target.value })}
+                        />
+                        <Input
+                            placeholder="Hostname"
+                            value={newAssignment.hostname}
+                            onChange={(e) => setNewAssignment({ ...newAssignment, hostname: e.target.value })}
+                        />
+                        <Input
+                            placeholder="MAC address"
+                            value={newAssignment.mac}
+                            onChange={(e) => setNewAssignment({ ...newAssignment, mac: e.target.value })}
+                        />
+                        <Input
+                            placeholder="Owner"
+                            value={newAssignment.owner}
+                            onChange={(e) => setNewAssignment({ ...newAssignment, owner: e.target.value })}
+                        />
+                        <select
+                            value={newAssignment.status}
+                            onChange={(e) =>
+                                setNewAssignment({ ...newAssignment, status: e.target.value as AddressStatus })
+                            }
+                            className={selectClassName}
+                        >
+                            {ADDRESS_STATUSES.map((status) => (
+                                <option key={status.value} value={status.value}>
+                                    {status.label}
+                                </option>
+                            ))}
+                        </select>
+                        <Input
+                            placeholder="Notes"
+                            value={newAssignment.notes}
+                            onChange={(e) => setNewAssignment({ ...newAssignment, notes: e.target.value })}
+                        />
+                        <Button variant="outline" size="sm" onClick={addAssignment}>
+                            <Plus className="w-4 h-4" />
+                            Assign Address
+                        </Button>
+                        {selectedSubnet && (
+                            <Button variant="outline" size="sm" onClick={assignNextFree}>
+                                <Sparkles className="w-4 h-4" />
+                                Assign Next Free
+                            </Button>
+                        )}
+                    </div>
+
+                    <div className="border p-4 space-y-2">
+                        <h3 className="font-semibold">VLANs</h3>
+                        {vlans.map((vlan) => (
+                            <div key={vlan.vlan_id} className="flex items-center justify-between text-sm">
+                                <span>
+                                    {vlan.vlan_id} {vlan.name}
+                                </span>
+                                <Button
+                                    variant="ghost"
+                                    size="sm"
+                                    onClick={() => write("ipam_delete_vlan", { vlanId: vlan.vlan_id })}
+                                >
+                                    <Trash2 className="w-4 h-4 text-destructive" />
+                                </Button>
+                            </div>
+                        ))}
+                        <Label htmlFor="newVlanId">New VLAN</Label>
+                        <Input
+                            id="newVlanId"
+                            type="number"
+                            placeholder="VLAN ID"
+                            value={newVlan.vlan_id}
+                            onChange={(e) => setNewVlan({ ...newVlan, vlan_id: e.target.value })}
+                        />
+                        <Input
+                            placeholder="Name"
+                            value={newVlan.name}
+                            onChange={(e) => setNewVlan({ ...newVlan, name: e.target.value })}
+                        />
+                        <Button variant="outline" size="sm" onClick={addVlan}>
+                            <Plus className="w-4 h-4" />
+                            Add VLAN
+                        </Button>
+                    </div>
+                </div>
+            </div>
+        </main>
+    );
+};
+
+export default IpamDatabase;
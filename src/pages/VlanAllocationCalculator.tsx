@@ -1,15 +1,26 @@
 import { Button, buttonVariants } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Label } from "@/components/ui/label";
+import { type Diagnostic, errorMessage, isDikaiosError } from "@/lib/errors";
+import type { CommittedPlan } from "@/lib/ipam";
+import { addToProject } from "@/lib/project";
+import { type UnusedSpace, describeUnused } from "@/lib/unused";
 import { cn } from "@/lib/utils";
 import { invoke } from "@tauri-apps/api/core";
-import { ArrowLeft, Copy, Plus, Trash2 } from "lucide-react";
+import { ArrowLeft, Copy, Database, FolderPlus, Plus, Trash2 } from "lucide-react";
 import { useState } from "react";
 
 type VlanConfig = {
     vlan_id: number;
     vlan_name: string;
     required_hosts: number;
+    access_ports: string[];
+};
+
+type TrunkPort = {
+    interface: string;
+    allowed_vlans: number[];
+    native_vlan: number | null;
 };
 
 type VlanAllocation = {
@@ -23,8 +34,66 @@ type VlanAllocation = {
     last_host: string;
     broadcast: string;
     usable_hosts: number;
+    access_ports: string[];
+    dhcp: DhcpScope | null;
+    redundancy: RedundantGateway | null;
+};
+
+type RedundantGateway = {
+    group: number;
+    router_addresses: [string, string];
+    priorities: [number, number];
+    active_router: number;
+};
+
+type Redundancy = "none" | "hsrp" | "vrrp" | "glbp";
+
+type DhcpScope = {
+    pool_start: string;
+    pool_end: string;
+    excluded_end: string;
+};
+
+type DhcpMode = "off" | "pool" | "relay";
+
+const DHCP_SERVERS = [
+    { id: "kea", name: "Kea DHCPv4" },
+    { id: "isc_dhcpd", name: "ISC dhcpd" },
+] as const;
+
+type DhcpServer = (typeof DHCP_SERVERS)[number]["id"];
+
+const PLATFORMS = [
+    { id: "cisco_ios", name: "Cisco IOS" },
+    { id: "juniper_junos", name: "Juniper Junos" },
+    { id: "arista_eos", name: "Arista EOS" },
+    { id: "mikrotik_routeros", name: "MikroTik RouterOS" },
+    { id: "vyos", name: "VyOS" },
+] as const;
+
+type Platform = (typeof PLATFORMS)[number]["id"];
+
+const LINUX_FORMATS = [
+    { id: "netplan", name: "netplan" },
+    { id: "systemd_networkd", name: "systemd-networkd" },
+    { id: "network_manager", name: "NetworkManager" },
+    { id: "ifupdown", name: "ifupdown" },
+] as const;
+
+type LinuxFormat = (typeof LINUX_FORMATS)[number]["id"];
+
+type ConfigFile = {
+    path: string;
+    contents: string;
 };
 
+/** Splits a comma-separated list, dropping empty entries. */
+const splitList = (value: string) =>
+    value
+        .split(",")
+        .map((item) => item.trim())
+        .filter((item) => item.length > 0);
+
 const VlanAllocationCalculator = () => {
     const [baseNetwork, setBaseNetwork] = useState("");
     const [baseCidr, setBaseCidr] = useState("");
@@ -32,8 +101,32 @@ const VlanAllocationCalculator = () => {
     const [newVlanId, setNewVlanId] = useState("");
     const [newVlanName, setNewVlanName] = useState("");
     const [newRequiredHosts, setNewRequiredHosts] = useState("");
+    const [newAccessPorts, setNewAccessPorts] = useState("");
+    const [trunks, setTrunks] = useState<TrunkPort[]>([]);
+    const [newTrunkInterface, setNewTrunkInterface] = useState("");
+    const [newTrunkAllowed, setNewTrunkAllowed] = useState("");
+    const [newTrunkNative, setNewTrunkNative] = useState("");
+    const [diagnostics, setDiagnostics] = useState<Diagnostic[]>([]);
+    const [dhcpMode, setDhcpMode] = useState<DhcpMode>("off");
+    const [dhcpServer, setDhcpServer] = useState("");
+    const [dnsServers, setDnsServers] = useState("");
+    const [domainName, setDomainName] = useState("");
+    const [leaseTime, setLeaseTime] = useState("86400");
+    const [excludedAddresses, setExcludedAddresses] = useState("0");
+    const [dhcpServerFormat, setDhcpServerFormat] = useState<DhcpServer>("kea");
+    const [dhcpConfig, setDhcpConfig] = useState("");
+    const [redundancy, setRedundancy] = useState<Redundancy>("none");
+    const [routers, setRouters] = useState<[string, string]>(["SW1", "SW2"]);
     const [result, setResult] = useState<VlanAllocation[]>([]);
-    const [ciscoCommands, setCiscoCommands] = useState("");
+    const [platform, setPlatform] = useState<Platform>("cisco_ios");
+    const [commands, setCommands] = useState("");
+    const [commandsPlatform, setCommandsPlatform] = useState<Platform>("cisco_ios");
+    const [unused, setUnused] = useState<UnusedSpace | null>(null);
+    const [calculatedPlan, setCalculatedPlan] = useState<{ input: object; trunks: TrunkPort[] } | null>(null);
+    const [parentInterface, setParentInterface] = useState("eth0");
+    const [linuxFormat, setLinuxFormat] = useState<LinuxFormat>("netplan");
+    const [uplinkVlan, setUplinkVlan] = useState("");
+    const [linuxFiles, setLinuxFiles] = useState<ConfigFile[]>([]);
 
     const addVlan = () => {
         if (newVlanId && newVlanName && newRequiredHosts) {
@@ -53,12 +146,46 @@ const VlanAllocationCalculator = () => {
                     vlan_id: vlanId,
                     vlan_name: newVlanName,
                     required_hosts: Number(newRequiredHosts),
+                    access_ports: splitList(newAccessPorts),
                 },
             ]);
             setNewVlanId("");
             setNewVlanName("");
             setNewRequiredHosts("");
+            setNewAccessPorts("");
+        }
+    };
+
+    const addTrunk = () => {
+        const trunkInterface = newTrunkInterface.trim();
+        if (!trunkInterface) {
+            return;
+        }
+        if (trunks.some((t) => t.interface === trunkInterface)) {
+            alert("Trunk already exists");
+            return;
         }
+        const allowedVlans = splitList(newTrunkAllowed).map(Number);
+        if (allowedVlans.some((id) => !Number.isInteger(id))) {
+            alert("Allowed VLANs must be a comma-separated list of VLAN IDs");
+            return;
+        }
+
+        setTrunks([
+            ...trunks,
+            {
+                interface: trunkInterface,
+                allowed_vlans: allowedVlans,
+                native_vlan: newTrunkNative ? Number(newTrunkNative) : null,
+            },
+        ]);
+        setNewTrunkInterface("");
+        setNewTrunkAllowed("");
+        setNewTrunkNative("");
+    };
+
+    const removeTrunk = (trunkInterface: string) => {
+        setTrunks(trunks.filter((trunk) => trunk.interface !== trunkInterface));
     };
 
     const removeVlan = (vlanId: number) => {
@@ -71,38 +198,119 @@ const VlanAllocationCalculator = () => {
             return;
         }
 
+        const input = {
+            base_network: baseNetwork,
+            base_cidr: baseCidr,
+            vlan_configs: vlanConfigs,
+            trunks,
+            platform,
+            dhcp:
+                dhcpMode === "off"
+                    ? null
+                    : {
+                          mode: dhcpMode,
+                          server: dhcpMode === "relay" ? dhcpServer : null,
+                          dns_servers: splitList(dnsServers),
+                          domain_name: domainName.trim() || null,
+                          lease_time: Number(leaseTime),
+                          excluded_addresses: Number(excludedAddresses),
+                      },
+            redundancy: redundancy === "none" ? null : { protocol: redundancy, routers },
+        };
+
         try {
             const response = await invoke<{
                 allocations: VlanAllocation[];
-                cisco_commands: string | null;
-                error: string | null;
-            }>("calculate_vlan_allocation", {
-                input: {
-                    base_network: baseNetwork,
-                    base_cidr: Number(baseCidr),
-                    vlan_configs: vlanConfigs,
-                },
-            });
+                trunks: TrunkPort[];
+                platform: Platform;
+                commands: string;
+                unused: UnusedSpace;
+                warnings: Diagnostic[];
+            }>("calculate_vlan_allocation", { input });
 
-            if (response.error) {
-                alert(response.error);
+            setResult(response.allocations);
+            setCommands(response.commands);
+            setCommandsPlatform(response.platform);
+            setLinuxFiles([]);
+            setUplinkVlan("");
+            setDhcpConfig("");
+            setUnused(response.unused);
+            setDiagnostics(response.warnings);
+            setCalculatedPlan({ input, trunks: response.trunks });
+        } catch (error) {
+            if (isDikaiosError(error) && error.diagnostics) {
+                setResult([]);
+                setUnused(null);
+                setCalculatedPlan(null);
+                setCommands("");
+                setDiagnostics(error.diagnostics);
                 return;
             }
+            alert(errorMessage(error));
+        }
+    };
 
-            setResult(response.allocations);
-            setCiscoCommands(response.cisco_commands || "");
+    const saveToProject = () => {
+        if (!calculatedPlan || !unused) {
+            return;
+        }
+        const name = prompt("Name of the plan in the project:", `${baseNetwork}/${baseCidr}`);
+        if (name !== null) {
+            addToProject("vlans", { name, ...calculatedPlan, allocations: result, commands, unused });
+        }
+    };
+
+    const commitToIpam = async () => {
+        if (!calculatedPlan) {
+            return;
+        }
+        try {
+            const committed = await invoke<CommittedPlan>("ipam_commit_vlan_plan", { input: calculatedPlan.input });
+            alert(
+                `Stored ${committed.vlans.length} VLANs and ${committed.subnets.length} subnets in ${committed.block.prefix}`,
+            );
         } catch (error) {
-            if (error instanceof Error) {
-                alert(error.message);
-            }
+            alert(errorMessage(error));
         }
     };
 
-    const copyCiscoCommands = () => {
-        navigator.clipboard.writeText(ciscoCommands);
-        alert("Cisco commands copied to clipboard!");
+    const copyCommands = () => {
+        navigator.clipboard.writeText(commands);
+        alert("Commands copied to clipboard!");
+    };
+
+    const exportLinuxConfig = async () => {
+        try {
+            const files = await invoke<ConfigFile[]>("export_linux_network_config", {
+                input: {
+                    allocations: result,
+                    parent_interface: parentInterface,
+                    uplink_vlan: uplinkVlan ? Number(uplinkVlan) : null,
+                    format: linuxFormat,
+                },
+            });
+            setLinuxFiles(files);
+        } catch (error) {
+            alert(errorMessage(error));
+        }
     };
 
+    const exportDhcpConfig = async () => {
+        try {
+            const config = await invoke<string>("export_dhcp_server_config", {
+                input: {
+                    allocations: result,
+                    server: dhcpServerFormat,
+                },
+            });
+            setDhcpConfig(config);
+        } catch (error) {
+            alert(errorMessage(error));
+        }
+    };
+
+    const platformName = PLATFORMS.find((p) => p.id === commandsPlatform)?.name;
+
     return (
         <main>
             <div className="p-4 flex flex-col gap-4">
@@ -121,7 +329,8 @@ const VlanAllocationCalculator = () => {
                 </a>
                 <h2 className="text-2xl font-bold">VLAN Allocation Calculator</h2>
                 <p className="text-sm text-muted-foreground">
-                    Allocate VLANs with IP addresses, subnet masks, and gateways. Generate Cisco Packet Tracer commands.
+                    Allocate VLANs with IP addresses, subnet masks, and gateways. Generate the configuration for Cisco
+                    IOS, Juniper Junos, Arista EOS, MikroTik RouterOS or VyOS.
                 </p>
 
                 <div className="flex flex-row gap-8">
@@ -140,14 +349,27 @@ const VlanAllocationCalculator = () => {
                             <Label htmlFor="baseCidr">Base Network CIDR</Label>
                             <Input
                                 id="baseCidr"
-                                type="number"
+                                type="text"
                                 value={baseCidr}
                                 onChange={(e) => setBaseCidr(e.target.value)}
                                 placeholder="16"
-                                min="0"
-                                max="32"
                             />
                         </div>
+                        <div>
+                            <Label htmlFor="platform">Platform</Label>
+                            <select
+                                id="platform"
+                                value={platform}
+                                onChange={(e) => setPlatform(e.target.value as Platform)}
+                                className="flex h-9 w-full border border-input bg-transparent px-3 py-1 text-sm shadow-sm"
+                            >
+                                {PLATFORMS.map((p) => (
+                                    <option key={p.id} value={p.id}>
+                                        {p.name}
+                                    </option>
+                                ))}
+                            </select>
+                        </div>
 
                         <div className="border-t pt-4">
                             <Label>VLAN Configurations</Label>
@@ -173,6 +395,12 @@ const VlanAllocationCalculator = () => {
                                     placeholder="Required Hosts"
                                     min="1"
                                 />
+                                <Input
+                                    type="text"
+                                    value={newAccessPorts}
+                                    onChange={(e) => setNewAccessPorts(e.target.value)}
+                                    placeholder="Access ports, e.g. Gi1/0/1-12, Gi1/0/20"
+                                />
                                 <Button variant="outline" size="sm" onClick={addVlan}>
                                     <Plus className="w-4 h-4 mr-2" />
                                     Add VLAN
@@ -189,6 +417,11 @@ const VlanAllocationCalculator = () => {
                                                 VLAN {vlan.vlan_id}: {vlan.vlan_name}
                                             </div>
                                             <div className="text-muted-foreground">{vlan.required_hosts} hosts</div>
+                                            {vlan.access_ports.length > 0 && (
+                                                <div className="text-muted-foreground">
+                                                    Access: {vlan.access_ports.join(", ")}
+                                                </div>
+                                            )}
                                         </div>
                                         <Button variant="ghost" size="sm" onClick={() => removeVlan(vlan.vlan_id)}>
                                             <Trash2 className="w-4 h-4 text-destructive" />
@@ -198,12 +431,173 @@ const VlanAllocationCalculator = () => {
                             </div>
                         </div>
 
+                        <div className="border-t pt-4">
+                            <Label>Trunk Ports</Label>
+                            <div className="flex flex-col gap-2 mt-2">
+                                <Input
+                                    type="text"
+                                    value={newTrunkInterface}
+                                    onChange={(e) => setNewTrunkInterface(e.target.value)}
+                                    placeholder="Interface, e.g. Gi1/0/48"
+                                />
+                                <Input
+                                    type="text"
+                                    value={newTrunkAllowed}
+                                    onChange={(e) => setNewTrunkAllowed(e.target.value)}
+                                    placeholder="Allowed VLANs, e.g. 10, 20 (empty for all)"
+                                />
+                                <Input
+                                    type="number"
+                                    value={newTrunkNative}
+                                    onChange={(e) => setNewTrunkNative(e.target.value)}
+                                    placeholder="Native VLAN (optional)"
+                                    min="1"
+                                    max="4094"
+                                />
+                                <Button variant="outline" size="sm" onClick={addTrunk}>
+                                    <Plus className="w-4 h-4 mr-2" />
+                                    Add Trunk
+                                </Button>
+                            </div>
+                            <div className="mt-4 space-y-2">
+                                {trunks.map((trunk) => (
+                                    <div
+                                        key={`trunk-${trunk.interface}`}
+                                        className="flex items-center justify-between border p-2 rounded"
+                                    >
+                                        <div className="text-sm">
+                                            <div className="font-medium">{trunk.interface}</div>
+                                            <div className="text-muted-foreground">
+                                                {trunk.allowed_vlans.length > 0
+                                                    ? `VLANs ${trunk.allowed_vlans.join(", ")}`
+                                                    : "All VLANs"}
+                                                {trunk.native_vlan !== null && `, native ${trunk.native_vlan}`}
+                                            </div>
+                                        </div>
+                                        <Button variant="ghost" size="sm" onClick={() => removeTrunk(trunk.interface)}>
+                                            <Trash2 className="w-4 h-4 text-destructive" />
+                                        </Button>
+                                    </div>
+                                ))}
+                            </div>
+                        </div>
+
+                        <div className="border-t pt-4">
+                            <Label htmlFor="dhcpMode">DHCP</Label>
+                            <div className="flex flex-col gap-2 mt-2">
+                                <select
+                                    id="dhcpMode"
+                                    value={dhcpMode}
+                                    onChange={(e) => setDhcpMode(e.target.value as DhcpMode)}
+                                    className="flex h-9 w-full border border-input bg-transparent px-3 py-1 text-sm shadow-sm"
+                                >
+                                    <option value="off">No DHCP</option>
+                                    <option value="pool">DHCP pools on the switch</option>
+                                    <option value="relay">Relay to a central server</option>
+                                </select>
+                                {dhcpMode === "relay" && (
+                                    <Input
+                                        type="text"
+                                        value={dhcpServer}
+                                        onChange={(e) => setDhcpServer(e.target.value)}
+                                        placeholder="DHCP server address"
+                                    />
+                                )}
+                                {dhcpMode !== "off" && (
+                                    <>
+                                        <Input
+                                            type="text"
+                                            value={dnsServers}
+                                            onChange={(e) => setDnsServers(e.target.value)}
+                                            placeholder="DNS servers, e.g. 1.1.1.1, 9.9.9.9"
+                                        />
+                                        <Input
+                                            type="text"
+                                            value={domainName}
+                                            onChange={(e) => setDomainName(e.target.value)}
+                                            placeholder="Domain name (optional)"
+                                        />
+                                        <Label htmlFor="leaseTime">Lease Time (seconds)</Label>
+                                        <Input
+                                            id="leaseTime"
+                                            type="number"
+                                            value={leaseTime}
+                                            onChange={(e) => setLeaseTime(e.target.value)}
+                                            min="60"
+                                        />
+                                        <Label htmlFor="excludedAddresses">Static Addresses After the Gateway</Label>
+                                        <Input
+                                            id="excludedAddresses"
+                                            type="number"
+                                            value={excludedAddresses}
+                                            onChange={(e) => setExcludedAddresses(e.target.value)}
+                                            min="0"
+                                        />
+                                    </>
+                                )}
+                            </div>
+                        </div>
+
+                        <div className="border-t pt-4">
+                            <Label htmlFor="redundancy">Gateway Redundancy</Label>
+                            <div className="flex flex-col gap-2 mt-2">
+                                <select
+                                    id="redundancy"
+                                    value={redundancy}
+                                    onChange={(e) => setRedundancy(e.target.value as Redundancy)}
+                                    className="flex h-9 w-full border border-input bg-transparent px-3 py-1 text-sm shadow-sm"
+                                >
+                                    <option value="none">Single layer-3 switch</option>
+                                    <option value="hsrp">HSRP pair</option>
+                                    <option value="vrrp">VRRP pair</option>
+                                    <option value="glbp">GLBP pair</option>
+                                </select>
+                                {redundancy !== "none" && (
+                                    <div className="flex gap-2">
+                                        <Input
+                                            type="text"
+                                            value={routers[0]}
+                                            onChange={(e) => setRouters([e.target.value, routers[1]])}
+                                            placeholder="First router"
+                                        />
+                                        <Input
+                                            type="text"
+                                            value={routers[1]}
+                                            onChange={(e) => setRouters([routers[0], e.target.value])}
+                                            placeholder="Second router"
+                                        />
+                                    </div>
+                                )}
+                            </div>
+                        </div>
+
                         <Button variant="default" type="button" onClick={calculateVlan}>
                             Calculate VLAN Allocation
                         </Button>
                     </div>
 
                     <div className="flex-1 flex flex-col gap-4">
+                        {diagnostics.length > 0 && (
+                            <div className="space-y-2">
+                                {diagnostics.map((diagnostic) => (
+                                    <div
+                                        key={`${diagnostic.field}-${diagnostic.vlan_id}-${diagnostic.message}`}
+                                        className={cn(
+                                            "border p-3 text-sm",
+                                            diagnostic.severity === "error" ? "border-destructive" : "border-yellow-500",
+                                        )}
+                                    >
+                                        <div className="font-medium">
+                                            {diagnostic.severity === "error" ? "Error" : "Warning"}: {diagnostic.message}
+                                        </div>
+                                        {diagnostic.suggestion && (
+                                            <div className="text-muted-foreground">{diagnostic.suggestion}</div>
+                                        )}
+                                    </div>
+                                ))}
+                            </div>
+                        )}
+
                         <div>
                             <h3 className="text-lg font-semibold mb-4">VLAN Allocations:</h3>
                             {result.length > 0 ? (
@@ -222,9 +616,21 @@ const VlanAllocationCalculator = () => {
                                                 <span className="font-medium">Subnet Mask:</span>
                                                 <span>{vlan.subnet_mask}</span>
 
-                                                <span className="font-medium">Gateway:</span>
+                                                <span className="font-medium">
+                                                    {vlan.redundancy ? "Virtual Gateway:" : "Gateway:"}
+                                                </span>
                                                 <span className="text-green-600 font-medium">{vlan.gateway}</span>
 
+                                                {vlan.redundancy && (
+                                                    <>
+                                                        <span className="font-medium">Router Addresses:</span>
+                                                        <span>
+                                                            {vlan.redundancy.router_addresses.join(", ")} (active:{" "}
+                                                            {routers[vlan.redundancy.active_router]})
+                                                        </span>
+                                                    </>
+                                                )}
+
                                                 <span className="font-medium">First Host:</span>
                                                 <span>{vlan.first_host}</span>
 
@@ -236,9 +642,24 @@ const VlanAllocationCalculator = () => {
 
                                                 <span className="font-medium">Usable Hosts:</span>
                                                 <span>{vlan.usable_hosts}</span>
+
+                                                {vlan.dhcp && (
+                                                    <>
+                                                        <span className="font-medium">DHCP Pool:</span>
+                                                        <span>
+                                                            {vlan.dhcp.pool_start} - {vlan.dhcp.pool_end}
+                                                        </span>
+                                                    </>
+                                                )}
                                             </div>
                                         </div>
                                     ))}
+                                    {unused && (
+                                        <div className="border p-4 text-sm">
+                                            <span className="font-medium">Unused Space: </span>
+                                            <span>{describeUnused(unused)}</span>
+                                        </div>
+                                    )}
                                 </div>
                             ) : (
                                 <div className="border p-4 text-center text-muted-foreground">
@@ -247,20 +668,105 @@ const VlanAllocationCalculator = () => {
                             )}
                         </div>
 
-                        {ciscoCommands && (
+                        {commands && (
                             <div className="border-t pt-4">
                                 <div className="flex items-center justify-between mb-2">
-                                    <h3 className="text-lg font-semibold">Cisco Packet Tracer Commands:</h3>
-                                    <Button variant="outline" size="sm" onClick={copyCiscoCommands}>
-                                        <Copy className="w-4 h-4 mr-2" />
-                                        Copy Commands
-                                    </Button>
+                                    <h3 className="text-lg font-semibold">{platformName} Configuration:</h3>
+                                    <div className="flex gap-2">
+                                        <Button variant="outline" size="sm" onClick={saveToProject}>
+                                            <FolderPlus className="w-4 h-4 mr-2" />
+                                            Add to Project
+                                        </Button>
+                                        <Button variant="outline" size="sm" onClick={commitToIpam}>
+                                            <Database className="w-4 h-4 mr-2" />
+                                            Commit to IPAM
+                                        </Button>
+                                        <Button variant="outline" size="sm" onClick={copyCommands}>
+                                            <Copy className="w-4 h-4 mr-2" />
+                                            Copy Commands
+                                        </Button>
+                                    </div>
                                 </div>
                                 <pre className="border p-4 bg-muted rounded text-xs overflow-x-auto max-h-64 overflow-y-auto">
-                                    {ciscoCommands}
+                                    {commands}
                                 </pre>
                             </div>
                         )}
+
+                        {result.some((vlan) => vlan.dhcp) && (
+                            <div className="border-t pt-4 space-y-2">
+                                <h3 className="text-lg font-semibold">DHCP Server Configuration:</h3>
+                                <div className="flex gap-2">
+                                    <select
+                                        value={dhcpServerFormat}
+                                        onChange={(e) => setDhcpServerFormat(e.target.value as DhcpServer)}
+                                        className="flex h-9 w-full border border-input bg-transparent px-3 py-1 text-sm shadow-sm"
+                                    >
+                                        {DHCP_SERVERS.map((server) => (
+                                            <option key={server.id} value={server.id}>
+                                                {server.name}
+                                            </option>
+                                        ))}
+                                    </select>
+                                    <Button variant="outline" size="sm" onClick={exportDhcpConfig}>
+                                        Generate
+                                    </Button>
+                                </div>
+                                {dhcpConfig && (
+                                    <pre className="border p-4 bg-muted rounded text-xs overflow-x-auto max-h-64 overflow-y-auto">
+                                        {dhcpConfig}
+                                    </pre>
+                                )}
+                            </div>
+                        )}
+
+                        {result.length > 0 && (
+                            <div className="border-t pt-4 space-y-2">
+                                <h3 className="text-lg font-semibold">Linux Host Configuration:</h3>
+                                <div className="flex gap-2">
+                                    <Input
+                                        type="text"
+                                        value={parentInterface}
+                                        onChange={(e) => setParentInterface(e.target.value)}
+                                        placeholder="Parent interface, e.g. eth0"
+                                    />
+                                    <select
+                                        value={uplinkVlan}
+                                        onChange={(e) => setUplinkVlan(e.target.value)}
+                                        className="flex h-9 w-full border border-input bg-transparent px-3 py-1 text-sm shadow-sm"
+                                    >
+                                        <option value="">No default route</option>
+                                        {result.map((vlan) => (
+                                            <option key={vlan.vlan_id} value={vlan.vlan_id}>
+                                                Default route via VLAN {vlan.vlan_id}
+                                            </option>
+                                        ))}
+                                    </select>
+                                    <select
+                                        value={linuxFormat}
+                                        onChange={(e) => setLinuxFormat(e.target.value as LinuxFormat)}
+                                        className="flex h-9 w-full border border-input bg-transparent px-3 py-1 text-sm shadow-sm"
+                                    >
+                                        {LINUX_FORMATS.map((f) => (
+                                            <option key={f.id} value={f.id}>
+                                                {f.name}
+                                            </option>
+                                        ))}
+                                    </select>
+                                    <Button variant="outline" size="sm" onClick={exportLinuxConfig}>
+                                        Generate
+                                    </Button>
+                                </div>
+                                {linuxFiles.map((file) => (
+                                    <div key={file.path}>
+                                        <div className="font-mono text-sm font-medium">{file.path}</div>
+                                        <pre className="border p-4 bg-muted rounded text-xs overflow-x-auto max-h-64 overflow-y-auto">
+                                            {file.contents}
+                                        </pre>
+                                    </div>
+                                ))}
+                            </div>
+                        )}
                     </div>
                 </div>
             </div>
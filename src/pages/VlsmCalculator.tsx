@@ -1,38 +1,80 @@
 import { Button, buttonVariants } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Label } from "@/components/ui/label";
+import { errorMessage } from "@/lib/errors";
+import type { CommittedPlan } from "@/lib/ipam";
+import { addToProject } from "@/lib/project";
+import { type UnusedSpace, describeUnused } from "@/lib/unused";
 import { cn, randomId } from "@/lib/utils";
 import { invoke } from "@tauri-apps/api/core";
-import { ArrowLeft, Plus, Trash2 } from "lucide-react";
+import { ArrowLeft, Database, FolderPlus, Plus, Trash2 } from "lucide-react";
 import { useState } from "react";
 
 type VlsmSubnet = {
+    name: string;
+    index: number;
+    family: "ipv4" | "ipv6";
     network: string;
     cidr: number;
     subnet_mask: string;
     first_host: string;
     last_host: string;
-    broadcast: string;
-    required_hosts: number;
-    usable_hosts: number;
+    broadcast: string | null;
+    required_hosts: number | null;
+    planned_hosts: number | null;
+    reserved_hosts: number | null;
+    usable_hosts: number | null;
+    address_count: string;
 };
 
+// For IPv4 the value is a host count, optionally with its own growth such
+// as "+30%". For IPv6 it is either a prefix length written as "/56" or a
+// number of /64 subnets.
 type HostRequirement = {
     id: string;
-    hosts: number;
+    name: string;
+    value: string;
+    growth: string;
 };
 
+type Ipv6Requirement = { name: string } & ({ prefix_length: number } | { subnets_64: number });
+
+const toIpv6Requirement = ({ name, value }: HostRequirement): Ipv6Requirement =>
+    value.startsWith("/") ? { name, prefix_length: Number(value.slice(1)) } : { name, subnets_64: Number(value) };
+
 const VlsmCalculator = () => {
     const [baseNetwork, setBaseNetwork] = useState("");
     const [baseCidr, setBaseCidr] = useState("");
     const [hostRequirements, setHostRequirements] = useState<HostRequirement[]>([]);
     const [newRequirement, setNewRequirement] = useState("");
+    const [newRequirementName, setNewRequirementName] = useState("");
+    const [newRequirementGrowth, setNewRequirementGrowth] = useState("");
+    const [growth, setGrowth] = useState("");
+    const [reserved, setReserved] = useState("");
+    const [pointToPoint, setPointToPoint] = useState(false);
+    const [inputOrder, setInputOrder] = useState(false);
     const [result, setResult] = useState<VlsmSubnet[]>([]);
+    const [unused, setUnused] = useState<UnusedSpace | null>(null);
+    const [calculatedInput, setCalculatedInput] = useState<object | null>(null);
+
+    const isIpv6 = baseNetwork.includes(":");
 
     const addRequirement = () => {
-        if (newRequirement && Number(newRequirement) > 0) {
-            setHostRequirements([...hostRequirements, { id: randomId(), hosts: Number(newRequirement) }]);
+        const value = newRequirement.trim();
+        const isPrefix = isIpv6 && /^\/\d+$/.test(value);
+        if (isPrefix || (value && Number(value) > 0)) {
+            setHostRequirements([
+                ...hostRequirements,
+                {
+                    id: randomId(),
+                    name: newRequirementName.trim(),
+                    value,
+                    growth: isIpv6 ? "" : newRequirementGrowth.trim(),
+                },
+            ]);
             setNewRequirement("");
+            setNewRequirementName("");
+            setNewRequirementGrowth("");
         }
     };
 
@@ -46,25 +88,54 @@ const VlsmCalculator = () => {
             return;
         }
 
+        const input = {
+            base_network: baseNetwork,
+            base_cidr: baseCidr,
+            host_requirements: isIpv6
+                ? []
+                : hostRequirements.map((req) => ({
+                      name: req.name,
+                      hosts: Number(req.value),
+                      growth: req.growth || null,
+                  })),
+            ipv6_requirements: isIpv6 ? hostRequirements.map(toIpv6Requirement) : [],
+            growth: growth.trim() || "none",
+            reserved: Number(reserved) || 0,
+            point_to_point: pointToPoint,
+        };
+
         try {
-            const response = await invoke<{ subnets: VlsmSubnet[]; error: string | null }>("calculate_vlsm", {
-                input: {
-                    base_network: baseNetwork,
-                    base_cidr: Number(baseCidr),
-                    host_requirements: hostRequirements.map((req) => req.hosts),
-                },
+            const response = await invoke<{ subnets: VlsmSubnet[]; unused: UnusedSpace }>("calculate_vlsm", {
+                input,
             });
 
-            if (response.error) {
-                alert(response.error);
-                return;
-            }
-
             setResult(response.subnets);
+            setUnused(response.unused);
+            setCalculatedInput(input);
         } catch (error) {
-            if (error instanceof Error) {
-                alert(error.message);
-            }
+            alert(errorMessage(error));
+        }
+    };
+
+    const saveToProject = () => {
+        if (!calculatedInput || !unused) {
+            return;
+        }
+        const name = prompt("Name of the plan in the project:", `${baseNetwork}/${baseCidr}`);
+        if (name !== null) {
+            addToProject("vlsm", { name, input: calculatedInput, subnets: result, unused });
+        }
+    };
+
+    const commitToIpam = async () => {
+        if (!calculatedInput) {
+            return;
+        }
+        try {
+            const committed = await invoke<CommittedPlan>("ipam_commit_vlsm", { input: calculatedInput });
+            alert(`Stored ${committed.subnets.length} subnets in ${committed.block.prefix}`);
+        } catch (error) {
+            alert(errorMessage(error));
         }
     };
 
@@ -99,32 +170,81 @@ const VlsmCalculator = () => {
                                 type="text"
                                 value={baseNetwork}
                                 onChange={(e) => setBaseNetwork(e.target.value)}
-                                placeholder="192.168.1.0"
+                                placeholder="192.168.1.0 or 2001:db8::"
                             />
                         </div>
                         <div>
                             <Label htmlFor="baseCidr">Base Network CIDR</Label>
                             <Input
                                 id="baseCidr"
-                                type="number"
+                                type="text"
                                 value={baseCidr}
                                 onChange={(e) => setBaseCidr(e.target.value)}
-                                placeholder="24"
-                                min="0"
-                                max="32"
+                                placeholder={isIpv6 ? "48" : "24"}
                             />
                         </div>
+                        {!isIpv6 && (
+                            <div className="flex gap-2">
+                                <div className="flex-1">
+                                    <Label htmlFor="growth">Growth</Label>
+                                    <Input
+                                        id="growth"
+                                        type="text"
+                                        value={growth}
+                                        onChange={(e) => setGrowth(e.target.value)}
+                                        placeholder="+30% or double"
+                                    />
+                                </div>
+                                <div className="flex-1">
+                                    <Label htmlFor="reserved">Reserved Addresses</Label>
+                                    <Input
+                                        id="reserved"
+                                        type="number"
+                                        value={reserved}
+                                        onChange={(e) => setReserved(e.target.value)}
+                                        placeholder="0"
+                                        min="0"
+                                    />
+                                </div>
+                            </div>
+                        )}
+                        {!isIpv6 && (
+                            <div className="flex items-center gap-2">
+                                <input
+                                    type="checkbox"
+                                    id="pointToPoint"
+                                    checked={pointToPoint}
+                                    onChange={(e) => setPointToPoint(e.target.checked)}
+                                    className="w-4 h-4"
+                                />
+                                <Label htmlFor="pointToPoint">Use /31 for 2-host links and /32 for loopbacks</Label>
+                            </div>
+                        )}
 
                         <div className="border-t pt-4">
-                            <Label>Host Requirements</Label>
+                            <Label>{isIpv6 ? "Subnet Requirements" : "Host Requirements"}</Label>
                             <div className="flex gap-2 mt-2">
                                 <Input
-                                    type="number"
+                                    type="text"
+                                    value={newRequirementName}
+                                    onChange={(e) => setNewRequirementName(e.target.value)}
+                                    placeholder="Name (optional)"
+                                />
+                                <Input
+                                    type={isIpv6 ? "text" : "number"}
                                     value={newRequirement}
                                     onChange={(e) => setNewRequirement(e.target.value)}
-                                    placeholder="Number of hosts"
+                                    placeholder={isIpv6 ? "/56 or number of /64s" : "Number of hosts"}
                                     min="1"
                                 />
+                                {!isIpv6 && (
+                                    <Input
+                                        type="text"
+                                        value={newRequirementGrowth}
+                                        onChange={(e) => setNewRequirementGrowth(e.target.value)}
+                                        placeholder="Growth (optional)"
+                                    />
+                                )}
                                 <Button variant="outline" size="sm" onClick={addRequirement}>
                                     <Plus className="w-4 h-4" />
                                 </Button>
@@ -132,7 +252,14 @@ const VlsmCalculator = () => {
                             <div className="mt-4 space-y-2">
                                 {hostRequirements.map((req) => (
                                     <div key={req.id} className="flex items-center justify-between border p-2 rounded">
-                                        <span>{req.hosts} hosts</span>
+                                        <span>
+                                            {req.name && <span className="font-medium">{req.name}: </span>}
+                                            {!isIpv6
+                                                ? `${req.value} hosts${req.growth ? ` + ${req.growth}` : ""}`
+                                                : req.value.startsWith("/")
+                                                  ? `${req.value} prefix`
+                                                  : `${req.value} × /64`}
+                                        </span>
                                         <Button variant="ghost" size="sm" onClick={() => removeRequirement(req.id)}>
                                             <Trash2 className="w-4 h-4 text-destructive" />
                                         </Button>
@@ -147,23 +274,64 @@ const VlsmCalculator = () => {
                     </div>
 
                     <div className="flex-1">
-                        <h3 className="text-lg font-semibold mb-4">Subnet Allocations:</h3>
+                        <div className="flex flex-row items-center justify-between mb-4">
+                            <h3 className="text-lg font-semibold">Subnet Allocations:</h3>
+                            {result.length > 0 && (
+                                <div className="flex gap-2">
+                                    <Button variant="outline" size="sm" onClick={saveToProject}>
+                                        <FolderPlus className="w-4 h-4" />
+                                        Add to Project
+                                    </Button>
+                                    <Button variant="outline" size="sm" onClick={commitToIpam}>
+                                        <Database className="w-4 h-4" />
+                                        Commit to IPAM
+                                    </Button>
+                                    <Button variant="outline" size="sm" onClick={() => setInputOrder(!inputOrder)}>
+                                        {inputOrder ? "Show allocation order" : "Show input order"}
+                                    </Button>
+                                </div>
+                            )}
+                        </div>
                         {result.length > 0 ? (
                             <div className="space-y-4">
-                                {result.map((subnet, index) => (
+                                {(inputOrder ? [...result].sort((a, b) => a.index - b.index) : result).map((subnet) => (
                                     <div
                                         key={`subnet-${subnet.network}-${subnet.cidr}`}
                                         className="border p-4 space-y-2"
                                     >
                                         <div className="font-semibold text-primary">
-                                            Subnet {index + 1}: {subnet.network}/{subnet.cidr}
+                                            {subnet.name || `Subnet ${subnet.index + 1}`}: {subnet.network}/
+                                            {subnet.cidr}
                                         </div>
                                         <div className="grid grid-cols-2 gap-2 text-sm">
-                                            <span className="font-medium">Required Hosts:</span>
-                                            <span>{subnet.required_hosts}</span>
+                                            {subnet.required_hosts !== null && (
+                                                <>
+                                                    <span className="font-medium">Required Hosts:</span>
+                                                    <span>{subnet.required_hosts}</span>
+                                                </>
+                                            )}
 
-                                            <span className="font-medium">Usable Hosts:</span>
-                                            <span>{subnet.usable_hosts}</span>
+                                            {subnet.planned_hosts !== null && (
+                                                <>
+                                                    <span className="font-medium">Planned Hosts:</span>
+                                                    <span>
+                                                        {subnet.planned_hosts}
+                                                        {subnet.reserved_hosts ? ` (${subnet.reserved_hosts} reserved)` : ""}
+                                                    </span>
+                                                </>
+                                            )}
+
+                                            {subnet.usable_hosts !== null && (
+                                                <>
+                                                    <span className="font-medium">Usable Hosts:</span>
+                                                    <span>{subnet.usable_hosts}</span>
+                                                </>
+                                            )}
+
+                                            <span className="font-medium">Addresses:</span>
+                                            <span className="break-all">
+                                                {BigInt(subnet.address_count).toLocaleString()}
+                                            </span>
 
                                             <span className="font-medium">Network:</span>
                                             <span>{subnet.network}</span>
@@ -177,11 +345,21 @@ const VlsmCalculator = () => {
                                             <span className="font-medium">Last Host:</span>
                                             <span>{subnet.last_host}</span>
 
-                                            <span className="font-medium">Broadcast:</span>
-                                            <span>{subnet.broadcast}</span>
+                                            {subnet.broadcast && (
+                                                <>
+                                                    <span className="font-medium">Broadcast:</span>
+                                                    <span>{subnet.broadcast}</span>
+                                                </>
+                                            )}
                                         </div>
                                     </div>
                                 ))}
+                                {unused && (
+                                    <div className="border p-4 text-sm">
+                                        <span className="font-medium">Unused Space: </span>
+                                        <span className="break-all">{describeUnused(unused)}</span>
+                                    </div>
+                                )}
                             </div>
                         ) : (
                             <div className="border p-4 text-center text-muted-foreground">
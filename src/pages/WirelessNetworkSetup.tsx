@@ -1,11 +1,23 @@
 import { Button, buttonVariants } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Label } from "@/components/ui/label";
+import { type Diagnostic, errorMessage, isDikaiosError } from "@/lib/errors";
+import { addToProject } from "@/lib/project";
 import { cn } from "@/lib/utils";
 import { invoke } from "@tauri-apps/api/core";
-import { ArrowLeft, Copy, Radio } from "lucide-react";
+import { ArrowLeft, Copy, FolderPlus, Printer, Radio } from "lucide-react";
 import { useState } from "react";
 
+type WirelessSecurity = "wpa2" | "wpa3" | "wpa2_wpa3";
+
+const SECURITY_MODES: { id: WirelessSecurity; name: string }[] = [
+    { id: "wpa2", name: "WPA2-PSK" },
+    { id: "wpa3", name: "WPA3-SAE" },
+    { id: "wpa2_wpa3", name: "WPA2/WPA3 transition" },
+];
+
+type CiscoSecretType = "type8" | "type9";
+
 type WirelessNetworkConfig = {
     network: string;
     cidr: number;
@@ -17,9 +29,25 @@ type WirelessNetworkConfig = {
     broadcast: string;
     usable_hosts: number;
     ssid: string;
+    security: WirelessSecurity;
+    /** The 256-bit WPA pre-shared key derived from the passphrase, in hex. */
+    psk: string;
+    /** Whether the configuration was generated with the passphrase masked. */
+    masked: boolean;
     router_commands: string;
     ap_commands: string;
     setup_steps: string[];
+    hostapd_conf: string;
+    dnsmasq_conf: string;
+    kea_config: string;
+    warnings: Diagnostic[];
+};
+
+type WifiOnboarding = {
+    /** The `WIFI:` payload encoded in the QR code; carries the passphrase. */
+    payload: string;
+    qr_svg: string;
+    card_html: string;
 };
 
 const WirelessNetworkSetup = () => {
@@ -30,7 +58,17 @@ const WirelessNetworkSetup = () => {
     const [wpaPassword, setWpaPassword] = useState("");
     const [routerHostname, setRouterHostname] = useState("WirelessRouter");
     const [apHostname, setApHostname] = useState("AccessPoint");
+    const [security, setSecurity] = useState<WirelessSecurity>("wpa2");
+    const [countryCode, setCountryCode] = useState("US");
+    const [channel, setChannel] = useState("6");
+    const [wlanInterface, setWlanInterface] = useState("wlan0");
+    const [maskPassphrase, setMaskPassphrase] = useState(true);
+    const [enableSecret, setEnableSecret] = useState("");
+    const [secretType, setSecretType] = useState<CiscoSecretType>("type9");
+    const [linuxDhcp, setLinuxDhcp] = useState<"dnsmasq" | "kea">("dnsmasq");
     const [result, setResult] = useState<WirelessNetworkConfig | null>(null);
+    const [onboarding, setOnboarding] = useState<WifiOnboarding | null>(null);
+    const [diagnostics, setDiagnostics] = useState<Diagnostic[]>([]);
 
     const generateConfig = async () => {
         if (!ssid || !wpaPassword) {
@@ -43,40 +81,59 @@ const WirelessNetworkSetup = () => {
             return;
         }
 
-        if (wpaPassword.length < 8) {
-            alert("WPA password must be at least 8 characters long.");
-            return;
-        }
-
         try {
-            const response = await invoke<{
-                config: WirelessNetworkConfig | null;
-                error: string | null;
-            }>("configure_wireless_network", {
-                input: {
-                    base_network: autoGenerate ? null : baseNetwork,
-                    base_cidr: autoGenerate ? null : Number(baseCidr),
-                    auto_generate: autoGenerate,
-                    ssid: ssid,
-                    wpa_password: wpaPassword,
-                    router_hostname: routerHostname,
-                    ap_hostname: apHostname,
-                },
-            });
-
-            if (response.error) {
-                alert(response.error);
-                return;
-            }
+            const input = {
+                base_network: autoGenerate ? null : baseNetwork,
+                base_cidr: autoGenerate ? null : baseCidr,
+                auto_generate: autoGenerate,
+                ssid: ssid,
+                wpa_password: wpaPassword,
+                router_hostname: routerHostname,
+                ap_hostname: apHostname,
+                security: security,
+                country_code: countryCode,
+                channel: Number(channel),
+                interface: wlanInterface,
+                mask_passphrase: maskPassphrase,
+                enable_secret: enableSecret || null,
+                secret_type: secretType,
+            };
+            const response = await invoke<{ config: WirelessNetworkConfig }>("configure_wireless_network", { input });
 
-            if (response.config) {
-                setResult(response.config);
-            }
+            setResult(response.config);
+            setOnboarding(await invoke<WifiOnboarding>("create_wifi_onboarding", { input }));
+            setDiagnostics(response.config.warnings);
         } catch (error) {
-            if (error instanceof Error) {
-                alert(error.message);
+            if (isDikaiosError(error) && error.diagnostics) {
+                setResult(null);
+                setOnboarding(null);
+                setDiagnostics(error.diagnostics);
+                return;
             }
+            alert(errorMessage(error));
+        }
+    };
+
+    /** Adds the configuration to the project, asking first if it holds the passphrase in plain text. */
+    const addWirelessToProject = () => {
+        if (!result) {
+            return;
         }
+        if (!result.masked && !confirm("This configuration contains the passphrase. Save it in the project anyway?")) {
+            return;
+        }
+        addToProject("wireless", { name: result.ssid, config: result, keep_secrets: !result.masked });
+    };
+
+    const printCard = (cardHtml: string) => {
+        const frame = document.createElement("iframe");
+        frame.style.display = "none";
+        frame.srcdoc = cardHtml;
+        frame.onload = () => {
+            frame.contentWindow?.print();
+            setTimeout(() => frame.remove(), 1000);
+        };
+        document.body.appendChild(frame);
     };
 
     const copyToClipboard = async (text: string, type: string) => {
@@ -144,12 +201,10 @@ const WirelessNetworkSetup = () => {
                                         <Label htmlFor="baseCidr">CIDR Prefix</Label>
                                         <Input
                                             id="baseCidr"
-                                            type="number"
+                                            type="text"
                                             value={baseCidr}
                                             onChange={(e) => setBaseCidr(e.target.value)}
                                             placeholder="24"
-                                            min="16"
-                                            max="30"
                                         />
                                     </div>
                                 </>
@@ -169,7 +224,7 @@ const WirelessNetworkSetup = () => {
                                 />
                             </div>
                             <div>
-                                <Label htmlFor="wpaPassword">WPA2 Password (min 8 characters)</Label>
+                                <Label htmlFor="wpaPassword">WPA Passphrase (8-63 characters or 64 hex digits)</Label>
                                 <Input
                                     id="wpaPassword"
                                     type="password"
@@ -178,6 +233,65 @@ const WirelessNetworkSetup = () => {
                                     placeholder="SecurePassword123"
                                 />
                             </div>
+                            <div>
+                                <Label htmlFor="security">Security</Label>
+                                <select
+                                    id="security"
+                                    value={security}
+                                    onChange={(e) => setSecurity(e.target.value as WirelessSecurity)}
+                                    className="flex h-9 w-full border border-input bg-transparent px-3 py-1 text-sm shadow-sm"
+                                >
+                                    {SECURITY_MODES.map((mode) => (
+                                        <option key={mode.id} value={mode.id}>
+                                            {mode.name}
+                                        </option>
+                                    ))}
+                                </select>
+                            </div>
+                            <div className="flex items-center gap-2">
+                                <input
+                                    type="checkbox"
+                                    id="maskPassphrase"
+                                    checked={maskPassphrase}
+                                    onChange={(e) => setMaskPassphrase(e.target.checked)}
+                                    className="w-4 h-4"
+                                />
+                                <Label htmlFor="maskPassphrase">Hide the passphrase and show the WPA PSK instead</Label>
+                            </div>
+                        </div>
+
+                        <div className="border rounded p-4 space-y-4">
+                            <h3 className="font-semibold">Linux Access Point</h3>
+                            <div>
+                                <Label htmlFor="countryCode">Country Code</Label>
+                                <Input
+                                    id="countryCode"
+                                    type="text"
+                                    value={countryCode}
+                                    onChange={(e) => setCountryCode(e.target.value)}
+                                    placeholder="US"
+                                />
+                            </div>
+                            <div>
+                                <Label htmlFor="channel">Channel</Label>
+                                <Input
+                                    id="channel"
+                                    type="number"
+                                    value={channel}
+                                    onChange={(e) => setChannel(e.target.value)}
+                                    placeholder="6"
+                                />
+                            </div>
+                            <div>
+                                <Label htmlFor="wlanInterface">Wireless Interface</Label>
+                                <Input
+                                    id="wlanInterface"
+                                    type="text"
+                                    value={wlanInterface}
+                                    onChange={(e) => setWlanInterface(e.target.value)}
+                                    placeholder="wlan0"
+                                />
+                            </div>
                         </div>
 
                         <div className="border rounded p-4 space-y-4">
@@ -202,6 +316,27 @@ const WirelessNetworkSetup = () => {
                                     placeholder="AccessPoint"
                                 />
                             </div>
+                            <div>
+                                <Label htmlFor="enableSecret">Enable Secret (optional)</Label>
+                                <Input
+                                    id="enableSecret"
+                                    type="password"
+                                    value={enableSecret}
+                                    onChange={(e) => setEnableSecret(e.target.value)}
+                                />
+                            </div>
+                            <div>
+                                <Label htmlFor="secretType">Enable Secret Hash</Label>
+                                <select
+                                    id="secretType"
+                                    value={secretType}
+                                    onChange={(e) => setSecretType(e.target.value as CiscoSecretType)}
+                                    className="flex h-9 w-full border border-input bg-transparent px-3 py-1 text-sm shadow-sm"
+                                >
+                                    <option value="type9">Type 9 (scrypt)</option>
+                                    <option value="type8">Type 8 (PBKDF2-SHA256)</option>
+                                </select>
+                            </div>
                         </div>
 
                         <Button variant="default" type="button" onClick={generateConfig}>
@@ -210,10 +345,41 @@ const WirelessNetworkSetup = () => {
                     </div>
 
                     <div className="flex-1 flex flex-col gap-4">
+                        {diagnostics.length > 0 && (
+                            <div className="space-y-2">
+                                {diagnostics.map((diagnostic) => (
+                                    <div
+                                        key={`${diagnostic.field}-${diagnostic.message}`}
+                                        className={cn(
+                                            "border p-3 text-sm",
+                                            diagnostic.severity === "error" ? "border-destructive" : "border-yellow-500",
+                                        )}
+                                    >
+                                        <div className="font-medium">
+                                            {diagnostic.severity === "error" ? "Error" : "Warning"}: {diagnostic.message}
+                                        </div>
+                                        {diagnostic.suggestion && (
+                                            <div className="text-muted-foreground">{diagnostic.suggestion}</div>
+                                        )}
+                                    </div>
+                                ))}
+                            </div>
+                        )}
+
                         {result ? (
                             <>
                                 <div>
-                                    <h3 className="text-lg font-semibold mb-4">Network Information</h3>
+                                    <div className="flex items-center justify-between mb-4">
+                                        <h3 className="text-lg font-semibold">Network Information</h3>
+                                        <Button
+                                            variant="outline"
+                                            size="sm"
+                                            onClick={addWirelessToProject}
+                                        >
+                                            <FolderPlus className="w-4 h-4 mr-2" />
+                                            Add to Project
+                                        </Button>
+                                    </div>
                                     <div className="border p-4 space-y-2">
                                         <div className="grid grid-cols-2 gap-2 text-sm">
                                             <span className="font-medium">Network:</span>
@@ -243,10 +409,45 @@ const WirelessNetworkSetup = () => {
 
                                             <span className="font-medium">SSID:</span>
                                             <span className="font-medium text-primary">{result.ssid}</span>
+
+                                            <span className="font-medium">WPA PSK:</span>
+                                            <span className="font-mono text-xs break-all">{result.psk}</span>
+
+                                            <span className="font-medium">Security:</span>
+                                            <span>
+                                                {SECURITY_MODES.find((mode) => mode.id === result.security)?.name}
+                                            </span>
                                         </div>
                                     </div>
                                 </div>
 
+                                {onboarding && (
+                                    <div>
+                                        <div className="flex items-center justify-between mb-2">
+                                            <h3 className="text-lg font-semibold">Join QR Code</h3>
+                                            <Button
+                                                variant="outline"
+                                                size="sm"
+                                                onClick={() => printCard(onboarding.card_html)}
+                                            >
+                                                <Printer className="w-4 h-4 mr-2" />
+                                                Print Onboarding Card
+                                            </Button>
+                                        </div>
+                                        <div className="border p-4 flex items-center gap-4">
+                                            <img
+                                                src={`data:image/svg+xml;charset=utf-8,${encodeURIComponent(onboarding.qr_svg)}`}
+                                                alt={`QR code to join ${result.ssid}`}
+                                                className="w-40 h-40"
+                                            />
+                                            <p className="text-sm text-muted-foreground">
+                                                Scan with a phone camera to join {result.ssid}. The code contains the
+                                                passphrase, so only share it with people who may use the network.
+                                            </p>
+                                        </div>
+                                    </div>
+                                )}
+
                                 <div>
                                     <h3 className="text-lg font-semibold mb-4">Setup Steps</h3>
                                     <div className="border p-4 space-y-2 max-h-64 overflow-y-auto">
@@ -293,11 +494,60 @@ const WirelessNetworkSetup = () => {
                                         {result.ap_commands}
                                     </pre>
                                 </div>
+
+                                <div>
+                                    <div className="flex items-center justify-between mb-2">
+                                        <h3 className="text-lg font-semibold">hostapd.conf</h3>
+                                        <Button
+                                            variant="outline"
+                                            size="sm"
+                                            onClick={() => copyToClipboard(result.hostapd_conf, "hostapd.conf")}
+                                        >
+                                            <Copy className="w-4 h-4 mr-2" />
+                                            Copy
+                                        </Button>
+                                    </div>
+                                    <pre className="border p-4 bg-muted rounded text-xs overflow-x-auto max-h-64 overflow-y-auto">
+                                        {result.hostapd_conf}
+                                    </pre>
+                                </div>
+
+                                <div>
+                                    <div className="flex items-center justify-between mb-2">
+                                        <div className="flex items-center gap-2">
+                                            <h3 className="text-lg font-semibold">DHCP for the Access Point</h3>
+                                            <select
+                                                value={linuxDhcp}
+                                                onChange={(e) => setLinuxDhcp(e.target.value as "dnsmasq" | "kea")}
+                                                className="h-8 border border-input bg-transparent px-2 text-sm shadow-sm"
+                                            >
+                                                <option value="dnsmasq">dnsmasq</option>
+                                                <option value="kea">Kea DHCPv4</option>
+                                            </select>
+                                        </div>
+                                        <Button
+                                            variant="outline"
+                                            size="sm"
+                                            onClick={() =>
+                                                copyToClipboard(
+                                                    linuxDhcp === "kea" ? result.kea_config : result.dnsmasq_conf,
+                                                    "DHCP configuration",
+                                                )
+                                            }
+                                        >
+                                            <Copy className="w-4 h-4 mr-2" />
+                                            Copy
+                                        </Button>
+                                    </div>
+                                    <pre className="border p-4 bg-muted rounded text-xs overflow-x-auto max-h-64 overflow-y-auto">
+                                        {linuxDhcp === "kea" ? result.kea_config : result.dnsmasq_conf}
+                                    </pre>
+                                </div>
                             </>
                         ) : (
                             <div className="border p-4 text-center text-muted-foreground">
                                 Enter wireless network settings and click Generate Configuration to see the setup guide
-                                and Cisco commands
+                                and Cisco commands, plus hostapd and DHCP files for a Linux access point
                             </div>
                         )}
                     </div>